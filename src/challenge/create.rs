@@ -1,21 +1,32 @@
 use wasmlanche::{public, Context, Address};
-use crate::types::{Challenge, ChallengeType, ChallengeStatus, ChallengeEvidence};
+use crate::{
+    types::{Challenge, ChallengeType, ChallengeStatus, Phase},
+    state::*,
+    challenge::types::{ChallengeEvidence, is_health_degraded},
+    error::{Error, Result},
+    CHALLENGE_REVEAL_WINDOW,
+};
+use sha2::{Digest, Sha256};
 
 #[public]
-pub async fn challenge_executor(
+pub fn challenge_executor(
     context: &mut Context,
     executor: Address,
     challenge_type: ChallengeType,
     evidence_requirements: ChallengeEvidence,
-) -> Result<Challenge, Error> {
+) -> Result<Challenge> {
     let caller = context.actor();
+    ensure_not_self_challenge(caller, executor)?;
     ensure_watchdog(context, caller)?;
+    ensure_challenge_target(context, executor, &challenge_type)?;
+    ensure_challenge_capacity(context, 1)?;
+    validate_evidence(&challenge_type, &evidence_requirements)?;
 
     // Create challenge with Enarx-specific requirements
     let challenge = match evidence_requirements {
         ChallengeEvidence::AttestationEvidence { .. } => {
             Challenge {
-                id: generate_challenge_id(),
+                id: generate_challenge_id(context),
                 challenger: caller,
                 challenged: executor,
                 challenge_type: ChallengeType::Attestation,
@@ -30,7 +41,7 @@ pub async fn challenge_executor(
         },
         ChallengeEvidence::ExecutionEvidence { .. } => {
             Challenge {
-                id: generate_challenge_id(),
+                id: generate_challenge_id(context),
                 challenger: caller,
                 challenged: executor,
                 challenge_type: ChallengeType::Execution,
@@ -42,6 +53,26 @@ pub async fn challenge_executor(
                 deadline: context.timestamp() + CHALLENGE_TIMEOUT,
             }
         },
+        ChallengeEvidence::HealthEvidence { memory_usage, failed_self_checks } => {
+            // The reported metrics are kept as the challenge data so the
+            // verdict can be checked against what the watchdog observed
+            let mut challenge_data = Vec::with_capacity(20);
+            challenge_data.extend_from_slice(&(memory_usage.used as u64).to_le_bytes());
+            challenge_data.extend_from_slice(&(memory_usage.total as u64).to_le_bytes());
+            challenge_data.extend_from_slice(&failed_self_checks.to_le_bytes());
+
+            Challenge {
+                id: generate_challenge_id(context),
+                challenger: caller,
+                challenged: executor,
+                challenge_type: ChallengeType::HealthDegradation,
+                challenge_data,
+                response_deadline: context.timestamp()
+                    + load_system_config(context).challenge_response_window,
+                status: ChallengeStatus::Pending,
+                verification_proofs: Vec::new(),
+            }
+        },
     };
 
     // Store challenge
@@ -50,11 +81,275 @@ pub async fn challenge_executor(
     Ok(challenge)
 }
 
-fn ensure_watchdog(context: &Context, address: Address) -> Result<(), Error> {
+/// Opens challenges against several executors in one call. The caller and
+/// every target are validated up front so either all challenges are opened,
+/// with sequential ids, or none are.
+#[public]
+pub fn challenge_executors_batch(
+    context: &mut Context,
+    targets: Vec<(Address, ChallengeType, Vec<u8>)>,
+) -> Result<Vec<Challenge>> {
+    let caller = context.actor();
+    ensure_watchdog(context, caller)?;
+
+    if targets.is_empty() {
+        return Err(Error::challenge_error("no challenge targets"));
+    }
+    for (executor, challenge_type, _) in &targets {
+        ensure_not_self_challenge(caller, *executor)?;
+        ensure_challenge_target(context, *executor, challenge_type)?;
+    }
+    if targets.iter().any(|(_, _, challenge_data)| challenge_data.is_empty()) {
+        return Err(Error::challenge_error("challenge data missing"));
+    }
+    ensure_challenge_capacity(context, targets.len())?;
+
+    let response_deadline = context.timestamp() + load_system_config(context).challenge_response_window;
+    let mut challenges = Vec::with_capacity(targets.len());
+
+    for (executor, challenge_type, challenge_data) in targets {
+        let challenge = Challenge {
+            id: generate_challenge_id(context),
+            challenger: caller,
+            challenged: executor,
+            challenge_type,
+            challenge_data,
+            response_deadline,
+            status: ChallengeStatus::Pending,
+            verification_proofs: Vec::new(),
+        };
+
+        store_challenge(context, &challenge)?;
+        challenges.push(challenge);
+    }
+
+    Ok(challenges)
+}
+
+/// Opens an attestation challenge against an executor that did not renew
+/// its attestation before the forced re-attestation deadline
+#[public]
+pub fn challenge_missed_reattestation(
+    context: &mut Context,
+    executor: Address,
+) -> Result<Challenge> {
+    let caller = context.actor();
+    ensure_not_self_challenge(caller, executor)?;
+    ensure_watchdog(context, caller)?;
+    ensure_challenge_target(context, executor, &ChallengeType::Attestation)?;
+    ensure_challenge_capacity(context, 1)?;
+
+    let deadline = context
+        .get(ReattestationDeadline())
+        .expect("state corrupt")
+        .ok_or(Error::challenge_error("no reattestation in progress"))?;
+    if context.timestamp() <= deadline {
+        return Err(Error::challenge_error("reattestation deadline not reached"));
+    }
+
+    let attested = context
+        .get(AttestationStatus(executor))
+        .expect("state corrupt")
+        .unwrap_or(false);
+    if attested {
+        return Err(Error::challenge_error("executor has re-attested"));
+    }
+
+    let challenge = Challenge {
+        id: generate_challenge_id(context),
+        challenger: caller,
+        challenged: executor,
+        challenge_type: ChallengeType::Attestation,
+        challenge_data: deadline.to_le_bytes().to_vec(),
+        response_deadline: context.timestamp() + load_system_config(context).challenge_response_window,
+        status: ChallengeStatus::Pending,
+        verification_proofs: Vec::new(),
+    };
+
+    store_challenge(context, &challenge)?;
+
+    Ok(challenge)
+}
+
+/// Challenges a misbehaving watchdog. Executors and other watchdogs may open
+/// one; the system stays in `ChallengeWatchdog` until it is resolved.
+#[public]
+pub fn challenge_watchdog(
+    context: &mut Context,
+    watchdog: Address,
+    evidence: Vec<u8>,
+) -> Result<Challenge> {
+    let caller = context.actor();
+    ensure_not_self_challenge(caller, watchdog)?;
+    if !is_executor(context, caller) {
+        ensure_watchdog(context, caller)?;
+    }
+    ensure_watchdog(context, watchdog)
+        .map_err(|_| Error::challenge_error("target is not a watchdog"))?;
+    if evidence.is_empty() {
+        return Err(Error::challenge_error("challenge data missing"));
+    }
+    ensure_challenge_capacity(context, 1)?;
+
+    let phase = context
+        .get(CurrentPhase())
+        .expect("state corrupt")
+        .unwrap_or(Phase::None);
+    if !matches!(phase, Phase::Executing | Phase::ChallengeWatchdog) {
+        return Err(Error::challenge_error("watchdog challenges require Executing phase"));
+    }
+
+    let challenge = Challenge {
+        id: generate_challenge_id(context),
+        challenger: caller,
+        challenged: watchdog,
+        challenge_type: ChallengeType::WatchdogMisbehavior,
+        challenge_data: evidence,
+        response_deadline: context.timestamp() + load_system_config(context).challenge_response_window,
+        status: ChallengeStatus::Pending,
+        verification_proofs: Vec::new(),
+    };
+
+    store_challenge(context, &challenge)?;
+    if phase != Phase::ChallengeWatchdog {
+        context
+            .store_by_key(PhaseBeforeWatchdogChallenge(), phase)
+            .map_err(|_| Error::StateError("failed to record prior phase".into()))?;
+    }
+    context
+        .store_by_key(CurrentPhase(), Phase::ChallengeWatchdog)
+        .map_err(|_| Error::StateError("failed to update phase".into()))?;
+
+    Ok(challenge)
+}
+
+/// Commits to a challenge without revealing its target or evidence, so the
+/// challenged executor cannot react before the challenge opens
+#[public]
+pub fn commit_challenge(context: &mut Context, commitment_hash: [u8; 32]) -> Result<()> {
+    let caller = context.actor();
+    ensure_watchdog(context, caller)?;
+
+    // Unrevealed commitments past the reveal window can never open a challenge
+    let now = context.timestamp();
+    let mut commitments = context
+        .get(ChallengeCommitments(caller))
+        .expect("state corrupt")
+        .unwrap_or_default();
+    commitments.retain(|(_, committed_at)| now <= committed_at + CHALLENGE_REVEAL_WINDOW);
+
+    if commitments.iter().any(|(hash, _)| *hash == commitment_hash) {
+        return Err(Error::challenge_error("commitment already exists"));
+    }
+    if commitments.len() >= load_system_config(context).max_active_challenges {
+        return Err(Error::challenge_error("too many pending commitments"));
+    }
+    commitments.push((commitment_hash, now));
+
+    context
+        .store_by_key(ChallengeCommitments(caller), commitments)
+        .map_err(|_| Error::StateError("failed to store commitment".into()))
+}
+
+/// Reveals a committed challenge and opens it. The revealed fields must hash
+/// to a commitment made by the caller within `CHALLENGE_REVEAL_WINDOW`.
+#[public]
+pub fn reveal_challenge(
+    context: &mut Context,
+    executor: Address,
+    challenge_type: ChallengeType,
+    evidence: Vec<u8>,
+    salt: Vec<u8>,
+) -> Result<Challenge> {
+    let caller = context.actor();
+    ensure_watchdog(context, caller)?;
+
+    let commitment_hash = challenge_commitment(executor, &challenge_type, &evidence, &salt);
+    let mut commitments = context
+        .get(ChallengeCommitments(caller))
+        .expect("state corrupt")
+        .unwrap_or_default();
+    let position = commitments
+        .iter()
+        .position(|(hash, _)| *hash == commitment_hash)
+        .ok_or(Error::challenge_error("evidence does not match commitment"))?;
+    let (_, committed_at) = commitments[position];
+
+    if context.timestamp() > committed_at + CHALLENGE_REVEAL_WINDOW {
+        commitments.remove(position);
+        context
+            .store_by_key(ChallengeCommitments(caller), commitments)
+            .map_err(|_| Error::StateError("failed to clear commitment".into()))?;
+        return Err(Error::challenge_error("reveal window elapsed"));
+    }
+    ensure_not_self_challenge(caller, executor)?;
+    ensure_challenge_target(context, executor, &challenge_type)?;
+    if evidence.is_empty() {
+        return Err(Error::challenge_error("challenge data missing"));
+    }
+    ensure_challenge_capacity(context, 1)?;
+
+    commitments.remove(position);
+    context
+        .store_by_key(ChallengeCommitments(caller), commitments)
+        .map_err(|_| Error::StateError("failed to clear commitment".into()))?;
+
+    let challenge = Challenge {
+        id: generate_challenge_id(context),
+        challenger: caller,
+        challenged: executor,
+        challenge_type,
+        challenge_data: evidence,
+        response_deadline: context.timestamp() + load_system_config(context).challenge_response_window,
+        status: ChallengeStatus::Pending,
+        verification_proofs: Vec::new(),
+    };
+
+    store_challenge(context, &challenge)?;
+
+    Ok(challenge)
+}
+
+/// Commitment over a challenge's target, type, evidence and salt. Each field
+/// is length-prefixed so shifting bytes between evidence and salt changes the
+/// commitment.
+pub fn challenge_commitment(
+    executor: Address,
+    challenge_type: &ChallengeType,
+    evidence: &[u8],
+    salt: &[u8],
+) -> [u8; 32] {
+    let type_tag: u8 = match challenge_type {
+        ChallengeType::Attestation => 0,
+        ChallengeType::Execution => 1,
+        ChallengeType::StateVerification => 2,
+        ChallengeType::HeartbeatMissed => 3,
+        ChallengeType::HealthDegradation => 4,
+        ChallengeType::WatchdogMisbehavior => 5,
+    };
+
+    let mut hasher = Sha256::new();
+    for field in [executor.to_string().as_bytes(), &[type_tag][..], evidence, salt] {
+        hasher.update((field.len() as u32).to_le_bytes());
+        hasher.update(field);
+    }
+    hasher.finalize().into()
+}
+
+/// A participant challenging itself could occupy a challenge slot to dodge a
+/// real one
+fn ensure_not_self_challenge(challenger: Address, challenged: Address) -> Result<()> {
+    if challenger == challenged {
+        return Err(Error::challenge_error("cannot self-challenge"));
+    }
+    Ok(())
+}
+
+fn ensure_watchdog(context: &Context, address: Address) -> Result<()> {
     let watchdog_pool = context
         .get(WatchdogPool())
         .expect("state corrupt")
-        .ok_or(Error::StateError("watchdog pool not initialized"))?;
+        .ok_or(Error::StateError("watchdog pool not initialized".into()))?;
 
     if !watchdog_pool.contains(&address) {
         return Err(Error::Unauthorized("not a watchdog".into()));
@@ -63,10 +358,150 @@ fn ensure_watchdog(context: &Context, address: Address) -> Result<(), Error> {
     Ok(())
 }
 
-#[derive(Debug)]
-pub enum Error {
-    StateError(String),
-    Unauthorized(String),
-    StorageError(String),
+/// A challenge against an address outside the pools could never be resolved.
+/// Watchdog misbehavior challenges target watchdogs; all others target a
+/// current executor.
+fn ensure_challenge_target(
+    context: &Context,
+    target: Address,
+    challenge_type: &ChallengeType,
+) -> Result<()> {
+    if *challenge_type == ChallengeType::WatchdogMisbehavior {
+        return ensure_watchdog(context, target)
+            .map_err(|_| Error::challenge_error("target not a watchdog"));
+    }
+
+    if !is_executor(context, target) {
+        return Err(Error::challenge_error("target not an executor"));
+    }
+
+    Ok(())
+}
+
+fn is_executor(context: &Context, address: Address) -> bool {
+    context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .map(|pool| pool.sgx_executor == Some(address) || pool.sev_executor == Some(address))
+        .unwrap_or(false)
+}
+
+/// Ensures the evidence shape matches the requested challenge type and carries data
+fn validate_evidence(challenge_type: &ChallengeType, evidence: &ChallengeEvidence) -> Result<()> {
+    match (challenge_type, evidence) {
+        (
+            ChallengeType::Attestation,
+            ChallengeEvidence::AttestationEvidence { attestation_report, drawbridge_token, .. },
+        ) => {
+            if attestation_report.measurement.is_empty() || attestation_report.keep_id.is_empty() {
+                return Err(Error::challenge_error("attestation evidence missing report data"));
+            }
+            if drawbridge_token.token.is_empty() {
+                return Err(Error::challenge_error("attestation evidence missing drawbridge token"));
+            }
+        },
+        (
+            ChallengeType::Execution,
+            ChallengeEvidence::ExecutionEvidence { result_hash, execution_proof, .. },
+        ) => {
+            if result_hash.is_empty() || execution_proof.is_empty() {
+                return Err(Error::challenge_error("execution evidence missing result hash or proof"));
+            }
+        },
+        (
+            ChallengeType::HealthDegradation,
+            ChallengeEvidence::HealthEvidence { memory_usage, failed_self_checks },
+        ) => {
+            if memory_usage.total == 0 {
+                return Err(Error::challenge_error("health evidence missing memory stats"));
+            }
+            if !is_health_degraded(memory_usage, *failed_self_checks) {
+                return Err(Error::challenge_error("health evidence does not show degradation"));
+            }
+        },
+        _ => {
+            return Err(Error::challenge_error("evidence does not match challenge type"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects new challenges that would exceed the configured number of
+/// unresolved challenges, or that a degraded watchdog pool couldn't vote on
+fn ensure_challenge_capacity(context: &Context, additional: usize) -> Result<()> {
+    if context.get(WatchdogPoolDegraded()).expect("state corrupt").unwrap_or(false) {
+        return Err(Error::challenge_error("watchdog pool degraded"));
+    }
+
+    let max_active = load_system_config(context).max_active_challenges;
+    let unresolved = unresolved_challenges(context).len();
+
+    if unresolved + additional > max_active {
+        return Err(Error::challenge_error("too many active challenges"));
+    }
+
+    Ok(())
 }
 
+/// Opens a challenge raised by the contract itself rather than a watchdog,
+/// e.g. when an automatic safeguard trips
+pub fn open_system_challenge(
+    context: &mut Context,
+    challenged: Address,
+    challenge_type: ChallengeType,
+    challenge_data: Vec<u8>,
+) -> Result<Challenge> {
+    ensure_challenge_capacity(context, 1)?;
+
+    let challenge = Challenge {
+        id: generate_challenge_id(context),
+        challenger: context.contract_address(),
+        challenged,
+        challenge_type,
+        challenge_data,
+        response_deadline: context.timestamp() + load_system_config(context).challenge_response_window,
+        status: ChallengeStatus::Pending,
+        verification_proofs: Vec::new(),
+    };
+
+    store_challenge(context, &challenge)?;
+
+    Ok(challenge)
+}
+
+fn generate_challenge_id(context: &mut Context) -> u128 {
+    let challenge_id = context
+        .get(ChallengeCount())
+        .expect("state corrupt")
+        .unwrap_or(0)
+        + 1;
+
+    context
+        .store_by_key(ChallengeCount(), challenge_id)
+        .expect("failed to update challenge count");
+
+    challenge_id
+}
+
+fn store_challenge(context: &mut Context, challenge: &Challenge) -> Result<()> {
+    let mut active_challenges = context
+        .get(ActiveChallenges())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    active_challenges.push(challenge.id);
+
+    context
+        .store((
+            (Challenge(challenge.id), challenge.clone()),
+            (ActiveChallenges(), active_challenges),
+        ))
+        .map_err(|_| Error::StateError("failed to store challenge".into()))?;
+
+    update_global_state(
+        context,
+        "store_challenge",
+        &[&challenge.id.to_le_bytes(), challenge.challenged.to_string().as_bytes()],
+    );
+    Ok(())
+}
@@ -3,7 +3,12 @@ use crate::{
     types::*,
     state::*,
     challenge::types::*,
-    core::utils::verify_attestation_report,
+    challenge::verification::notify_challenge_resolved,
+    core::{
+        utils::{verify_attestation_report, verify_signature, quote_report_data},
+        get_enclave_type,
+    },
+    CHALLENGE_DEADLINE_EXTENSION,
 };
 
 #[public]
@@ -34,13 +39,15 @@ pub fn respond_to_challenge(
 
     // Update challenge status
     challenge.status = ChallengeStatus::Responded;
-    challenge.verification_proofs.push(response_data);
+    challenge.verification_proofs.push(encode_proof(context, response_data.clone()));
 
     // Store updated challenge
     context
         .store_by_key(Challenge(challenge_id), challenge.clone())
         .expect("failed to update challenge");
 
+    update_global_state(context, "respond_to_challenge", &[&challenge_id.to_le_bytes(), &response_data]);
+
     // Update operator stats
     if let Some(mut operator) = context
         .get(OperatorData(caller.to_string()))
@@ -58,24 +65,185 @@ pub fn respond_to_challenge(
     }
 }
 
+/// Records a partial proof from the challenged party and grants a single
+/// bounded deadline extension so multi-part proofs can be completed
+#[public]
+pub fn extend_challenge_deadline(
+    context: &mut Context,
+    challenge_id: u128,
+    partial_proof: Vec<u8>,
+) -> u64 {
+    ensure_initialized(context);
+
+    let caller = context.actor();
+    let timestamp = context.timestamp();
+
+    let mut challenge = context
+        .get(Challenge(challenge_id))
+        .expect("state corrupt")
+        .expect("challenge not found");
+
+    assert!(challenge.challenged == caller, "unauthorized responder");
+    assert!(challenge.status == ChallengeStatus::Pending, "challenge not pending");
+    assert!(timestamp <= challenge.response_deadline, "challenge deadline passed");
+    assert!(!partial_proof.is_empty(), "empty partial proof");
+
+    let already_extended = context
+        .get(ChallengeDeadlineExtended(challenge_id))
+        .expect("state corrupt")
+        .unwrap_or(false);
+    assert!(!already_extended, "deadline already extended");
+
+    let partial_proof = encode_proof(context, partial_proof);
+    challenge.response_deadline += CHALLENGE_DEADLINE_EXTENSION;
+    let new_deadline = challenge.response_deadline;
+
+    context
+        .store((
+            (Challenge(challenge_id), challenge),
+            (ChallengeDeadlineExtended(challenge_id), true),
+            (ChallengePartialProof(challenge_id), partial_proof),
+        ))
+        .expect("failed to extend challenge deadline");
+
+    new_deadline
+}
+
+/// Answers a health-degradation challenge with the keep's current health.
+/// The metrics must be attested: the keep presents its Drawbridge token and a
+/// fresh attestation whose report data commits to `health_report_data`.
+/// A passing report verifies the challenge; a degraded one fails it and
+/// removes the executor so its slot can be refilled.
+#[public]
+pub fn respond_to_health_challenge(
+    context: &mut Context,
+    challenge_id: u128,
+    memory_usage: MemoryStats,
+    failed_self_checks: u32,
+    attestation_report: Vec<u8>,
+    drawbridge_token: Vec<u8>,
+) -> bool {
+    ensure_initialized(context);
+
+    let caller = context.actor();
+    let timestamp = context.timestamp();
+
+    let mut challenge = context
+        .get(Challenge(challenge_id))
+        .expect("state corrupt")
+        .expect("challenge not found");
+
+    assert!(challenge.challenged == caller, "unauthorized responder");
+    assert!(
+        challenge.challenge_type == ChallengeType::HealthDegradation,
+        "not a health challenge"
+    );
+    assert!(challenge.status == ChallengeStatus::Pending, "challenge not pending");
+    assert!(timestamp <= challenge.response_deadline, "challenge deadline passed");
+    assert!(memory_usage.total > 0, "missing memory stats");
+    verify_health_attestation(
+        context,
+        &challenge,
+        &memory_usage,
+        failed_self_checks,
+        &attestation_report,
+        &drawbridge_token,
+    );
+
+    let healthy = !is_health_degraded(&memory_usage, failed_self_checks);
+    if healthy {
+        challenge.status = ChallengeStatus::Verified;
+    } else {
+        challenge.status = ChallengeStatus::Failed;
+        handle_failed_challenge(context, &challenge);
+    }
+    notify_challenge_resolved(context, &challenge);
+
+    context
+        .store_by_key(Challenge(challenge_id), challenge)
+        .expect("failed to update challenge");
+
+    update_global_state(
+        context,
+        "respond_to_health_challenge",
+        &[
+            &challenge_id.to_le_bytes(),
+            &memory_usage.used.to_le_bytes(),
+            &memory_usage.total.to_le_bytes(),
+            &failed_self_checks.to_le_bytes(),
+        ],
+    );
+
+    healthy
+}
+
+/// Checks that the challenged keep attested the health metrics it reported
+fn verify_health_attestation(
+    context: &mut Context,
+    challenge: &Challenge,
+    memory_usage: &MemoryStats,
+    failed_self_checks: u32,
+    attestation_report: &[u8],
+    drawbridge_token: &[u8],
+) {
+    let responder = challenge.challenged;
+    assert!(
+        drawbridge_token_matches(context, responder, drawbridge_token),
+        "invalid drawbridge token"
+    );
+
+    let keep_id = context
+        .get(KeepId(responder))
+        .expect("state corrupt")
+        .expect("keep not registered");
+    let enclave_type = get_enclave_type(context, responder).expect("enclave type not registered");
+    let registered_measurement = context.get(KeepMeasurement(responder)).expect("state corrupt");
+    if let Err(reason) = verify_attestation_report(
+        context,
+        attestation_report,
+        drawbridge_token,
+        &keep_id,
+        enclave_type.clone(),
+        registered_measurement.as_deref(),
+    ) {
+        panic!("invalid attestation: {}", reason);
+    }
+
+    let expected = health_report_data(challenge.id, memory_usage, failed_self_checks);
+    let attested = quote_report_data(attestation_report, &enclave_type)
+        .map_or(false, |report_data| report_data.starts_with(&expected));
+    assert!(attested, "health metrics not attested");
+}
+
+/// Requires the configured number of distinct watchdogs to have signed the proof
 fn verify_challenge_proof(
     context: &mut Context,
     challenge: &Challenge,
     proof: &ChallengeProof,
-) -> bool {
-    // Verify proof signatures from witnesses
+) {
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+    assert!(proof.challenge_id == challenge.id, "proof for another challenge");
+    let proof_hash = witness_message(challenge.id, challenge.challenged, &proof.proof_data);
+
+    let mut witnesses = Vec::new();
     for (witness, signature) in &proof.witness_signatures {
-        // Verify witness is a valid watchdog
-        let watchdog_pool = context
-            .get(WatchdogPool())
-            .expect("state corrupt")
-            .expect("watchdog pool not initialized");
-
-        if !watchdog_pool.watchdogs.iter().any(|(addr, _)| addr == witness) {
-            return false;
-        }
+        assert!(!witnesses.contains(witness), "duplicate witness");
+        assert!(
+            watchdog_pool.watchdogs.iter().any(|(addr, _)| addr == witness),
+            "witness not a watchdog"
+        );
+        assert!(
+            verify_signature(&proof_hash, signature, &witness.to_string()),
+            "invalid witness signature"
+        );
+        witnesses.push(*witness);
     }
-    true
+
+    let required = load_system_config(context).min_proof_witnesses;
+    assert!(witnesses.len() >= required, "insufficient witnesses");
 }
 
 fn verify_attestation_challenge(
@@ -83,8 +251,28 @@ fn verify_attestation_challenge(
     challenge: &Challenge,
     proof: &ChallengeProof,
 ) {
-    // Verify attestation-specific proof
-    let attestation_valid = verify_attestation_report(context, &proof.proof_data, &[]);
+    // Verify attestation-specific proof against the token the responder
+    // presents, which must match the stored token or its commitment
+    let keep_id = context
+        .get(KeepId(challenge.challenged))
+        .expect("state corrupt")
+        .unwrap_or_default();
+    let registered_measurement = context
+        .get(KeepMeasurement(challenge.challenged))
+        .expect("state corrupt");
+    let token_valid = drawbridge_token_matches(context, challenge.challenged, &proof.drawbridge_token);
+    let attestation_valid = match get_enclave_type(context, challenge.challenged) {
+        Some(enclave_type) if token_valid => verify_attestation_report(
+            context,
+            &proof.proof_data,
+            &proof.drawbridge_token,
+            &keep_id,
+            enclave_type,
+            registered_measurement.as_deref(),
+        )
+        .is_ok(),
+        _ => false,
+    };
     
     if attestation_valid {
         // Update attestation status
@@ -97,6 +285,8 @@ fn verify_attestation_challenge(
 }
 
 fn handle_failed_challenge(context: &mut Context, challenge: &Challenge) {
+    mark_executor_failed(context, challenge.challenged);
+
     let mut executor_pool = context
         .get(ExecutorPool())
         .expect("state corrupt")
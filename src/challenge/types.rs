@@ -1,4 +1,5 @@
-use crate::types::*;
+use crate::{types::*, HEALTH_MEMORY_THRESHOLD_PERCENT};
+use sha2::{Digest, Sha256};
 
 #[derive(Debug)]
 pub struct ChallengeContext {
@@ -27,6 +28,43 @@ pub enum ChallengeEvidence {
         execution_proof: Vec<u8>,
         keep_measurement: Vec<u8>,
     },
+    HealthEvidence {
+        memory_usage: MemoryStats,
+        failed_self_checks: u32,
+    },
+}
+
+/// A keep is degraded when memory use crosses the threshold or any self-check failed
+pub fn is_health_degraded(memory_usage: &MemoryStats, failed_self_checks: u32) -> bool {
+    failed_self_checks > 0
+        || memory_usage.used as u128 * 100
+            >= memory_usage.total as u128 * HEALTH_MEMORY_THRESHOLD_PERCENT as u128
+}
+
+/// Digest a keep must place in its quote's report data to attest the health
+/// metrics it answers a challenge with
+pub fn health_report_data(
+    challenge_id: u128,
+    memory_usage: &MemoryStats,
+    failed_self_checks: u32,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(challenge_id.to_le_bytes());
+    hasher.update((memory_usage.used as u64).to_le_bytes());
+    hasher.update((memory_usage.total as u64).to_le_bytes());
+    hasher.update(failed_self_checks.to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Digest a witness signs to endorse a challenge proof. Binding the challenge
+/// id and the challenged address keeps an endorsement from being replayed
+/// against another challenge carrying the same proof bytes.
+pub fn witness_message(challenge_id: u128, challenged: Address, proof_data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(challenge_id.to_le_bytes());
+    hasher.update(challenged.to_string().as_bytes());
+    hasher.update(proof_data);
+    hasher.finalize().into()
 }
 
 #[derive(Debug)]
@@ -1,7 +1,12 @@
-use wasmlanche::{public, Context};
+use wasmlanche::{public, Context, Address};
 use crate::{
     types::*,
     state::*,
+    core::decompress_proof,
+    execution::settle_execution_dispute,
+    external::slash_stake,
+    MAX_CHALLENGE_PAGE_SIZE,
+    MIN_WATCHDOGS,
 };
 
 #[public]
@@ -37,20 +42,49 @@ pub fn verify_challenge_response(
         "challenge not in response phase"
     );
 
+    // Each watchdog votes once; the outcome is decided by the tally, not the last vote
+    assert!(
+        context.get(ChallengeVote(challenge_id, caller)).expect("state corrupt").is_none(),
+        "watchdog already voted"
+    );
+    let (mut approvals, mut rejections) = context
+        .get(ChallengeVoteTally(challenge_id))
+        .expect("state corrupt")
+        .unwrap_or((0, 0));
+    if verification_result {
+        approvals += 1;
+    } else {
+        rejections += 1;
+    }
+    context
+        .store((
+            (ChallengeVote(challenge_id, caller), verification_result),
+            (ChallengeVoteTally(challenge_id), (approvals, rejections)),
+        ))
+        .expect("failed to record vote");
+
     // Add verification proof
-    challenge.verification_proofs.push(verification_proof);
+    challenge.verification_proofs.push(encode_proof(context, verification_proof));
+
+    // Quorum counts votes cast, not proofs: the response itself may carry proofs
+    let config = load_system_config(context);
+    let required_verifications = required_verifications_for(watchdog_pool.watchdogs.len(), &config);
+    if approvals + rejections >= required_verifications {
+        let verified = match approvals.cmp(&rejections) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => config.vote_tie_policy == VoteTiePolicy::FailOpen,
+        };
 
-    // Check if we have enough verifications
-    let required_verifications = (watchdog_pool.watchdogs.len() * 2) / 3 + 1;
-    if challenge.verification_proofs.len() >= required_verifications {
         // Process verification result
-        if verification_result {
+        if verified {
             challenge.status = ChallengeStatus::Verified;
             transition_to_executing(context);
         } else {
             challenge.status = ChallengeStatus::Failed;
             handle_challenge_failure(context, &challenge);
         }
+        notify_challenge_resolved(context, &challenge);
     }
 
     // Store updated challenge
@@ -59,7 +93,50 @@ pub fn verify_challenge_response(
         .expect("failed to update challenge");
 }
 
+/// Number of watchdog verifications needed to resolve a challenge with the
+/// current pool size and quorum policy
+#[public]
+pub fn get_required_verifications(context: &mut Context) -> usize {
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+
+    required_verifications_for(watchdog_pool.watchdogs.len(), &load_system_config(context))
+}
+
+/// Whether the current watchdog pool can resolve a challenge: it must hold at
+/// least `MIN_WATCHDOGS` members and, when enclave diversity is required,
+/// span more than one enclave type so a quorum can include both
+#[public]
+pub fn can_reach_quorum(context: &mut Context) -> bool {
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+    let config = load_system_config(context);
+
+    let watchdog_count = watchdog_pool.watchdogs.len();
+    if watchdog_count < MIN_WATCHDOGS
+        || required_verifications_for(watchdog_count, &config) > watchdog_count
+    {
+        return false;
+    }
+
+    !config.require_enclave_diversity
+        || watchdog_pool
+            .watchdogs
+            .iter()
+            .any(|(_, enclave_type)| *enclave_type != watchdog_pool.watchdogs[0].1)
+}
+
+fn required_verifications_for(watchdog_count: usize, config: &SystemConfig) -> usize {
+    (watchdog_count * config.quorum_numerator) / config.quorum_denominator + 1
+}
+
 fn handle_challenge_failure(context: &mut Context, challenge: &Challenge) {
+    mark_executor_failed(context, challenge.challenged);
+
     let mut executor_pool = context
         .get(ExecutorPool())
         .expect("state corrupt")
@@ -81,10 +158,175 @@ fn handle_challenge_failure(context: &mut Context, challenge: &Challenge) {
 
     // If no executors remain, transition to crashed phase
     if executor_pool.sgx_executor.is_none() && executor_pool.sev_executor.is_none() {
+        enter_crashed_phase(context);
+    }
+}
+
+/// Resolves a watchdog challenge. A watchdog found to have misbehaved loses
+/// its stake and is removed from the pool; if that leaves fewer than
+/// `MIN_WATCHDOGS`, the pool is marked degraded and new challenges are
+/// refused until more watchdogs register. Once no watchdog challenges remain
+/// open the system returns to the phase the first challenge replaced. Only
+/// callable by governance.
+#[public]
+pub fn resolve_watchdog_challenge(
+    context: &mut Context,
+    challenge_id: u128,
+    misbehaved: bool,
+) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let mut challenge = context
+        .get(Challenge(challenge_id))
+        .expect("state corrupt")
+        .expect("challenge not found");
+
+    assert!(
+        challenge.challenge_type == ChallengeType::WatchdogMisbehavior,
+        "not a watchdog challenge"
+    );
+    assert!(
+        matches!(challenge.status, ChallengeStatus::Pending | ChallengeStatus::Responded),
+        "challenge already resolved"
+    );
+
+    if misbehaved {
+        challenge.status = ChallengeStatus::Failed;
+
+        slash_stake(context, challenge.challenged);
+
+        let mut watchdog_pool = context
+            .get(WatchdogPool())
+            .expect("state corrupt")
+            .expect("watchdog pool not initialized");
+        watchdog_pool.watchdogs.retain(|(addr, _)| *addr != challenge.challenged);
+        if watchdog_pool.watchdogs.len() < MIN_WATCHDOGS {
+            context
+                .store_by_key(WatchdogPoolDegraded(), true)
+                .expect("failed to mark watchdog pool degraded");
+        }
+        context
+            .store_by_key(WatchdogPool(), watchdog_pool)
+            .expect("failed to update watchdog pool");
+
+        clear_participant_state(context, challenge.challenged);
+    } else {
+        challenge.status = ChallengeStatus::Verified;
+    }
+
+    notify_challenge_resolved(context, &challenge);
+    context
+        .store_by_key(Challenge(challenge_id), challenge)
+        .expect("failed to update challenge");
+
+    let watchdog_challenge_open = context
+        .get(ActiveChallenges())
+        .expect("state corrupt")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|id| context.get(Challenge(*id)).expect("state corrupt"))
+        .any(|challenge| {
+            challenge.challenge_type == ChallengeType::WatchdogMisbehavior
+                && matches!(challenge.status, ChallengeStatus::Pending | ChallengeStatus::Responded)
+        });
+    if !watchdog_challenge_open {
+        let previous = context
+            .get(PhaseBeforeWatchdogChallenge())
+            .expect("state corrupt")
+            .unwrap_or(Phase::Executing);
         context
-            .store_by_key(CurrentPhase(), Phase::Crashed)
-            .expect("failed to update phase");
+            .remove(PhaseBeforeWatchdogChallenge())
+            .expect("failed to clear prior phase");
+
+        // Leave any phase entered while the challenge was open in place
+        let phase = context.get(CurrentPhase()).expect("state corrupt");
+        if phase == Some(Phase::ChallengeWatchdog) {
+            context
+                .store_by_key(CurrentPhase(), previous)
+                .expect("failed to update phase");
+        }
+    }
+}
+
+/// Records the final outcome for the challenged address and emits `ChallengeResolved`
+pub fn notify_challenge_resolved(context: &mut Context, challenge: &Challenge) {
+    let resolved = ChallengeResolved {
+        challenge_id: challenge.id,
+        challenged: challenge.challenged,
+        challenge_type: challenge.challenge_type.clone(),
+        outcome: challenge.status.clone(),
+    };
+
+    context
+        .store_by_key(LastChallengeOutcome(challenge.challenged), resolved.clone())
+        .expect("failed to store challenge outcome");
+    context
+        .emit_event("ChallengeResolved", &resolved)
+        .expect("failed to emit challenge resolved event");
+
+    settle_execution_dispute(context, challenge);
+}
+
+/// Time the challenged party has left to respond, saturating at zero once the
+/// deadline has passed. `None` unless the challenge is still pending.
+#[public]
+pub fn get_challenge_time_remaining(context: &mut Context, challenge_id: u128) -> Option<u64> {
+    let challenge = context
+        .get(Challenge(challenge_id))
+        .expect("state corrupt")?;
+
+    if challenge.status != ChallengeStatus::Pending {
+        return None;
     }
+
+    Some(challenge.response_deadline.saturating_sub(context.timestamp()))
+}
+
+#[public]
+pub fn get_last_challenge_outcome(
+    context: &mut Context,
+    address: Address,
+) -> Option<ChallengeResolved> {
+    context
+        .get(LastChallengeOutcome(address))
+        .expect("state corrupt")
+}
+
+/// Returns a challenge's verification proofs decoded to their original bytes
+#[public]
+pub fn get_verification_proofs(context: &mut Context, challenge_id: u128) -> Vec<Vec<u8>> {
+    let challenge = context
+        .get(Challenge(challenge_id))
+        .expect("state corrupt")
+        .expect("challenge not found");
+
+    challenge
+        .verification_proofs
+        .iter()
+        .map(|stored| decompress_proof(stored))
+        .collect()
+}
+
+/// One row per open (pending or responded) challenge as
+/// `(id, type, status, challenged)`, starting at `offset`. At most
+/// `MAX_CHALLENGE_PAGE_SIZE` rows are returned per call.
+#[public]
+pub fn get_active_challenges(
+    context: &mut Context,
+    offset: usize,
+    limit: usize,
+) -> Vec<(u128, ChallengeType, ChallengeStatus, Address)> {
+    ensure_initialized(context);
+
+    unresolved_challenges(context)
+        .into_iter()
+        .skip(offset)
+        .take(limit.min(MAX_CHALLENGE_PAGE_SIZE))
+        .map(|challenge| {
+            (challenge.id, challenge.challenge_type, challenge.status, challenge.challenged)
+        })
+        .collect()
 }
 
 #[public]
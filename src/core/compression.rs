@@ -0,0 +1,59 @@
+/// Tag byte prefixed to every stored proof describing its encoding
+const PROOF_RAW: u8 = 0;
+const PROOF_RLE: u8 = 1;
+
+/// Encodes a proof for storage. When `compress` is set the proof is
+/// run-length encoded, falling back to raw bytes if that would not shrink it.
+pub fn compress_proof(proof: &[u8], compress: bool) -> Vec<u8> {
+    if compress {
+        let encoded = rle_encode(proof);
+        if encoded.len() < proof.len() {
+            let mut stored = Vec::with_capacity(encoded.len() + 1);
+            stored.push(PROOF_RLE);
+            stored.extend(encoded);
+            return stored;
+        }
+    }
+
+    let mut stored = Vec::with_capacity(proof.len() + 1);
+    stored.push(PROOF_RAW);
+    stored.extend_from_slice(proof);
+    stored
+}
+
+/// Recovers the original proof bytes from their stored encoding
+pub fn decompress_proof(stored: &[u8]) -> Vec<u8> {
+    match stored.split_first() {
+        Some((&PROOF_RLE, encoded)) => rle_decode(encoded),
+        Some((&PROOF_RAW, raw)) => raw.to_vec(),
+        Some(_) => panic!("unknown proof encoding"),
+        None => Vec::new(),
+    }
+}
+
+/// Encodes runs as `(count, byte)` pairs with runs capped at 255
+fn rle_encode(data: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    let mut iter = data.iter().peekable();
+
+    while let Some(&byte) = iter.next() {
+        let mut run = 1u8;
+        while run < u8::MAX && iter.peek() == Some(&&byte) {
+            iter.next();
+            run += 1;
+        }
+        encoded.push(run);
+        encoded.push(byte);
+    }
+
+    encoded
+}
+
+fn rle_decode(encoded: &[u8]) -> Vec<u8> {
+    assert!(encoded.len() % 2 == 0, "corrupt proof encoding");
+
+    encoded
+        .chunks(2)
+        .flat_map(|pair| std::iter::repeat(pair[1]).take(pair[0] as usize))
+        .collect()
+}
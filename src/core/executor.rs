@@ -2,9 +2,26 @@ use wasmlanche::{public, Context, Address};
 use crate::{
     types::*,
     state::*,
-    core::utils::verify_attestation_report,
+    core::utils::{
+        verify_attestation_report, drawbridge_token_hash, drawbridge_token_keep_id, quote_measurement,
+    },
+    external::refund_stake,
+    error::{Error, Result},
+    REATTESTATION_WINDOW,
+    MAX_HEARTBEAT_MISSES,
+    HEARTBEAT_RECOVERY_STREAK,
+    UPTIME_BASIS_POINTS,
+    MIN_WATCHDOGS,
+    PENDING_REGISTRATION_TTL,
 };
 
+/// Registers the caller as the executor for `enclave_type`. The keep
+/// measurement is read from the attested quote; one already held by another
+/// executor, watchdog or ready TEE is rejected as a likely cloned keep.
+/// When `registration_cosigns` is set the registration stays pending until
+/// enough watchdogs co-sign it with `attest_pending_executor`. A pending
+/// registration expires after `PENDING_REGISTRATION_TTL`, after which the
+/// candidate may register again.
 #[public]
 pub fn register_executor(
     context: &mut Context,
@@ -17,19 +34,129 @@ pub fn register_executor(
     ensure_phase(context, Phase::Creation);
 
     let caller = context.actor();
-    
+    ensure_attestation_report_size(context, &attestation_report);
+
     // Verify Enarx Keep attestation
+    if let Err(reason) = verify_attestation_report(
+        context,
+        &attestation_report,
+        &drawbridge_token,
+        &keep_id,
+        enclave_type.clone(),
+        None,
+    ) {
+        panic!("invalid attestation: {}", reason);
+    }
+
+    let measurement = quote_measurement(&attestation_report, &enclave_type)
+        .expect("attestation report carries no measurement");
+    if let Err(err) = ensure_unique_measurement(context, caller, &measurement) {
+        panic!("{}", err);
+    }
+
+    let registration = PendingExecutor {
+        enclave_type,
+        keep_id,
+        measurement,
+        drawbridge_token,
+        requested_at: context.timestamp(),
+        cosigners: Vec::new(),
+    };
+
+    if load_system_config(context).registration_cosigns == 0 {
+        activate_executor(context, caller, registration);
+        return;
+    }
+
+    ensure_slot_free(context, enclave_type);
+    if let Some(pending) = context.get(PendingRegistration(caller)).expect("state corrupt") {
+        assert!(is_registration_expired(context, &pending), "registration already pending");
+    }
+
+    context
+        .store_by_key(PendingRegistration(caller), registration)
+        .expect("failed to store pending registration");
+    context
+        .emit_event("ExecutorRegistrationPending", &caller)
+        .expect("failed to emit pending registration event");
+}
+
+/// Co-signs a pending executor registration. The registration activates once
+/// it carries `registration_cosigns` distinct watchdog co-signs.
+#[public]
+pub fn attest_pending_executor(context: &mut Context, candidate: Address) {
+    ensure_initialized(context);
+    ensure_phase(context, Phase::Creation);
+
+    let caller = context.actor();
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
     assert!(
-        verify_attestation_report(
-            context,
-            &attestation_report,
-            &drawbridge_token,
-            enclave_type
-        ),
-        "invalid attestation"
+        watchdog_pool.watchdogs.iter().any(|(addr, _)| *addr == caller),
+        "not a watchdog"
     );
+    assert!(caller != candidate, "cannot co-sign own registration");
 
-    let mut executor_pool = context
+    let mut registration = context
+        .get(PendingRegistration(candidate))
+        .expect("state corrupt")
+        .expect("no pending registration");
+    assert!(!is_registration_expired(context, &registration), "registration expired");
+    assert!(!registration.cosigners.contains(&caller), "already co-signed");
+    registration.cosigners.push(caller);
+
+    if registration.cosigners.len() < load_system_config(context).registration_cosigns {
+        context
+            .store_by_key(PendingRegistration(candidate), registration)
+            .expect("failed to store pending registration");
+        return;
+    }
+
+    context
+        .remove(PendingRegistration(candidate))
+        .expect("failed to clear pending registration");
+
+    // Another keep may have taken the measurement while this one waited
+    if let Err(err) = ensure_unique_measurement(context, candidate, &registration.measurement) {
+        panic!("{}", err);
+    }
+    activate_executor(context, candidate, registration);
+}
+
+/// Withdraws the caller's pending registration so it can register with a
+/// different keep or stop waiting for co-signs
+#[public]
+pub fn cancel_pending_registration(context: &mut Context) {
+    ensure_initialized(context);
+    let caller = context.actor();
+
+    assert!(
+        context.get(PendingRegistration(caller)).expect("state corrupt").is_some(),
+        "no pending registration"
+    );
+    context
+        .remove(PendingRegistration(caller))
+        .expect("failed to clear pending registration");
+    context
+        .emit_event("ExecutorRegistrationCancelled", &caller)
+        .expect("failed to emit cancelled registration event");
+}
+
+fn is_registration_expired(context: &Context, registration: &PendingExecutor) -> bool {
+    context.timestamp() > registration.requested_at + PENDING_REGISTRATION_TTL
+}
+
+#[public]
+pub fn get_pending_registration(context: &mut Context, candidate: Address) -> Option<PendingExecutor> {
+    context
+        .get(PendingRegistration(candidate))
+        .expect("state corrupt")
+}
+
+fn ensure_slot_free(context: &mut Context, enclave_type: EnclaveType) {
+    let executor_pool = context
         .get(ExecutorPool())
         .expect("state corrupt")
         .expect("executor pool not initialized");
@@ -37,11 +164,29 @@ pub fn register_executor(
     match enclave_type {
         EnclaveType::IntelSGX => {
             assert!(executor_pool.sgx_executor.is_none(), "SGX executor slot already filled");
-            executor_pool.sgx_executor = Some(caller);
         },
         EnclaveType::AMDSEV => {
             assert!(executor_pool.sev_executor.is_none(), "SEV executor slot already filled");
-            executor_pool.sev_executor = Some(caller);
+        }
+    }
+}
+
+/// Puts an attested executor into its slot and moves to `Executing` once
+/// both slots are filled
+fn activate_executor(context: &mut Context, executor: Address, registration: PendingExecutor) {
+    let mut executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    match registration.enclave_type {
+        EnclaveType::IntelSGX => {
+            assert!(executor_pool.sgx_executor.is_none(), "SGX executor slot already filled");
+            executor_pool.sgx_executor = Some(executor);
+        },
+        EnclaveType::AMDSEV => {
+            assert!(executor_pool.sev_executor.is_none(), "SEV executor slot already filled");
+            executor_pool.sev_executor = Some(executor);
         }
     }
 
@@ -49,13 +194,18 @@ pub fn register_executor(
     context
         .store((
             (ExecutorPool(), executor_pool.clone()),
-            (EnclaveType(caller), enclave_type),
-            (KeepId(caller), keep_id),              // New
-            (DrawbridgeToken(caller), drawbridge_token), // New
-            (AttestationStatus(caller), true),
-            (HeartbeatTimestamp(caller), context.timestamp()),
+            (EnclaveType(executor), registration.enclave_type),
+            (KeepId(executor), registration.keep_id),              // New
+            (KeepMeasurement(executor), registration.measurement),
+            (AttestationStatus(executor), true),
+            (LastAttestationTime(executor), context.timestamp()),
+            (HeartbeatTimestamp(executor), context.timestamp()),
+            (ExecutorState(executor), ExecutorStatus::Active),
         ))
         .expect("failed to register executor");
+    store_drawbridge_token(context, executor, registration.drawbridge_token);
+
+    update_global_state(context, "activate_executor", &[executor.to_string().as_bytes()]);
 
     if executor_pool.sgx_executor.is_some() && executor_pool.sev_executor.is_some() {
         transition_to_executing(context);
@@ -115,37 +265,116 @@ pub fn register_executor(
     }
 }
 
+/// Undoes a mistaken registration while the system is still in `Creation`,
+/// freeing the executor slot and returning any stake
 #[public]
-pub fn submit_heartbeat(context: &mut Context) {
+pub fn unregister_executor(context: &mut Context) {
     ensure_initialized(context);
+    ensure_phase(context, Phase::Creation);
+
     let caller = context.actor();
-    let timestamp = context.timestamp();
 
-    // Verify caller is either executor or watchdog
-    let executor_pool = context
+    let mut executor_pool = context
         .get(ExecutorPool())
         .expect("state corrupt")
         .expect("executor pool not initialized");
 
-    let watchdog_pool = context
-        .get(WatchdogPool())
+    if executor_pool.sgx_executor == Some(caller) {
+        executor_pool.sgx_executor = None;
+    } else if executor_pool.sev_executor == Some(caller) {
+        executor_pool.sev_executor = None;
+    } else {
+        panic!("not a registered executor");
+    }
+
+    context
+        .store_by_key(ExecutorPool(), executor_pool)
+        .expect("failed to update executor pool");
+
+    clear_participant_state(context, caller);
+    refund_stake(context, caller);
+
+    update_global_state(context, "unregister_executor", &[]);
+}
+
+#[public]
+pub fn submit_heartbeat(context: &mut Context) {
+    ensure_initialized(context);
+    let caller = context.actor();
+    let timestamp = context.timestamp();
+
+    // Verify caller is either executor or watchdog
+    let role = get_role(context, caller);
+    assert!(role != Role::None, "unauthorized caller");
+    let is_executor = matches!(role, Role::Executor(_));
+
+    // Rapid no-op heartbeats would otherwise inflate the execution count
+    let block_height = context.block_height();
+    if let Some(last_block) = context
+        .get(LastHeartbeatBlock(caller))
         .expect("state corrupt")
-        .expect("watchdog pool not initialized");
+    {
+        let spacing = load_system_config(context).min_heartbeat_spacing;
+        assert!(
+            block_height.saturating_sub(last_block) >= spacing,
+            "heartbeat too frequent"
+        );
+    }
 
-    let is_executor = executor_pool.sgx_executor == Some(caller) || 
-                     executor_pool.sev_executor == Some(caller);
-    let is_watchdog = watchdog_pool.watchdogs.iter().any(|(addr, _)| *addr == caller);
+    // A sustained run of on-time heartbeats clears accumulated misses
+    let timeout_interval = load_system_config(context).timeout_interval;
+    let on_time = context
+        .get(HeartbeatTimestamp(caller))
+        .expect("state corrupt")
+        .map_or(false, |last| timestamp.saturating_sub(last) <= timeout_interval);
+    let mut streak = if on_time {
+        context
+            .get(HealthyHeartbeatStreak(caller))
+            .expect("state corrupt")
+            .unwrap_or(0)
+            + 1
+    } else {
+        0
+    };
+    if streak >= HEARTBEAT_RECOVERY_STREAK {
+        context
+            .remove(HeartbeatMisses(caller))
+            .expect("failed to reset heartbeat misses");
+        streak = 0;
+    }
 
-    assert!(is_executor || is_watchdog, "unauthorized caller");
+    // Uptime counts the timeout intervals of the reward period that saw a
+    // heartbeat; further heartbeats within the same interval add nothing
+    let period_start = context
+        .get(RewardPeriodStart())
+        .expect("state corrupt")
+        .unwrap_or(0);
+    let uptime_interval = (period_start, timestamp.saturating_sub(period_start) / timeout_interval);
+    let mut period_heartbeats = context
+        .get(PeriodHeartbeats(caller))
+        .expect("state corrupt")
+        .unwrap_or(0);
+    if context.get(LastUptimeInterval(caller)).expect("state corrupt") != Some(uptime_interval) {
+        period_heartbeats += 1;
+    }
 
     // Update heartbeat timestamp
     context
-        .store_by_key(HeartbeatTimestamp(caller), timestamp)
+        .store((
+            (HeartbeatTimestamp(caller), timestamp),
+            (LastHeartbeatBlock(caller), block_height),
+            (HealthyHeartbeatStreak(caller), streak),
+            (PeriodHeartbeats(caller), period_heartbeats),
+            (LastUptimeInterval(caller), uptime_interval),
+        ))
         .expect("failed to update heartbeat");
 
     // If executor, update execution count
     if is_executor {
-        let mut pool = executor_pool;
+        let mut pool = context
+            .get(ExecutorPool())
+            .expect("state corrupt")
+            .expect("executor pool not initialized");
         pool.last_execution_time = timestamp;
         pool.execution_count += 1;
         context
@@ -154,10 +383,529 @@ pub fn submit_heartbeat(context: &mut Context) {
     }
 }
 
-fn transition_to_executing(context: &mut Context) {
+/// Fails if another active executor already attested `measurement`; the same
+/// address may re-register with its own measurement
+pub fn ensure_unique_measurement(
+    context: &mut Context,
+    caller: Address,
+    measurement: &[u8],
+) -> Result<()> {
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    for executor in executor_pool.sgx_executor.into_iter().chain(executor_pool.sev_executor) {
+        if executor == caller
+            || context.get(ExecutorState(executor)).expect("state corrupt") != Some(ExecutorStatus::Active)
+        {
+            continue;
+        }
+        let existing = context.get(KeepMeasurement(executor)).expect("state corrupt");
+        if existing.as_deref() == Some(measurement) {
+            return Err(Error::executor_error("duplicate measurement"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Submits a fresh attestation for the caller's keep and restores its
+/// attestation status. The report is verified as the registered enclave type
+/// and the token must be issued for the registered keep; moving to a
+/// different keep goes through migration.
+#[public]
+pub fn renew_attestation(
+    context: &mut Context,
+    attestation_report: Vec<u8>,
+    drawbridge_token: Vec<u8>,
+) {
+    ensure_initialized(context);
+    let caller = context.actor();
+
+    let enclave_type = get_enclave_type(context, caller).expect("not registered");
+    ensure_attestation_report_size(context, &attestation_report);
+
+    let keep_id = context
+        .get(KeepId(caller))
+        .expect("state corrupt")
+        .expect("keep id not found");
+    assert!(
+        drawbridge_token_keep_id(&drawbridge_token) == Some(keep_id.as_bytes()),
+        "renewal cannot change keep id"
+    );
+    let registered_measurement = context.get(KeepMeasurement(caller)).expect("state corrupt");
+    if let Err(reason) = verify_attestation_report(
+        context,
+        &attestation_report,
+        &drawbridge_token,
+        &keep_id,
+        enclave_type,
+        registered_measurement.as_deref(),
+    ) {
+        panic!("invalid attestation: {}", reason);
+    }
+
+    context
+        .store((
+            (AttestationStatus(caller), true),
+            (LastAttestationTime(caller), context.timestamp()),
+        ))
+        .expect("failed to renew attestation");
+    store_drawbridge_token(context, caller, drawbridge_token);
+
+    update_global_state(context, "renew_attestation", &[keep_id.as_bytes(), &attestation_report]);
+}
+
+/// Forces every executor to re-attest, e.g. after a TCB advisory. Attestation
+/// status is cleared and executors that have not called `renew_attestation`
+/// by the returned deadline can be challenged.
+#[public]
+pub fn force_reattestation(context: &mut Context) -> u64 {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    for executor in executor_pool.sgx_executor.into_iter().chain(executor_pool.sev_executor) {
+        context
+            .store_by_key(AttestationStatus(executor), false)
+            .expect("failed to clear attestation status");
+    }
+
+    let deadline = context.timestamp() + REATTESTATION_WINDOW;
+    context
+        .store_by_key(ReattestationDeadline(), deadline)
+        .expect("failed to store reattestation deadline");
+
+    update_global_state(context, "force_reattestation", &[&deadline.to_le_bytes()]);
+
+    deadline
+}
+
+/// Marks the calling executor as draining so it stops accepting new executions
+#[public]
+pub fn drain_executor(context: &mut Context) {
+    ensure_initialized(context);
+    let caller = context.actor();
+
+    assert!(
+        get_executor_status(context, caller) == Some(ExecutorStatus::Active),
+        "executor not active"
+    );
+
+    context
+        .store_by_key(ExecutorState(caller), ExecutorStatus::Draining)
+        .expect("failed to update executor state");
+}
+
+/// Suspends an executor pending investigation. Only callable by governance.
+#[public]
+pub fn suspend_executor(context: &mut Context, executor: Address) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    assert!(get_executor_status(context, executor).is_some(), "not a registered executor");
+
+    context
+        .store_by_key(ExecutorState(executor), ExecutorStatus::Suspended)
+        .expect("failed to update executor state");
+}
+
+/// Returns a suspended or draining executor to `Active` and clears its lost
+/// mismatch streak. Failed executors stay failed. Only callable by governance.
+#[public]
+pub fn reinstate_executor(context: &mut Context, executor: Address) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    assert!(
+        matches!(
+            get_executor_status(context, executor),
+            Some(ExecutorStatus::Suspended | ExecutorStatus::Draining)
+        ),
+        "executor not suspended or draining"
+    );
+
+    context
+        .store_by_key(ExecutorState(executor), ExecutorStatus::Active)
+        .expect("failed to update executor state");
+    context
+        .remove(ConsecutiveMismatches(executor))
+        .expect("failed to clear mismatch streak");
+}
+
+#[public]
+pub fn get_executor_status(context: &mut Context, executor: Address) -> Option<ExecutorStatus> {
+    context
+        .get(ExecutorState(executor))
+        .expect("state corrupt")
+}
+
+/// Number of challenges lost by executors since the last reset
+#[public]
+pub fn get_failed_attempts(context: &mut Context) -> u64 {
+    context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized")
+        .failed_attempts
+}
+
+/// Clears the failure counter, e.g. after remediation. Only callable by governance.
+#[public]
+pub fn reset_failed_attempts(context: &mut Context) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let mut executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+    executor_pool.failed_attempts = 0;
+
+    context
+        .store_by_key(ExecutorPool(), executor_pool)
+        .expect("failed to update executor pool");
+}
+
+/// Enclave type registered for `address`, or `None` if it is not registered
+#[public]
+pub fn get_enclave_type(context: &mut Context, address: Address) -> Option<EnclaveType> {
+    context
+        .get(EnclaveType(address))
+        .expect("state corrupt")
+}
+
+/// Enclave types currently backed by a registered executor
+#[public]
+pub fn get_active_enclave_types(context: &mut Context) -> Vec<EnclaveType> {
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    let mut enclave_types = Vec::new();
+    if executor_pool.sgx_executor.is_some() {
+        enclave_types.push(EnclaveType::IntelSGX);
+    }
+    if executor_pool.sev_executor.is_some() {
+        enclave_types.push(EnclaveType::AMDSEV);
+    }
+    enclave_types
+}
+
+/// Whether the keep registered for `address` can be live-migrated; keeps that
+/// can't must be cold-replaced on recovery
+#[public]
+pub fn is_migration_supported(context: &mut Context, address: Address) -> bool {
+    get_enclave_type(context, address)
+        .map_or(false, |enclave_type| enclave_type.supports_live_migration())
+}
+
+/// Role the caller holds in the executor or watchdog pool
+#[public]
+pub fn get_caller_role(context: &mut Context) -> Role {
+    let caller = context.actor();
+    get_role(context, caller)
+}
+
+/// Role `address` holds in the executor or watchdog pool
+pub fn get_role(context: &mut Context, address: Address) -> Role {
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    if executor_pool.sgx_executor == Some(address) {
+        return Role::Executor(EnclaveType::IntelSGX);
+    }
+    if executor_pool.sev_executor == Some(address) {
+        return Role::Executor(EnclaveType::AMDSEV);
+    }
+
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+
+    watchdog_pool
+        .watchdogs
+        .into_iter()
+        .find(|(addr, _)| *addr == address)
+        .map_or(Role::None, |(_, enclave_type)| Role::Watchdog(enclave_type))
+}
+
+/// Reports, for each registered executor's enclave type, whether a payload of
+/// `size` bytes is within its keep's limits, so submitters can check before
+/// paying for an execution
+#[public]
+pub fn validate_payload(
+    context: &mut Context,
+    payload_hash: Vec<u8>,
+    size: usize,
+) -> Vec<(EnclaveType, bool)> {
+    assert!(!payload_hash.is_empty(), "empty payload hash");
+
+    let config = load_system_config(context);
+    get_active_enclave_types(context)
+        .into_iter()
+        .map(|enclave_type| {
+            let accepted = size > 0 && size <= config.max_payload_size(&enclave_type);
+            (enclave_type, accepted)
+        })
+        .collect()
+}
+
+/// Whether the attestation of `address` is still within the validity period
+/// configured for its enclave type
+#[public]
+pub fn is_attestation_fresh(context: &mut Context, address: Address) -> bool {
+    let Some(enclave_type) = get_enclave_type(context, address) else {
+        return false;
+    };
+    let Some(last_attestation) = context
+        .get(LastAttestationTime(address))
+        .expect("state corrupt")
+    else {
+        return false;
+    };
+
+    let validity = load_system_config(context).attestation_validity(&enclave_type);
+    context.timestamp().saturating_sub(last_attestation) <= validity
+}
+
+/// Last attestation time, keep measurement and attestation status for a
+/// registered keep, or `None` if `address` has no keep
+#[public]
+pub fn get_keep_attestation_state(
+    context: &mut Context,
+    address: Address,
+) -> Option<(u64, Vec<u8>, bool)> {
+    context.get(KeepId(address)).expect("state corrupt")?;
+
+    let last_attestation = context
+        .get(LastAttestationTime(address))
+        .expect("state corrupt")
+        .unwrap_or(0);
+    let measurement = context
+        .get(KeepMeasurement(address))
+        .expect("state corrupt")
+        .unwrap_or_default();
+    let active = context
+        .get(AttestationStatus(address))
+        .expect("state corrupt")
+        .unwrap_or(false);
+
+    Some((last_attestation, measurement, active))
+}
+
+/// Checks a presented Drawbridge token against the one registered for `address`
+#[public]
+pub fn verify_drawbridge_token(
+    context: &mut Context,
+    address: Address,
+    drawbridge_token: Vec<u8>,
+) -> bool {
+    drawbridge_token_matches(context, address, &drawbridge_token)
+}
+
+/// Revokes a Drawbridge token so it can no longer be used to attest. Only
+/// callable by governance.
+#[public]
+pub fn revoke_drawbridge_token(context: &mut Context, token_hash: [u8; 32]) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    context
+        .store_by_key(RevokedDrawbridgeToken(token_hash), true)
+        .expect("failed to revoke drawbridge token");
+}
+
+/// Replaces full Drawbridge tokens stored before hashing was enabled with
+/// their SHA-256 commitment. Returns the number of entries migrated. Only
+/// runs once `hash_drawbridge_tokens` is on, otherwise new registrations
+/// would keep storing full tokens next to the migrated hashes.
+#[public]
+pub fn migrate_drawbridge_tokens(context: &mut Context) -> usize {
+    ensure_initialized(context);
+    ensure_governance(context);
+    assert!(
+        load_system_config(context).hash_drawbridge_tokens,
+        "drawbridge token hashing not enabled"
+    );
+
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+
+    let mut addresses = participant_addresses(context);
+    addresses.extend(watchdog_pool.ready_tees.iter().map(|(addr, _)| *addr));
+
+    let mut migrated = 0;
+    for address in addresses {
+        if let Some(token) = context.get(DrawbridgeToken(address)).expect("state corrupt") {
+            context
+                .store_by_key(DrawbridgeTokenHash(address), drawbridge_token_hash(&token))
+                .expect("failed to store drawbridge token hash");
+            context
+                .remove(DrawbridgeToken(address))
+                .expect("failed to remove drawbridge token");
+            migrated += 1;
+        }
+    }
+
+    migrated
+}
+
+/// Number of liveness misses recorded against `address`
+#[public]
+pub fn get_heartbeat_misses(context: &mut Context, address: Address) -> u64 {
+    context
+        .get(HeartbeatMisses(address))
+        .expect("state corrupt")
+        .unwrap_or(0)
+}
+
+/// Share of the current reward period's timeout intervals in which `address`
+/// sent a heartbeat, in basis points
+#[public]
+pub fn get_uptime_ratio(context: &mut Context, address: Address) -> u64 {
+    let period_start = context
+        .get(RewardPeriodStart())
+        .expect("state corrupt")
+        .unwrap_or(0);
+    let timeout_interval = load_system_config(context).timeout_interval;
+    let expected = (context.timestamp().saturating_sub(period_start) / timeout_interval).max(1);
+    let received = context
+        .get(PeriodHeartbeats(address))
+        .expect("state corrupt")
+        .unwrap_or(0);
+
+    (received.min(expected) * UPTIME_BASIS_POINTS / expected).min(UPTIME_BASIS_POINTS)
+}
+
+/// Whether `address` has stayed under `MAX_HEARTBEAT_MISSES`
+pub fn has_reliable_liveness(context: &mut Context, address: Address) -> bool {
+    get_heartbeat_misses(context, address) < MAX_HEARTBEAT_MISSES
+}
+
+/// Clears attestation status for every executor or watchdog whose last
+/// heartbeat is older than the configured timeout and records one miss for
+/// each timeout interval that has passed without a heartbeat, however often
+/// it is called. Returns the affected addresses.
+#[public]
+pub fn check_liveness(context: &mut Context) -> Vec<Address> {
+    ensure_initialized(context);
+    let timestamp = context.timestamp();
+    let timeout_interval = load_system_config(context).timeout_interval;
+
+    let mut expired = Vec::new();
+    for participant in participant_addresses(context) {
+        let last_heartbeat = context
+            .get(HeartbeatTimestamp(participant))
+            .expect("state corrupt")
+            .unwrap_or(0);
+
+        let elapsed = timestamp.saturating_sub(last_heartbeat);
+        if elapsed > timeout_interval {
+            let missed_intervals = (elapsed - 1) / timeout_interval;
+            let already_counted = match context
+                .get(CountedHeartbeatMisses(participant))
+                .expect("state corrupt")
+            {
+                Some((counted_heartbeat, counted)) if counted_heartbeat == last_heartbeat => counted,
+                _ => 0,
+            };
+            let misses = get_heartbeat_misses(context, participant)
+                + missed_intervals.saturating_sub(already_counted);
+            context
+                .store((
+                    (AttestationStatus(participant), false),
+                    (HeartbeatMisses(participant), misses),
+                    (CountedHeartbeatMisses(participant), (last_heartbeat, missed_intervals.max(already_counted))),
+                    (HealthyHeartbeatStreak(participant), 0),
+                ))
+                .expect("failed to record liveness miss");
+            expired.push(participant);
+        }
+    }
+
+    expired
+}
+
+/// Every unmet precondition keeping the system out of `Executing`; empty once
+/// it is executing
+#[public]
+pub fn why_not_executing(context: &mut Context) -> Vec<TransitionBlocker> {
+    ensure_initialized(context);
+
+    let phase = context
+        .get(CurrentPhase())
+        .expect("state corrupt")
+        .unwrap_or(Phase::None);
+    if phase == Phase::Executing {
+        return Vec::new();
+    }
+
+    let mut blockers = Vec::new();
+    if phase != Phase::Creation {
+        blockers.push(TransitionBlocker::NotInCreation(phase));
+    }
+
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+    if executor_pool.sgx_executor.is_none() {
+        blockers.push(TransitionBlocker::MissingExecutor(EnclaveType::IntelSGX));
+    }
+    if executor_pool.sev_executor.is_none() {
+        blockers.push(TransitionBlocker::MissingExecutor(EnclaveType::AMDSEV));
+    }
+
+    let registered = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized")
+        .watchdogs
+        .len();
+    if registered < MIN_WATCHDOGS {
+        blockers.push(TransitionBlocker::InsufficientWatchdogs {
+            registered,
+            required: MIN_WATCHDOGS,
+        });
+    }
+
+    blockers
+}
+
+/// Enters `Executing` once both executor slots are filled and at least
+/// `MIN_WATCHDOGS` watchdogs can vote on challenges; otherwise the system
+/// stays where it is and `why_not_executing` reports what is missing
+pub(crate) fn transition_to_executing(context: &mut Context) {
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+    if executor_pool.sgx_executor.is_none()
+        || executor_pool.sev_executor.is_none()
+        || watchdog_pool.watchdogs.len() < MIN_WATCHDOGS
+    {
+        return;
+    }
+
     context
         .store_by_key(CurrentPhase(), Phase::Executing)
         .expect("failed to transition to executing");
     
-    update_global_state(context);
+    update_global_state(context, "transition_to_executing", &[]);
 }
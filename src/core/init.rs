@@ -2,8 +2,41 @@ use wasmlanche::{public, Context, Address};
 use crate::{
     types::*,
     state::*,
+    core::utils::{verify_signature, hash_message},
+    challenge::can_reach_quorum,
 };
 
+/// Message both operators sign to approve a deployment's initialization,
+/// including any config overrides. Every field is length-prefixed so values
+/// can't be shifted from one field into the next.
+pub fn init_message(
+    sgx_operator: &str,
+    sev_operator: &str,
+    token_contract: Address,
+    governance_contract: Address,
+    config_overrides: &Option<SystemConfig>,
+) -> Vec<u8> {
+    let token_contract = token_contract.to_string();
+    let governance_contract = governance_contract.to_string();
+    let config_overrides = format!("{:?}", config_overrides);
+
+    let mut message = Vec::new();
+    for field in [
+        sgx_operator,
+        sev_operator,
+        &token_contract,
+        &governance_contract,
+        &config_overrides,
+    ] {
+        message.extend((field.len() as u32).to_le_bytes());
+        message.extend(field.as_bytes());
+    }
+    hash_message(&message)
+}
+
+/// Initializes the system. The parameters must be co-signed by both the SGX
+/// and SEV operator keys so no single party controls the deployment, and the
+/// two operators must be distinct.
 #[public]
 pub fn init(
     context: &mut Context,
@@ -11,12 +44,33 @@ pub fn init(
     sev_operator: String,
     token_contract: Address,
     governance_contract: Address,
+    config_overrides: Option<SystemConfig>,
+    sgx_signature: Vec<u8>,
+    sev_signature: Vec<u8>,
 ) {
     // Ensure system isn't already initialized
     assert!(
         !context.get(SystemInitialized()).expect("state corrupt").unwrap_or(false),
         "system already initialized"
     );
+    assert!(sgx_operator != sev_operator, "operators must be distinct");
+
+    let message = init_message(
+        &sgx_operator,
+        &sev_operator,
+        token_contract,
+        governance_contract,
+        &config_overrides,
+    );
+    for (operator, signature) in [(&sgx_operator, &sgx_signature), (&sev_operator, &sev_signature)] {
+        assert!(
+            !signature.is_empty() && verify_signature(&message, signature, operator),
+            "missing operator co-signature"
+        );
+    }
+
+    let config = config_overrides.unwrap_or_default();
+    config.validate();
 
     // Initialize phase
     context
@@ -38,6 +92,8 @@ pub fn init(
         last_verification: context.timestamp(),
     };
 
+    let operator_ids = vec![sgx_operator.clone(), sev_operator.clone()];
+
     // Initialize operators
     let sgx_op = Operator {
         initialized: true,
@@ -69,6 +125,7 @@ pub fn init(
             (OperatorData(sev_operator), sev_op),
             (TokenContract(), token_contract),
             (GovernanceContract(), governance_contract),
+            (SystemConfig(), config),
             (LastGlobalUpdate(), context.timestamp()),
         ))
         .expect("failed to initialize system state");
@@ -80,6 +137,138 @@ pub fn init(
             (ChallengeCount(), 0),
             (ActiveContracts(), Vec::new()),
             (ActiveChallenges(), Vec::new()),
+            (OperatorIds(), operator_ids),
+            (RewardPeriodStart(), context.timestamp()),
         ))
         .expect("failed to initialize tracking state");
 }
+
+/// Looks up a registered operator by id
+#[public]
+pub fn get_operator(context: &mut Context, operator_id: String) -> Option<Operator> {
+    context.get(OperatorData(operator_id)).expect("state corrupt")
+}
+
+/// Ids of all registered operators
+#[public]
+pub fn list_operators(context: &mut Context) -> Vec<String> {
+    context
+        .get(OperatorIds())
+        .expect("state corrupt")
+        .unwrap_or_default()
+}
+
+/// Current head of the incremental transaction hash chain, for auditing continuity
+#[public]
+pub fn get_incremental_tx_hash(context: &mut Context) -> Vec<u8> {
+    context
+        .get(IncrementalTxHash())
+        .expect("state corrupt")
+        .unwrap_or_default()
+}
+
+/// Snapshot of the phase, pool membership, counters and state root, so the
+/// system can be audited without reading every key
+#[public]
+pub fn export_state_summary(context: &mut Context) -> StateSummary {
+    ensure_initialized(context);
+
+    let phase = context
+        .get(CurrentPhase())
+        .expect("state corrupt")
+        .expect("phase not initialized");
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+    let challenge_count = context
+        .get(ChallengeCount())
+        .expect("state corrupt")
+        .unwrap_or(0);
+    let verified_execution_count = context
+        .get(VerifiedExecutions())
+        .expect("state corrupt")
+        .unwrap_or_default()
+        .len();
+    let state_root = context
+        .get(StateRoot())
+        .expect("state corrupt")
+        .unwrap_or_default();
+
+    StateSummary {
+        phase,
+        sgx_executor: executor_pool.sgx_executor,
+        sev_executor: executor_pool.sev_executor,
+        watchdogs: watchdog_pool.watchdogs.into_iter().map(|(addr, _)| addr).collect(),
+        challenge_count,
+        verified_execution_count,
+        state_root,
+    }
+}
+
+/// Aggregates the readiness conditions into a single probe result
+#[public]
+pub fn system_health(context: &mut Context) -> SystemHealth {
+    ensure_initialized(context);
+
+    let phase = context
+        .get(CurrentPhase())
+        .expect("state corrupt")
+        .expect("phase not initialized");
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    let executors_ready = [executor_pool.sgx_executor, executor_pool.sev_executor]
+        .into_iter()
+        .all(|slot| {
+            slot.is_some_and(|executor| {
+                context.get(ExecutorState(executor)).expect("state corrupt")
+                    == Some(ExecutorStatus::Active)
+                    && context
+                        .get(AttestationStatus(executor))
+                        .expect("state corrupt")
+                        .unwrap_or(false)
+            })
+        });
+
+    SystemHealth {
+        phase,
+        executors_ready,
+        watchdogs_sufficient: can_reach_quorum(context),
+        paused: context.get(SystemPaused()).expect("state corrupt").unwrap_or(false),
+    }
+}
+
+/// Reopens executor registration after a crash. Only callable by governance,
+/// and only once the configured cool-down has passed since the crash so
+/// control can't be seized in the middle of an incident.
+#[public]
+pub fn recover_from_crash(context: &mut Context) {
+    ensure_initialized(context);
+    ensure_governance(context);
+    ensure_phase(context, Phase::Crashed);
+
+    let crashed_at = context
+        .get(CrashedAt())
+        .expect("state corrupt")
+        .expect("crash time not recorded");
+    assert!(
+        context.timestamp() >= crashed_at + load_system_config(context).crash_recovery_cooldown,
+        "crash cool-down active"
+    );
+
+    context
+        .store_by_key(CurrentPhase(), Phase::Creation)
+        .expect("failed to update phase");
+    context
+        .remove(CrashedAt())
+        .expect("failed to clear crash time");
+
+    update_global_state(context, "recover_from_crash", &[]);
+}
@@ -0,0 +1,111 @@
+use wasmlanche::{public, Context};
+use crate::{
+    types::*,
+    state::*,
+    CURRENT_STATE_VERSION,
+    MIGRATION_ROLLBACK_WINDOW,
+};
+
+/// Upgrades stored state to `CURRENT_STATE_VERSION`, snapshotting the pools
+/// first. Only callable by governance. Returns the new version.
+#[public]
+pub fn migrate_state(context: &mut Context) -> u32 {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let version = get_state_version(context);
+    assert!(version < CURRENT_STATE_VERSION, "state already current");
+
+    let backup = MigrationBackup {
+        version,
+        executor_pool: context
+            .get(ExecutorPool())
+            .expect("state corrupt")
+            .expect("executor pool not initialized"),
+        watchdog_pool: context
+            .get(WatchdogPool())
+            .expect("state corrupt")
+            .expect("watchdog pool not initialized"),
+        taken_at: context.timestamp(),
+    };
+    context
+        .store_by_key(MigrationBackup(), backup)
+        .expect("failed to store migration backup");
+
+    for from_version in version..CURRENT_STATE_VERSION {
+        match from_version {
+            0 => drop_orphaned_ready_tees(context),
+            _ => unreachable!("no migration from version {}", from_version),
+        }
+    }
+
+    context
+        .store_by_key(StateVersion(), CURRENT_STATE_VERSION)
+        .expect("failed to update state version");
+    update_global_state(context, "migrate_state", &[&CURRENT_STATE_VERSION.to_le_bytes()]);
+
+    CURRENT_STATE_VERSION
+}
+
+/// Restores the pools captured by the last `migrate_state` and reverts the
+/// state version. Only callable by governance within `MIGRATION_ROLLBACK_WINDOW`.
+#[public]
+pub fn rollback_migration(context: &mut Context) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let backup = context
+        .get(MigrationBackup())
+        .expect("state corrupt")
+        .expect("no migration to roll back");
+    assert!(
+        context.timestamp() - backup.taken_at <= MIGRATION_ROLLBACK_WINDOW,
+        "rollback window elapsed"
+    );
+
+    context
+        .store((
+            (ExecutorPool(), backup.executor_pool),
+            (WatchdogPool(), backup.watchdog_pool),
+        ))
+        .expect("failed to restore pools");
+    context
+        .store_by_key(StateVersion(), backup.version)
+        .expect("failed to revert state version");
+    context
+        .remove(MigrationBackup())
+        .expect("failed to clear migration backup");
+
+    update_global_state(context, "rollback_migration", &[&backup.version.to_le_bytes()]);
+}
+
+#[public]
+pub fn get_state_version(context: &mut Context) -> u32 {
+    context
+        .get(StateVersion())
+        .expect("state corrupt")
+        .unwrap_or(0)
+}
+
+/// v0 -> v1: deregistration used to leave ready TEEs in the pool after their
+/// keep registration was cleared
+fn drop_orphaned_ready_tees(context: &mut Context) {
+    let mut watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+
+    let orphaned: Vec<_> = watchdog_pool.ready_tees.iter()
+        .map(|(addr, _)| *addr)
+        .filter(|addr| context.get(KeepId(*addr)).expect("state corrupt").is_none())
+        .collect();
+
+    watchdog_pool.ready_tees.retain(|(addr, _)| !orphaned.contains(addr));
+    for addr in &orphaned {
+        watchdog_pool.health_status.remove(addr);
+    }
+
+    context
+        .store_by_key(WatchdogPool(), watchdog_pool)
+        .expect("failed to update watchdog pool");
+}
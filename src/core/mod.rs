@@ -2,8 +2,12 @@ mod init;
 mod executor;
 mod watchdog;
 mod utils;
+mod compression;
+mod migration;
 
 pub use init::*;
 pub use executor::*;
 pub use watchdog::*;
 pub use utils::*;
+pub use compression::*;
+pub use migration::*;
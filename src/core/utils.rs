@@ -1,6 +1,10 @@
 use wasmlanche::{Context, ExternalCallArgs};
+use sha2::{Digest, Sha256};
 use crate::MAX_GAS;
 use crate::ZERO;
+use crate::error::AttestationFailure;
+use crate::state::{load_system_config, RevokedDrawbridgeToken};
+use crate::types::{AttestationBundle, EnclaveType, VerificationMode};
 
 pub fn call_args_from_address(address: wasmlanche::Address) -> ExternalCallArgs {
     ExternalCallArgs {
@@ -14,24 +18,162 @@ pub fn verify_attestation_report(
     context: &mut Context,
     attestation_report: &[u8],
     drawbridge_token: &[u8],
+    keep_id: &str,
     enclave_type: EnclaveType,
-) -> bool {
+    expected_measurement: Option<&[u8]>,
+) -> Result<(), AttestationFailure> {
+    if attestation_report.is_empty() {
+        return Err(AttestationFailure::EmptyReport);
+    }
+
+    // A token issued for one keep must not be replayable for another
+    if !token_bound_to_keep(drawbridge_token, keep_id) {
+        return Err(AttestationFailure::TokenNotBoundToKeep);
+    }
+
+    let revoked = context
+        .get(RevokedDrawbridgeToken(drawbridge_token_hash(drawbridge_token)))
+        .expect("state corrupt")
+        .unwrap_or(false);
+    if revoked {
+        return Err(AttestationFailure::RevokedToken);
+    }
+
+    // A monolithic quote is verified as a bundle without collateral or TCB info
+    let bundle = match parse_attestation_bundle(attestation_report)? {
+        Some(bundle) => {
+            if collateral_next_update(&bundle.collateral)? < context.timestamp() {
+                return Err(AttestationFailure::ExpiredQuote);
+            }
+            bundle
+        },
+        None if load_system_config(context).verification_mode == VerificationMode::RequireBundle => {
+            return Err(AttestationFailure::MalformedBundle);
+        },
+        None => AttestationBundle {
+            quote: attestation_report.to_vec(),
+            collateral: Vec::new(),
+            tcb_info: Vec::new(),
+        },
+    };
+
+    // Renewals and challenge responses must attest the keep that registered
+    if let Some(expected) = expected_measurement {
+        if quote_measurement(&bundle.quote, &enclave_type).as_deref() != Some(expected) {
+            return Err(AttestationFailure::MeasurementMismatch);
+        }
+    }
+
     match enclave_type {
-        EnclaveType::IntelSGX => verify_sgx_keep(attestation_report, drawbridge_token),
-        EnclaveType::AMDSEV => verify_sev_keep(attestation_report, drawbridge_token),
+        EnclaveType::IntelSGX => verify_sgx_keep(&bundle, drawbridge_token),
+        EnclaveType::AMDSEV => verify_sev_keep(&bundle, drawbridge_token),
+    }
+}
+
+/// Time after which a bundle's collateral must be refreshed; collateral opens
+/// with it as a little-endian `u64`
+fn collateral_next_update(collateral: &[u8]) -> Result<u64, AttestationFailure> {
+    let (next_update, _) = collateral
+        .split_first_chunk::<8>()
+        .ok_or(AttestationFailure::MalformedBundle)?;
+    Ok(u64::from_le_bytes(*next_update))
+}
+
+/// Parses a multi-quote report, requiring every component to be present.
+/// Returns `None` for a monolithic report.
+pub fn parse_attestation_bundle(
+    attestation_report: &[u8],
+) -> Result<Option<AttestationBundle>, AttestationFailure> {
+    if !AttestationBundle::is_bundle(attestation_report) {
+        return Ok(None);
+    }
+
+    let bundle = AttestationBundle::decode(attestation_report)
+        .ok_or(AttestationFailure::MalformedBundle)?;
+    for (name, component) in [
+        ("quote", &bundle.quote),
+        ("collateral", &bundle.collateral),
+        ("tcb info", &bundle.tcb_info),
+    ] {
+        if component.is_empty() {
+            return Err(AttestationFailure::MissingBundleComponent(name.to_string()));
+        }
     }
+
+    Ok(Some(bundle))
+}
+
+/// Offset of the 64-byte report data in an SGX DCAP quote: the 48-byte quote
+/// header plus 320 bytes into the report body
+const SGX_REPORT_DATA_OFFSET: usize = 368;
+/// Offset of the 64-byte report data in an SEV-SNP attestation report
+const SEV_REPORT_DATA_OFFSET: usize = 0x50;
+const REPORT_DATA_LEN: usize = 64;
+
+/// User data the enclave bound into its quote, or `None` if the quote is too
+/// short to carry it
+pub fn quote_report_data(attestation_report: &[u8], enclave_type: &EnclaveType) -> Option<Vec<u8>> {
+    let quote = match AttestationBundle::decode(attestation_report) {
+        Some(bundle) => bundle.quote,
+        None => attestation_report.to_vec(),
+    };
+    let offset = match enclave_type {
+        EnclaveType::IntelSGX => SGX_REPORT_DATA_OFFSET,
+        EnclaveType::AMDSEV => SEV_REPORT_DATA_OFFSET,
+    };
+    quote.get(offset..offset + REPORT_DATA_LEN).map(<[u8]>::to_vec)
+}
+
+/// Offset of MRENCLAVE in an SGX DCAP quote: the 48-byte quote header plus
+/// 64 bytes into the report body
+const SGX_MEASUREMENT_OFFSET: usize = 112;
+const SGX_MEASUREMENT_LEN: usize = 32;
+/// Offset of the launch measurement in an SEV-SNP attestation report
+const SEV_MEASUREMENT_OFFSET: usize = 0x90;
+const SEV_MEASUREMENT_LEN: usize = 48;
+
+/// Code measurement the enclave's quote attests to, or `None` if the quote is
+/// too short to carry it
+pub fn quote_measurement(attestation_report: &[u8], enclave_type: &EnclaveType) -> Option<Vec<u8>> {
+    let quote = match AttestationBundle::decode(attestation_report) {
+        Some(bundle) => bundle.quote,
+        None => attestation_report.to_vec(),
+    };
+    let (offset, len) = match enclave_type {
+        EnclaveType::IntelSGX => (SGX_MEASUREMENT_OFFSET, SGX_MEASUREMENT_LEN),
+        EnclaveType::AMDSEV => (SEV_MEASUREMENT_OFFSET, SEV_MEASUREMENT_LEN),
+    };
+    quote.get(offset..offset + len).map(<[u8]>::to_vec)
+}
+
+/// Drawbridge proofs end with the id of the keep they were issued for,
+/// followed by its length as a little-endian `u32`
+pub fn token_bound_to_keep(drawbridge_token: &[u8], keep_id: &str) -> bool {
+    !keep_id.is_empty() && drawbridge_token_keep_id(drawbridge_token) == Some(keep_id.as_bytes())
 }
 
-fn verify_sgx_keep(attestation: &[u8], token: &[u8]) -> bool {
+/// Keep id carried in a Drawbridge token, or `None` if the trailer is malformed
+pub fn drawbridge_token_keep_id(drawbridge_token: &[u8]) -> Option<&[u8]> {
+    let (rest, len) = drawbridge_token.split_last_chunk::<4>()?;
+    let len = u32::from_le_bytes(*len) as usize;
+    let start = rest.len().checked_sub(len)?;
+    Some(&rest[start..])
+}
+
+/// Checks an SGX quote against its collateral (PCK certificates, CRLs) and
+/// TCB info; both are empty for a monolithic quote
+fn verify_sgx_keep(bundle: &AttestationBundle, token: &[u8]) -> Result<(), AttestationFailure> {
     // Implement SGX Keep verification
-    // For now, return true until implementation is complete
-    true
+    // For now, accept until implementation is complete
+    Ok(())
 }
 
-fn verify_sev_keep(attestation: &[u8], token: &[u8]) -> bool {
+/// Checks an SEV-SNP report against its collateral (VCEK chain) and TCB
+/// info; both are empty for a monolithic report
+fn verify_sev_keep(bundle: &AttestationBundle, token: &[u8]) -> Result<(), AttestationFailure> {
     // Implement SEV Keep verification
-    // For now, return true until implementation is complete
-    true
+    // For now, accept until implementation is complete
+    Ok(())
 }
 
 pub fn verify_signature(
@@ -48,8 +190,30 @@ pub fn hash_message(message: &[u8]) -> Vec<u8> {
     message.to_vec()
 }
 
-pub fn hash_incremental(previous_hash: Vec<u8>, operator_address: String) -> Vec<u8> {
-    let mut new_hash = previous_hash;
-    new_hash.extend(operator_address.as_bytes());
-    new_hash
+/// On-chain commitment to a Drawbridge token
+pub fn drawbridge_token_hash(drawbridge_token: &[u8]) -> [u8; 32] {
+    Sha256::digest(drawbridge_token).into()
+}
+
+/// Next head of the transaction hash chain: `SHA-256(previous || link)`
+pub fn hash_incremental(previous_hash: Vec<u8>, link: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(previous_hash);
+    hasher.update(link);
+    hasher.finalize().to_vec()
+}
+
+/// One link of the transaction hash chain. Every field is length-prefixed so
+/// distinct operations can't encode to the same bytes.
+pub fn encode_tx_link(actor: &str, selector: &str, args: &[&[u8]], state_root: &[u8]) -> Vec<u8> {
+    let mut link = Vec::new();
+    for field in [actor.as_bytes(), selector.as_bytes()]
+        .into_iter()
+        .chain(args.iter().copied())
+        .chain([state_root])
+    {
+        link.extend((field.len() as u32).to_le_bytes());
+        link.extend(field);
+    }
+    link
 }
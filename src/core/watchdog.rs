@@ -1,10 +1,98 @@
-use wasmlanche::{public, Context};
+use wasmlanche::{public, Context, Address};
 use crate::{
     types::*,
     state::*,
-    core::utils::verify_attestation_report,
+    core::{
+        utils::{verify_attestation_report, verify_signature, hash_message, quote_measurement},
+        get_enclave_type,
+        transition_to_executing,
+        is_attestation_fresh,
+        has_reliable_liveness,
+        get_heartbeat_misses,
+    },
+    external::{hand_off_stake, refund_stake},
+    error::{Error, Result},
+    MAX_REPLACEMENT_PAGE_SIZE,
+    MIN_WATCHDOGS,
 };
 
+/// Registers the caller as a watchdog. Like an executor, the watchdog's keep
+/// must attest with a Drawbridge token bound to `keep_id`, and the measurement
+/// is read from the verified quote, so watchdog keeps can be health-monitored
+/// too.
+#[public]
+pub fn register_watchdog(
+    context: &mut Context,
+    enclave_type: EnclaveType,
+    keep_id: String,
+    attestation_report: Vec<u8>,
+    drawbridge_token: Vec<u8>,
+    signature: Vec<u8>,
+) {
+    ensure_initialized(context);
+    let caller = context.actor();
+    ensure_attestation_report_size(context, &attestation_report);
+
+    assert!(
+        verify_signature(&hash_message(&attestation_report), &signature, &caller.to_string()),
+        "invalid attestation"
+    );
+    if let Err(reason) = verify_attestation_report(
+        context,
+        &attestation_report,
+        &drawbridge_token,
+        &keep_id,
+        enclave_type.clone(),
+        None,
+    ) {
+        panic!("invalid attestation: {}", reason);
+    }
+    let measurement = quote_measurement(&attestation_report, &enclave_type)
+        .expect("attestation report carries no measurement");
+
+    let mut pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+
+    assert!(
+        !pool.watchdogs.iter().any(|(addr, _)| *addr == caller),
+        "watchdog already registered"
+    );
+    let max_watchdogs = load_system_config(context).max_watchdogs;
+    assert!(
+        pool.watchdogs.len() + pool.ready_tees.len() < max_watchdogs,
+        "watchdog pool full"
+    );
+
+    pool.watchdogs.push((caller, enclave_type));
+    if pool.watchdogs.len() >= MIN_WATCHDOGS {
+        context
+            .remove(WatchdogPoolDegraded())
+            .expect("failed to clear degraded watchdog pool");
+    }
+
+    context
+        .store((
+            (WatchdogPool(), pool),
+            (KeepId(caller), keep_id),
+            (KeepMeasurement(caller), measurement),
+        ))
+        .expect("failed to register watchdog");
+    context
+        .store((
+            (AttestationStatus(caller), true),
+            (LastAttestationTime(caller), context.timestamp()),
+        ))
+        .expect("failed to store attestation");
+    store_drawbridge_token(context, caller, drawbridge_token);
+
+    // The last watchdog needed for quorum may arrive after both executors
+    if context.get(CurrentPhase()).expect("state corrupt") == Some(Phase::Creation) {
+        transition_to_executing(context);
+    }
+}
+
 /// Registers a TEE into the watchdog pool for potential executor replacement
 #[public]
 pub fn register_ready_tee(
@@ -16,9 +104,24 @@ pub fn register_ready_tee(
 ) -> Result<()> {
     ensure_initialized(context);
     let caller = context.actor();
-    
+    ensure_attestation_report_size(context, &attestation_report);
+
     // Verify TEE attestation
-    verify_attestation_report(context, &attestation_report, &drawbridge_token)?;
+    if let Err(reason) = verify_attestation_report(
+        context,
+        &attestation_report,
+        &drawbridge_token,
+        &keep_id,
+        enclave_type.clone(),
+        None,
+    ) {
+        panic!("invalid attestation: {}", reason);
+    }
+
+    // Standbys run the executors' workload and share their measurement, so
+    // only the attested value is recorded here
+    let measurement = quote_measurement(&attestation_report, &enclave_type)
+        .ok_or(Error::InvalidAttestation)?;
 
     let mut pool = context.get(WatchdogPool())?
         .expect("watchdog pool not initialized");
@@ -29,6 +132,11 @@ pub fn register_ready_tee(
         "TEE already in ready pool"
     );
 
+    let max_watchdogs = load_system_config(context).max_watchdogs;
+    if pool.watchdogs.len() + pool.ready_tees.len() >= max_watchdogs {
+        return Err(Error::StateError("watchdog pool full".into()));
+    }
+
     // Add to ready pool
     pool.ready_tees.push((caller, enclave_type));
     pool.health_status.insert(caller, KeepHealth {
@@ -42,21 +150,61 @@ pub fn register_ready_tee(
     context.store((
         (WatchdogPool(), pool),
         (KeepId(caller), keep_id),
-        (DrawbridgeToken(caller), drawbridge_token),
         (EnclaveType(caller), enclave_type),
+        (KeepMeasurement(caller), measurement),
     ))?;
+    context.store_by_key(LastAttestationTime(caller), context.timestamp())?;
+    store_drawbridge_token(context, caller, drawbridge_token);
 
     Ok(())
 }
 
-/// Replaces a failed executor with a ready TEE from the watchdog pool
+/// Replaces a failed executor with a ready TEE from the watchdog pool.
+/// Only governance or a watchdog may trigger a replacement, and only for an
+/// executor marked failed. A lost challenge already vacated its slot; an
+/// executor removed without a replacement at hand still holds it. Either way
+/// the slot must not have been refilled since.
 #[public]
 pub fn replace_executor(
     context: &mut Context,
     failed_executor: Address,
 ) -> Result<()> {
     ensure_initialized(context);
-    
+    let caller = context.actor();
+
+    let governance = context.get(GovernanceContract())?
+        .ok_or(Error::StateError("governance contract not initialized".into()))?;
+    let watchdog_pool = context.get(WatchdogPool())?
+        .ok_or(Error::StateError("watchdog pool not initialized".into()))?;
+    if caller != governance && !watchdog_pool.contains(&caller) {
+        return Err(Error::Unauthorized("not governance or a watchdog".into()));
+    }
+
+    let executor_pool = context.get(ExecutorPool())?
+        .ok_or(Error::StateError("executor pool not initialized".into()))?;
+    let failed_type = get_enclave_type(context, failed_executor)
+        .ok_or(Error::ExecutorNotFound)?;
+    let slot = match failed_type {
+        EnclaveType::IntelSGX => executor_pool.sgx_executor,
+        EnclaveType::AMDSEV => executor_pool.sev_executor,
+    };
+    if slot.is_some_and(|holder| holder != failed_executor) {
+        return Err(Error::ExecutorNotFound);
+    }
+
+    if context.get(ExecutorState(failed_executor))? != Some(ExecutorStatus::Failed) {
+        return Err(Error::challenge_error("executor has not failed"));
+    }
+
+    promote_replacement(context, failed_executor)
+}
+
+/// Swaps `failed_executor` for the best eligible ready TEE of the same
+/// enclave type. Callers are responsible for authorization.
+pub(crate) fn promote_replacement(
+    context: &mut Context,
+    failed_executor: Address,
+) -> Result<()> {
     // Get pools
     let mut executor_pool = context.get(ExecutorPool())?
         .expect("executor pool not initialized");
@@ -64,13 +212,34 @@ pub fn replace_executor(
         .expect("watchdog pool not initialized");
 
     // Get failed executor type
-    let failed_type = context.get(EnclaveType(failed_executor))?
-        .expect("failed executor type not found");
+    let failed_type = get_enclave_type(context, failed_executor)
+        .ok_or(Error::ExecutorNotFound)?;
 
     // Find compatible replacement
-    let replacement_idx = watchdog_pool.ready_tees.iter()
-        .position(|(_, e_type)| *e_type == failed_type)
-        .ok_or(Error::NoAvailableWatchdog)?;
+    let candidates: Vec<(usize, Address)> = watchdog_pool.ready_tees.iter()
+        .enumerate()
+        .filter(|(_, (_, e_type))| *e_type == failed_type)
+        .map(|(idx, (addr, _))| (idx, *addr))
+        .collect();
+    if candidates.is_empty() {
+        return Err(Error::NoAvailableWatchdog);
+    }
+
+    // A TEE may have waited in the ready pool past its attestation validity;
+    // stale candidates must call `renew_attestation` before they can be promoted.
+    // Keeps with a history of missed heartbeats are passed over as well.
+    // Among eligible keeps the fewest heartbeat misses wins, then the lowest
+    // address, so every node picks the same replacement regardless of pool order.
+    let mut eligible = Vec::new();
+    for (idx, addr) in candidates {
+        if is_attestation_fresh(context, addr) && has_reliable_liveness(context, addr) {
+            eligible.push((get_heartbeat_misses(context, addr), addr, idx));
+        }
+    }
+    let replacement_idx = eligible.into_iter()
+        .min()
+        .map(|(_, _, idx)| idx)
+        .ok_or(Error::InvalidAttestation)?;
 
     // Remove from watchdog pool
     let (replacement_tee, _) = watchdog_pool.ready_tees.remove(replacement_idx);
@@ -87,18 +256,116 @@ pub fn replace_executor(
 
     // Update pools and record replacement
     watchdog_pool.last_replacement = context.timestamp();
+    let replacement_count = context.get(ReplacementCount())?.unwrap_or(0);
+    let record = ReplacementRecord {
+        failed_executor,
+        replacement: replacement_tee,
+        enclave_type: failed_type,
+        timestamp: context.timestamp(),
+    };
     
     context.store((
         (ExecutorPool(), executor_pool),
         (WatchdogPool(), watchdog_pool),
+        (ExecutorState(replacement_tee), ExecutorStatus::Active),
+        (ReplacementLog(replacement_count), record),
+        (ReplacementCount(), replacement_count + 1),
     ))?;
 
+    // Settle the failed executor's stake before its registration is dropped
+    hand_off_stake(context, failed_executor, replacement_tee);
+
+    // Drop the failed executor's registration so the address can re-join later
+    clear_participant_state(context, failed_executor);
+
     // Emit replacement event
     context.emit_event("ExecutorReplaced", &(failed_executor, replacement_tee))?;
 
     Ok(())
 }
 
+/// Removes the caller from the watchdog pool, clears its per-address state
+/// and returns any stake. Voting watchdogs can't leave while a challenge is open, nor shrink the
+/// pool below `MIN_WATCHDOGS` once the system is past `Creation`, since
+/// either could leave a challenge quorum unreachable.
+#[public]
+pub fn deregister_watchdog(context: &mut Context) -> Result<()> {
+    ensure_initialized(context);
+    let caller = context.actor();
+
+    let mut pool = context.get(WatchdogPool())?
+        .expect("watchdog pool not initialized");
+
+    let is_watchdog = pool.watchdogs.iter().any(|(addr, _)| *addr == caller);
+    let is_member = is_watchdog || pool.ready_tees.iter().any(|(addr, _)| *addr == caller);
+    assert!(is_member, "not a registered watchdog");
+
+    if is_watchdog {
+        let phase = context.get(CurrentPhase())?.unwrap_or(Phase::None);
+        if matches!(phase, Phase::ChallengeExecutor | Phase::ChallengeWatchdog)
+            || !unresolved_challenges(context).is_empty()
+        {
+            return Err(Error::challenge_error("challenge in progress"));
+        }
+        if phase != Phase::Creation && pool.watchdogs.len() <= MIN_WATCHDOGS {
+            return Err(Error::StateError("watchdog pool at minimum size".into()));
+        }
+    }
+
+    pool.watchdogs.retain(|(addr, _)| *addr != caller);
+    pool.ready_tees.retain(|(addr, _)| *addr != caller);
+    pool.health_status.remove(&caller);
+
+    context.store(WatchdogPool(), pool)?;
+    clear_participant_state(context, caller);
+    refund_stake(context, caller);
+
+    Ok(())
+}
+
+/// Reads up to `limit` replacement records, oldest first, starting at
+/// `offset`, along with the total number recorded. At most
+/// `MAX_REPLACEMENT_PAGE_SIZE` records are returned per call.
+#[public]
+pub fn get_replacement_history_page(
+    context: &mut Context,
+    offset: usize,
+    limit: usize,
+) -> Result<(Vec<ReplacementRecord>, usize)> {
+    let total = context.get(ReplacementCount())?.unwrap_or(0) as usize;
+    let end = total.min(offset.saturating_add(limit.min(MAX_REPLACEMENT_PAGE_SIZE)));
+
+    let mut records = Vec::new();
+    for index in offset..end {
+        if let Some(record) = context.get(ReplacementLog(index as u64))? {
+            records.push(record);
+        }
+    }
+
+    Ok((records, total))
+}
+
+/// Returns the TEEs waiting in the ready pool as replacement candidates
+#[public]
+pub fn get_ready_tees(context: &mut Context) -> Result<Vec<(Address, EnclaveType)>> {
+    let pool = context.get(WatchdogPool())?
+        .expect("watchdog pool not initialized");
+
+    Ok(pool.ready_tees)
+}
+
+/// Returns how many ready TEEs can replace an executor of `enclave_type`
+#[public]
+pub fn get_ready_tee_count_by_type(
+    context: &mut Context,
+    enclave_type: EnclaveType,
+) -> Result<usize> {
+    let pool = context.get(WatchdogPool())?
+        .expect("watchdog pool not initialized");
+
+    Ok(pool.ready_tees.iter().filter(|(_, e_type)| *e_type == enclave_type).count())
+}
+
 /// Checks health of all TEEs in the watchdog pool
 #[public]
 pub fn check_watchdog_pool_health(context: &mut Context) -> Result<()> {
@@ -162,6 +162,14 @@ impl Keep {
 
     /// Migration Support
 
+    pub fn enclave_type(&self) -> EnclaveType {
+        self.enclave_type.clone()
+    }
+
+    pub fn supports_migration(&self) -> bool {
+        self.enclave_type.supports_live_migration()
+    }
+
     pub async fn prepare_migration(&mut self) -> Result<MigrationPackage> {
         let mut keep = self.keep.write().await;
         let state = keep.export_state().await?;
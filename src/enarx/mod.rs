@@ -5,6 +5,7 @@ pub mod drawbridge;
 use wasmlanche::{Context, Address};
 use std::path::PathBuf;
 use std::time::{SystemTime, Duration};
+use crate::types::EnclaveType;
 
 pub use self::keep::{Keep, KeepHealth, KeepState, MigrationPackage};
 pub use self::attestation::{AttestationReport, AttestationResult};
@@ -17,6 +18,12 @@ pub struct EnarxConfig {
     pub drawbridge_config: DrawbridgeConfig,
     pub heap_size: usize,
     pub stack_size: usize,
+    /// Largest execution payload dispatched to a keep, in bytes
+    pub max_payload_size: usize,
+    /// Failed restarts of an unhealthy keep before it is migrated or replaced
+    pub max_restart_attempts: u32,
+    /// Wait after the first failed restart; doubles with each further failure
+    pub restart_backoff_base: Duration,
     pub debug: bool,
 }
 
@@ -47,6 +54,169 @@ pub struct VerificationRequirements {
     pub max_token_age: Duration,
 }
 
+/// Teardown hook used by `EnarxManager::shutdown_all`
+trait ShutdownKeep {
+    async fn shutdown(&mut self) -> Result<(), Error>;
+}
+
+impl ShutdownKeep for Keep {
+    async fn shutdown(&mut self) -> Result<(), Error> {
+        Ok(Keep::shutdown(self).await?)
+    }
+}
+
+async fn shutdown_each<'a, K: ShutdownKeep + 'a>(
+    keeps: impl IntoIterator<Item = &'a mut K>,
+) -> Result<(), Error> {
+    let mut first_error = None;
+    for keep in keeps {
+        if let Err(err) = keep.shutdown().await {
+            first_error.get_or_insert(err);
+        }
+    }
+
+    match first_error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Recovery hooks used by `EnarxManager::handle_unhealthy_keep`
+trait RecoverKeep: Sized {
+    fn enclave_type(&self) -> EnclaveType;
+    async fn restart(&mut self) -> Result<(), Error>;
+    /// Moves the keep's state into a fresh keep
+    async fn migrate(&mut self, config: &EnarxConfig) -> Result<Self, Error>;
+    /// Launches a fresh keep of the same enclave type without its state
+    async fn cold_replace(&self, config: &EnarxConfig) -> Result<Self, Error>;
+}
+
+impl RecoverKeep for Keep {
+    fn enclave_type(&self) -> EnclaveType {
+        Keep::enclave_type(self)
+    }
+
+    async fn restart(&mut self) -> Result<(), Error> {
+        Ok(Keep::restart(self).await?)
+    }
+
+    async fn migrate(&mut self, config: &EnarxConfig) -> Result<Self, Error> {
+        let migration_package = self.prepare_migration().await?;
+        Ok(Keep::receive_migration(config, migration_package).await?)
+    }
+
+    async fn cold_replace(&self, config: &EnarxConfig) -> Result<Self, Error> {
+        let mut new_keep = Keep::new(config, Keep::enclave_type(self)).await?;
+        new_keep.start().await?;
+        Ok(new_keep)
+    }
+}
+
+/// Tries to restart an unhealthy keep, backing off after each failed restart.
+/// Once the attempts are exhausted, returns the keep that should replace it:
+/// migrated where the backend allows it, otherwise started over fresh.
+async fn recover_keep<K: RecoverKeep>(
+    keep: &mut K,
+    backoff: &mut RestartBackoff,
+    config: &EnarxConfig,
+    now: SystemTime,
+) -> Result<Option<K>, Error> {
+    let action = backoff.next_action(now, config.restart_backoff_base, config.max_restart_attempts);
+
+    let escalate = match action {
+        RestartAction::Wait => return Ok(None),
+        RestartAction::Restart => {
+            if keep.restart().await.is_ok() {
+                return Ok(None);
+            }
+            backoff.record_failure(now);
+            backoff.next_action(now, config.restart_backoff_base, config.max_restart_attempts)
+                == RestartAction::Escalate
+        },
+        RestartAction::Escalate => true,
+    };
+    if !escalate {
+        return Ok(None);
+    }
+
+    let replacement = match RecoveryStrategy::for_enclave_type(&keep.enclave_type()) {
+        RecoveryStrategy::Migrate => keep.migrate(config).await?,
+        RecoveryStrategy::ColdReplace => keep.cold_replace(config).await?,
+    };
+    Ok(Some(replacement))
+}
+
+/// How an unhealthy keep that failed to restart is recovered
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecoveryStrategy {
+    /// Move the keep's state into a fresh keep
+    Migrate,
+    /// Launch a fresh keep without carrying state over
+    ColdReplace,
+}
+
+impl RecoveryStrategy {
+    pub fn for_enclave_type(enclave_type: &EnclaveType) -> Self {
+        if enclave_type.supports_live_migration() {
+            RecoveryStrategy::Migrate
+        } else {
+            RecoveryStrategy::ColdReplace
+        }
+    }
+}
+
+/// What to do with an unhealthy keep given its restart history
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestartAction {
+    /// Try restarting the keep in place
+    Restart,
+    /// Still backing off from the last failed restart
+    Wait,
+    /// Restarts are exhausted; migrate or replace the keep
+    Escalate,
+}
+
+/// Failed restart attempts for a keep, used to back off exponentially
+/// instead of restarting a crash-looping keep in a tight loop
+#[derive(Debug, Clone, Default)]
+pub struct RestartBackoff {
+    attempts: u32,
+    last_restart: Option<SystemTime>,
+}
+
+impl RestartBackoff {
+    pub fn next_action(&self, now: SystemTime, base: Duration, max_attempts: u32) -> RestartAction {
+        if self.attempts >= max_attempts {
+            return RestartAction::Escalate;
+        }
+
+        match self.last_restart {
+            Some(last_restart) => {
+                let backoff = base * 2u32.saturating_pow(self.attempts.saturating_sub(1));
+                let elapsed = now.duration_since(last_restart).unwrap_or_default();
+                if elapsed >= backoff {
+                    RestartAction::Restart
+                } else {
+                    RestartAction::Wait
+                }
+            },
+            None => RestartAction::Restart,
+        }
+    }
+
+    pub fn record_failure(&mut self, now: SystemTime) {
+        self.attempts += 1;
+        self.last_restart = Some(now);
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Launches and maintains keeps. `Drop` can't await, so owners must call
+/// `shutdown_all` before dropping the manager; otherwise the tracked keeps
+/// keep running.
 pub struct EnarxManager {
     config: EnarxConfig,
     active_keeps: Vec<ActiveKeep>,
@@ -57,6 +227,7 @@ struct ActiveKeep {
     last_health_check: SystemTime,
     last_attestation_refresh: SystemTime,
     last_token_refresh: SystemTime,
+    restart_backoff: RestartBackoff,
 }
 
 impl EnarxManager {
@@ -86,6 +257,7 @@ impl EnarxManager {
             last_health_check: SystemTime::now(),
             last_attestation_refresh: SystemTime::now(),
             last_token_refresh: SystemTime::now(),
+            restart_backoff: RestartBackoff::default(),
         });
 
         Ok(keep)
@@ -95,18 +267,22 @@ impl EnarxManager {
         let now = SystemTime::now();
         
         // Check each active Keep
-        for active_keep in &mut self.active_keeps {
+        for index in 0..self.active_keeps.len() {
             // Health check if needed
-            if now.duration_since(active_keep.last_health_check)? >= Duration::from_secs(60) {
-                let health = active_keep.keep.health_check().await?;
-                active_keep.last_health_check = now;
+            if now.duration_since(self.active_keeps[index].last_health_check)? >= Duration::from_secs(60) {
+                let health = self.active_keeps[index].keep.health_check().await?;
+                self.active_keeps[index].last_health_check = now;
                 
-                if !self.verify_keep_health(&health) {
+                if self.verify_keep_health(&health) {
+                    self.active_keeps[index].restart_backoff.reset();
+                } else {
                     // Handle unhealthy Keep
-                    self.handle_unhealthy_keep(&active_keep.keep).await?;
+                    self.handle_unhealthy_keep(index, now).await?;
                 }
             }
 
+            let active_keep = &mut self.active_keeps[index];
+
             // Refresh attestation if needed
             if now.duration_since(active_keep.last_attestation_refresh)? >= self.config.attestation_config.refresh_interval {
                 active_keep.keep.refresh_attestation().await?;
@@ -123,22 +299,33 @@ impl EnarxManager {
         Ok(())
     }
 
-    async fn handle_unhealthy_keep(&mut self, keep: &Keep) -> Result<(), Error> {
-        // Attempt recovery
-        if let Err(e) = keep.restart().await {
-            // If recovery fails, prepare for migration
-            let migration_package = keep.prepare_migration().await?;
-            
-            // Launch new Keep
-            let new_keep = Keep::receive_migration(&self.config, migration_package).await?;
-            
+    async fn handle_unhealthy_keep(&mut self, index: usize, now: SystemTime) -> Result<(), Error> {
+        let active_keep = &mut self.active_keeps[index];
+        let replacement = recover_keep(
+            &mut active_keep.keep,
+            &mut active_keep.restart_backoff,
+            &self.config,
+            now,
+        )
+        .await?;
+
+        if let Some(new_keep) = replacement {
             // Replace old Keep
-            self.replace_keep(keep.id().to_string(), new_keep).await?;
+            let old_id = self.active_keeps[index].keep.id().to_string();
+            self.replace_keep(old_id, new_keep).await?;
         }
 
         Ok(())
     }
 
+    /// Shuts down every tracked keep and empties the pool. Every keep is
+    /// attempted even if an earlier one fails; the first error is returned.
+    pub async fn shutdown_all(&mut self) -> Result<(), Error> {
+        let result = shutdown_each(self.active_keeps.iter_mut().map(|active| &mut active.keep)).await;
+        self.active_keeps.clear();
+        result
+    }
+
     async fn replace_keep(&mut self, old_id: String, new_keep: Keep) -> Result<(), Error> {
         // Find and remove old Keep
         if let Some(pos) = self.active_keeps.iter().position(|k| k.keep.id() == old_id) {
@@ -153,6 +340,7 @@ impl EnarxManager {
                 last_health_check: SystemTime::now(),
                 last_attestation_refresh: SystemTime::now(),
                 last_token_refresh: SystemTime::now(),
+                restart_backoff: RestartBackoff::default(),
             };
         }
 
@@ -191,6 +379,8 @@ pub struct EnarxConfig {
     pub drawbridge_config: DrawbridgeConfig,
     pub heap_size: usize,
     pub stack_size: usize,
+    /// Largest execution payload dispatched to a keep, in bytes
+    pub max_payload_size: usize,
     pub rotation_threshold: u64,
     pub rotation_interval: Duration,
     pub min_watchdogs: usize,
@@ -224,3 +414,189 @@ pub enum Error {
     #[error("Time error: {0}")]
     TimeError(#[from] std::time::SystemTimeError),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recovery_config(max_restart_attempts: u32) -> EnarxConfig {
+        EnarxConfig {
+            keep_binary: PathBuf::from("keep.wasm"),
+            attestation_config: AttestationConfig {
+                refresh_interval: Duration::from_secs(3600),
+                required_tcb_level: None,
+                platform_requirements: None,
+            },
+            drawbridge_config: DrawbridgeConfig {
+                token_refresh_interval: Duration::from_secs(3600),
+                verification_requirements: VerificationRequirements {
+                    require_matching_measurements: true,
+                    require_matching_platform: true,
+                    max_token_age: Duration::from_secs(3600),
+                },
+            },
+            heap_size: 1 << 20,
+            stack_size: 1 << 16,
+            max_payload_size: 1 << 20,
+            max_restart_attempts,
+            restart_backoff_base: Duration::from_secs(10),
+            debug: false,
+        }
+    }
+
+    /// Keep whose restarts always fail; replacements record how they were made
+    struct FailingKeep {
+        enclave_type: EnclaveType,
+        restarts: u32,
+        recovered_by: Option<RecoveryStrategy>,
+    }
+
+    impl FailingKeep {
+        fn new(enclave_type: EnclaveType) -> Self {
+            Self { enclave_type, restarts: 0, recovered_by: None }
+        }
+    }
+
+    impl RecoverKeep for FailingKeep {
+        fn enclave_type(&self) -> EnclaveType {
+            self.enclave_type.clone()
+        }
+
+        async fn restart(&mut self) -> Result<(), Error> {
+            self.restarts += 1;
+            Err(Error::TimeError(
+                SystemTime::UNIX_EPOCH.duration_since(SystemTime::now()).unwrap_err(),
+            ))
+        }
+
+        async fn migrate(&mut self, _config: &EnarxConfig) -> Result<Self, Error> {
+            Ok(Self {
+                recovered_by: Some(RecoveryStrategy::Migrate),
+                ..Self::new(self.enclave_type.clone())
+            })
+        }
+
+        async fn cold_replace(&self, _config: &EnarxConfig) -> Result<Self, Error> {
+            Ok(Self {
+                recovered_by: Some(RecoveryStrategy::ColdReplace),
+                ..Self::new(self.enclave_type.clone())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_capable_keep_migrates() {
+        let config = recovery_config(1);
+        let mut keep = FailingKeep::new(EnclaveType::AMDSEV);
+        let mut backoff = RestartBackoff::default();
+
+        let replacement = recover_keep(&mut keep, &mut backoff, &config, SystemTime::now())
+            .await
+            .unwrap()
+            .expect("exhausted restarts should escalate");
+
+        assert_eq!(keep.restarts, 1);
+        assert_eq!(replacement.recovered_by, Some(RecoveryStrategy::Migrate));
+        assert_eq!(replacement.enclave_type, EnclaveType::AMDSEV);
+    }
+
+    #[tokio::test]
+    async fn test_non_migratable_keep_cold_replaced() {
+        let config = recovery_config(1);
+        let mut keep = FailingKeep::new(EnclaveType::IntelSGX);
+        let mut backoff = RestartBackoff::default();
+
+        let replacement = recover_keep(&mut keep, &mut backoff, &config, SystemTime::now())
+            .await
+            .unwrap()
+            .expect("exhausted restarts should escalate");
+
+        assert_eq!(keep.restarts, 1);
+        assert_eq!(replacement.recovered_by, Some(RecoveryStrategy::ColdReplace));
+        assert_eq!(replacement.enclave_type, EnclaveType::IntelSGX);
+    }
+
+    #[tokio::test]
+    async fn test_keep_with_restarts_left_not_replaced() {
+        let config = recovery_config(3);
+        let mut keep = FailingKeep::new(EnclaveType::AMDSEV);
+        let mut backoff = RestartBackoff::default();
+        let now = SystemTime::now();
+
+        let replacement = recover_keep(&mut keep, &mut backoff, &config, now).await.unwrap();
+        assert!(replacement.is_none());
+
+        // Still backing off from the failed restart
+        let replacement = recover_keep(&mut keep, &mut backoff, &config, now).await.unwrap();
+        assert!(replacement.is_none());
+        assert_eq!(keep.restarts, 1);
+    }
+
+    #[test]
+    fn test_repeated_restart_failures_back_off_then_escalate() {
+        let base = Duration::from_secs(10);
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let mut backoff = RestartBackoff::default();
+
+        assert_eq!(backoff.next_action(start, base, 3), RestartAction::Restart);
+        backoff.record_failure(start);
+
+        // First failure waits the base delay
+        assert_eq!(backoff.next_action(start + Duration::from_secs(9), base, 3), RestartAction::Wait);
+        let second = start + Duration::from_secs(10);
+        assert_eq!(backoff.next_action(second, base, 3), RestartAction::Restart);
+        backoff.record_failure(second);
+
+        // Second failure doubles it
+        assert_eq!(backoff.next_action(second + Duration::from_secs(19), base, 3), RestartAction::Wait);
+        let third = second + Duration::from_secs(20);
+        assert_eq!(backoff.next_action(third, base, 3), RestartAction::Restart);
+        backoff.record_failure(third);
+
+        // Third failure exhausts the attempts
+        assert_eq!(backoff.next_action(third, base, 3), RestartAction::Escalate);
+        assert_eq!(
+            backoff.next_action(third + Duration::from_secs(1_000), base, 3),
+            RestartAction::Escalate
+        );
+    }
+
+    #[derive(Default)]
+    struct MockKeep {
+        shutdowns: u32,
+        fail: bool,
+    }
+
+    impl ShutdownKeep for MockKeep {
+        async fn shutdown(&mut self) -> Result<(), Error> {
+            self.shutdowns += 1;
+            if self.fail {
+                return Err(Error::TimeError(
+                    SystemTime::UNIX_EPOCH.duration_since(SystemTime::now()).unwrap_err(),
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_reaches_every_keep() {
+        let mut keeps: Vec<MockKeep> = (0..3).map(|_| MockKeep::default()).collect();
+        keeps[1].fail = true;
+
+        let result = shutdown_each(keeps.iter_mut()).await;
+
+        assert!(result.is_err());
+        assert!(keeps.iter().all(|keep| keep.shutdowns == 1));
+    }
+
+    #[test]
+    fn test_reset_clears_restart_history() {
+        let now = SystemTime::UNIX_EPOCH;
+        let mut backoff = RestartBackoff::default();
+        backoff.record_failure(now);
+        backoff.reset();
+
+        assert_eq!(backoff.next_action(now, Duration::from_secs(10), 3), RestartAction::Restart);
+    }
+}
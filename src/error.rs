@@ -1,6 +1,7 @@
 use std::fmt;
 use thiserror::Error;
 use wasmlanche::Error as WasmlancheError;
+use crate::types::Phase;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -28,6 +29,27 @@ pub enum Error {
     #[error("Execution not found")]
     ExecutionNotFound,
 
+    #[error("Execution failed: {0}")]
+    ExecutionFailed(String),
+
+    #[error("Execution already settled")]
+    ExecutionSettled,
+
+    #[error("System paused")]
+    SystemPaused,
+
+    #[error("Invalid phase: {0:?}")]
+    InvalidPhase(Phase),
+
+    #[error("Stale nonce")]
+    StaleNonce,
+
+    #[error("Unexpected result hash length: expected {expected}, got {actual}")]
+    UnexpectedHashLength { expected: usize, actual: usize },
+
+    #[error("Payload hash does not match allocation")]
+    PayloadMismatch,
+
     #[error("Invalid evidence")]
     InvalidEvidence,
 
@@ -46,6 +68,9 @@ pub enum Error {
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
     #[error("Enarx error: {0}")]
     EnarxError(String),
 
@@ -53,6 +78,31 @@ pub enum Error {
     WasmlancheError(#[from] WasmlancheError),
 }
 
+/// Why an attestation report was rejected
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum AttestationFailure {
+    #[error("empty attestation report")]
+    EmptyReport,
+
+    #[error("drawbridge token not bound to keep")]
+    TokenNotBoundToKeep,
+
+    #[error("drawbridge token revoked")]
+    RevokedToken,
+
+    #[error("malformed attestation bundle")]
+    MalformedBundle,
+
+    #[error("attestation bundle missing {0}")]
+    MissingBundleComponent(String),
+
+    #[error("attested measurement does not match registered keep")]
+    MeasurementMismatch,
+
+    #[error("attestation quote expired")]
+    ExpiredQuote,
+}
+
 // Implementation for converting from other error types
 impl From<enarx::Error> for Error {
     fn from(err: enarx::Error) -> Self {
@@ -1,46 +1,294 @@
+mod probe;
+
+pub use probe::*;
+
 use wasmlanche::{public, Context, Address};
 use crate::{
     types::*,
     state::*,
     challenge::*,  // For creating challenges
+    external::{slash_stake, distribute_rewards_if_due},
+    core::{utils::hash_message, promote_replacement},
+    error::{Error, Result},
+    MAX_THROUGHPUT_WINDOW,
+    TIEBREAKER_QUORUM,
 };
 
+/// Reserves a fresh execution id; governance only, and results are only
+/// accepted for ids reserved here. Workloads with a fixed commitment format
+/// (e.g. 48-byte SHA-384) can register the expected result hash length so
+/// malformed submissions are rejected. A payload hash commits both executors
+/// to running the same input.
+#[public]
+pub fn allocate_execution_id(
+    context: &mut Context,
+    expected_hash_len: Option<usize>,
+    payload_hash: Option<Vec<u8>>,
+) -> u128 {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let execution_id = context
+        .get(ExecutionCounter())
+        .expect("state corrupt")
+        .unwrap_or(0)
+        + 1;
+    let block_height = context.block_height();
+    context
+        .store((
+            (ExecutionCounter(), execution_id),
+            (ExecutionAllocatedAt(execution_id), block_height),
+        ))
+        .expect("failed to update execution counter");
+
+    if let Some(hash_len) = expected_hash_len {
+        assert!(hash_len > 0, "expected hash length must be positive");
+        context
+            .store_by_key(ExpectedHashLength(execution_id), hash_len)
+            .expect("failed to store expected hash length");
+    }
+
+    if let Some(payload_hash) = payload_hash {
+        context
+            .store_by_key(ExecutionPayloadHash(execution_id), payload_hash)
+            .expect("failed to store payload hash");
+    }
+
+    execution_id
+}
+
+/// Id the next `allocate_execution_id` call will return. Advisory only:
+/// another allocation landing first takes this id.
+#[public]
+pub fn peek_next_execution_id(context: &mut Context) -> u128 {
+    context
+        .get(ExecutionCounter())
+        .expect("state corrupt")
+        .unwrap_or(0)
+        + 1
+}
+
+/// Non-aborting variant of `submit_execution_result` for clients batching
+/// submissions. Every rejection `submit_execution_result` would panic on is
+/// returned instead: callers outside the executor pool get
+/// `Error::ExecutorError("unauthorized executor")`, executors that can't
+/// submit right now get `Error::InvalidAttestation` or
+/// `Error::ExecutorNotActive`, and a paused system, wrong phase, unknown or
+/// settled id, stale nonce or malformed hash each have their own variant.
+/// A rejected submission leaves state untouched.
+#[public]
+pub fn try_submit_execution_result(
+    context: &mut Context,
+    execution_id: u128,
+    result_hash: Vec<u8>,
+    payload_hash: Vec<u8>,
+    nonce: u64,
+) -> Result<()> {
+    let block_height = context.block_height();
+    try_record_execution_result(context, execution_id, result_hash, payload_hash, nonce, block_height, None)
+}
+
 #[public]
 pub fn submit_execution_result(
     context: &mut Context,
     execution_id: u128,
     result_hash: Vec<u8>,
+    payload_hash: Vec<u8>,
+    nonce: u64,
+) {
+    let block_height = context.block_height();
+    record_execution_result(context, execution_id, result_hash, payload_hash, nonce, block_height, None);
+}
+
+/// Submits a result produced at an earlier `block_height`, for relayed or
+/// chunked submissions that land after the execution itself. Heights beyond
+/// the current block are rejected so results can't be future-dated, and
+/// heights before the id was allocated so they can't be backdated.
+#[public]
+pub fn submit_execution_result_at(
+    context: &mut Context,
+    execution_id: u128,
+    result_hash: Vec<u8>,
+    payload_hash: Vec<u8>,
+    nonce: u64,
+    block_height: u64,
+) {
+    assert!(block_height <= context.block_height(), "block height in the future");
+    let allocated_at = context
+        .get(ExecutionAllocatedAt(execution_id))
+        .expect("state corrupt")
+        .unwrap_or(0);
+    assert!(block_height >= allocated_at, "block height before allocation");
+    record_execution_result(context, execution_id, result_hash, payload_hash, nonce, block_height, None);
+}
+
+/// Submits a result together with the time, attested by the executor's keep,
+/// at which it was produced. When both results of a match carry one, the
+/// executors' clocks are compared against `max_clock_skew`.
+#[public]
+pub fn submit_timed_execution_result(
+    context: &mut Context,
+    execution_id: u128,
+    result_hash: Vec<u8>,
+    payload_hash: Vec<u8>,
+    nonce: u64,
+    executed_at: u64,
+) {
+    assert!(executed_at <= context.timestamp(), "execution time in the future");
+    let block_height = context.block_height();
+    record_execution_result(
+        context,
+        execution_id,
+        result_hash,
+        payload_hash,
+        nonce,
+        block_height,
+        Some(executed_at),
+    );
+}
+
+fn record_execution_result(
+    context: &mut Context,
+    execution_id: u128,
+    result_hash: Vec<u8>,
+    payload_hash: Vec<u8>,
+    nonce: u64,
+    block_height: u64,
+    executed_at: Option<u64>,
 ) {
+    let recorded = try_record_execution_result(
+        context,
+        execution_id,
+        result_hash,
+        payload_hash,
+        nonce,
+        block_height,
+        executed_at,
+    );
+
+    match recorded {
+        Ok(()) => {}
+        Err(Error::SystemPaused) => panic!("system paused"),
+        Err(Error::InvalidAttestation) => panic!("attestation not valid"),
+        Err(Error::ExecutorNotActive) => panic!("executor not active"),
+        Err(Error::InvalidPhase(phase)) => panic!("invalid phase: {:?}", phase),
+        Err(Error::ExecutionNotFound) => panic!("execution id not allocated"),
+        Err(Error::ExecutionSettled) => panic!("execution already settled"),
+        Err(Error::StaleNonce) => panic!("stale nonce"),
+        Err(Error::UnexpectedHashLength { .. }) => panic!("unexpected result hash length"),
+        Err(Error::PayloadMismatch) => panic!("payload hash does not match allocation"),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Validates a submission and records it. Every check runs before the
+/// first write, so a rejected submission doesn't consume its nonce.
+fn try_record_execution_result(
+    context: &mut Context,
+    execution_id: u128,
+    result_hash: Vec<u8>,
+    payload_hash: Vec<u8>,
+    nonce: u64,
+    block_height: u64,
+    executed_at: Option<u64>,
+) -> Result<()> {
     let caller = context.actor();
-    
-    // Verify caller is an executor
-    let executor_pool = context
-        .get(ExecutorPool())
+    if context.get(SystemPaused()).expect("state corrupt").unwrap_or(false) {
+        return Err(Error::SystemPaused);
+    }
+
+    let enclave_type = check_submitter(context, caller)?;
+
+    // Results are only taken once both executors are up; challenges raised
+    // by earlier mismatches don't stop other executions
+    let phase = context
+        .get(CurrentPhase())
         .expect("state corrupt")
-        .expect("executor pool not initialized");
+        .unwrap_or(Phase::None);
+    if !matches!(phase, Phase::Executing | Phase::ChallengeExecutor | Phase::ChallengeWatchdog) {
+        return Err(Error::InvalidPhase(phase));
+    }
 
-    let enclave_type = if Some(caller) == executor_pool.sgx_executor {
-        EnclaveType::IntelSGX
-    } else if Some(caller) == executor_pool.sev_executor {
-        EnclaveType::AMDSEV
-    } else {
-        panic!("unauthorized executor");
-    };
+    // Results may only land on ids handed out by allocate_execution_id
+    let allocated = context
+        .get(ExecutionCounter())
+        .expect("state corrupt")
+        .unwrap_or(0);
+    if execution_id == 0 || execution_id > allocated {
+        return Err(Error::ExecutionNotFound);
+    }
+
+    // Once the pair matched or mismatched, the outcome is settled by
+    // finalization, disputes or the tiebreak; a late submission must not
+    // reopen it
+    if is_execution_settled(context, execution_id) {
+        return Err(Error::ExecutionSettled);
+    }
+
+    // Each submission must carry a fresh nonce so captured calls can't be replayed
+    if nonce <= get_last_nonce(context, caller) {
+        return Err(Error::StaleNonce);
+    }
+
+    if let Some(expected_len) = context
+        .get(ExpectedHashLength(execution_id))
+        .expect("state corrupt")
+    {
+        if result_hash.len() != expected_len {
+            return Err(Error::UnexpectedHashLength {
+                expected: expected_len,
+                actual: result_hash.len(),
+            });
+        }
+    }
+
+    if let Some(committed_payload) = context
+        .get(ExecutionPayloadHash(execution_id))
+        .expect("state corrupt")
+    {
+        if payload_hash != committed_payload {
+            return Err(Error::PayloadMismatch);
+        }
+    }
+
+    context
+        .store_by_key(ExecutorNonce(caller), nonce)
+        .expect("failed to record nonce");
+
+    // Resubmitting the identical result is a no-op
+    let previous_hash = context
+        .get(SubmittedResult(execution_id, caller))
+        .expect("state corrupt");
+    if previous_hash.as_ref() == Some(&result_hash) {
+        return Ok(());
+    }
+
+    context
+        .store_by_key(SubmittedResult(execution_id, caller), result_hash.clone())
+        .expect("failed to record submission");
 
     let result = ExecutionResult {
         result_hash,
+        payload_hash,
         execution_id,
         executor: caller,
         enclave_type,
         timestamp: context.timestamp(),
-        block_height: context.block_height(),
+        block_height,
+        executed_at,
     };
 
     // Store result
     context
-        .store_by_key(ExecutionResult(execution_id), result.clone())
+        .store_by_key(ExecutionResult(execution_id, result.enclave_type.clone()), result.clone())
         .expect("failed to store result");
 
+    update_global_state(
+        context,
+        "record_execution_result",
+        &[&execution_id.to_le_bytes(), &result.result_hash, &result.payload_hash],
+    );
+
     // Add to pending verifications if this is the first result
     let mut pending = context
         .get(PendingVerifications())
@@ -56,35 +304,153 @@ pub fn submit_execution_result(
         // If this is the second result, verify match
         verify_execution_match(context, execution_id);
     }
+
+    Ok(())
 }
 
-fn verify_execution_match(context: &mut Context, execution_id: u128) {
-    let result = context
-        .get(ExecutionResult(execution_id))
+/// Whether both results for the execution are in and compared, so further
+/// submissions would overwrite a settled outcome
+fn is_execution_settled(context: &mut Context, execution_id: u128) -> bool {
+    context.get(ExecutionVerified(execution_id)).expect("state corrupt").unwrap_or(false)
+        || context.get(TentativeMatch(execution_id)).expect("state corrupt").is_some()
+        || context.get(ExecutionMismatches(execution_id)).expect("state corrupt").is_some()
+}
+
+/// Enclave type of `caller` if it is an attested, active executor
+fn check_submitter(context: &mut Context, caller: Address) -> Result<EnclaveType> {
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    let enclave_type = if Some(caller) == executor_pool.sgx_executor {
+        EnclaveType::IntelSGX
+    } else if Some(caller) == executor_pool.sev_executor {
+        EnclaveType::AMDSEV
+    } else {
+        return Err(Error::executor_error("unauthorized executor"));
+    };
+
+    if !context.get(AttestationStatus(caller)).expect("state corrupt").unwrap_or(false) {
+        return Err(Error::InvalidAttestation);
+    }
+    if context.get(ExecutorState(caller)).expect("state corrupt") != Some(ExecutorStatus::Active) {
+        return Err(Error::ExecutorNotActive);
+    }
+
+    Ok(enclave_type)
+}
+
+/// Whether the executors reported running different payloads for an execution
+#[public]
+pub fn has_input_mismatch(context: &mut Context, execution_id: u128) -> bool {
+    context
+        .get(ExecutionInputMismatch(execution_id))
+        .expect("state corrupt")
+        .unwrap_or(false)
+}
+
+/// Whether the executors last attested matching results too far apart in time
+#[public]
+pub fn has_clock_skew(context: &mut Context, execution_id: u128) -> bool {
+    context
+        .get(ExecutionClockSkewed(execution_id))
         .expect("state corrupt")
-        .expect("no execution result found");
+        .unwrap_or(false)
+}
+
+/// Whether both results carry attested execution times further apart than
+/// the configured `max_clock_skew`
+fn is_clock_skewed(context: &mut Context, sgx: &ExecutionResult, sev: &ExecutionResult) -> bool {
+    match (sgx.executed_at, sev.executed_at) {
+        (Some(sgx_time), Some(sev_time)) => {
+            sgx_time.abs_diff(sev_time) > load_system_config(context).max_clock_skew
+        },
+        _ => false,
+    }
+}
+
+/// Highest nonce accepted from `executor` so far
+#[public]
+pub fn get_last_nonce(context: &mut Context, executor: Address) -> u64 {
+    context
+        .get(ExecutorNonce(executor))
+        .expect("state corrupt")
+        .unwrap_or(0)
+}
 
+fn verify_execution_match(context: &mut Context, execution_id: u128) {
     // Get both executor results
     let sgx_result = get_executor_result(context, execution_id, EnclaveType::IntelSGX);
     let sev_result = get_executor_result(context, execution_id, EnclaveType::AMDSEV);
 
     match (sgx_result, sev_result) {
         (Some(sgx), Some(sev)) => {
-            if sgx.result_hash == sev.result_hash {
-                // Results match
+            if sgx.payload_hash != sev.payload_hash {
+                // The executors ran different inputs, so their outputs can't be compared
                 context
-                    .store_by_key(ExecutionVerified(execution_id), true)
-                    .expect("failed to mark verification");
-                
-                // Log successful verification
-                log_verification_success(context, execution_id, &sgx, &sev);
+                    .store_by_key(ExecutionInputMismatch(execution_id), true)
+                    .expect("failed to record input mismatch");
+                context
+                    .emit_event("ExecutionInputMismatch", &execution_id)
+                    .expect("failed to emit input mismatch event");
+                log_warn!("Execution {} input mismatch between executors", execution_id);
+            } else if sgx.result_hash == sev.result_hash
+                && is_clock_skewed(context, &sgx, &sev)
+            {
+                // Agreeing results attested too far apart point to a
+                // desynchronized or spoofed clock, so the match isn't
+                // trusted. Both results are discarded and the execution
+                // stays pending until the executors resubmit.
+                context
+                    .store_by_key(ExecutionClockSkewed(execution_id), true)
+                    .expect("failed to record clock skew");
+                for executor in [sgx.executor, sev.executor] {
+                    context
+                        .remove(SubmittedResult(execution_id, executor))
+                        .expect("failed to clear submission");
+                }
+                for enclave_type in [EnclaveType::IntelSGX, EnclaveType::AMDSEV] {
+                    context
+                        .remove(ExecutionResult(execution_id, enclave_type))
+                        .expect("failed to clear execution result");
+                }
+                context
+                    .emit_event("ExecutionClockSkewExceeded", &execution_id)
+                    .expect("failed to emit clock skew event");
+                log_warn!(
+                    "Execution {} clock skew between executors: sgx={:?} sev={:?}",
+                    execution_id,
+                    sgx.executed_at,
+                    sev.executed_at,
+                );
+                return;
+            } else if sgx.result_hash == sev.result_hash {
+                context
+                    .remove(ExecutionClockSkewed(execution_id))
+                    .expect("failed to clear clock skew");
+                // Results match; hold them open for disputes if configured,
+                // since matching hashes alone can't rule out collusion
+                let dispute_window = load_system_config(context).execution_dispute_window;
+                if dispute_window == 0 {
+                    finalize_verified_execution(context, execution_id, &sgx, &sev);
+                } else {
+                    context
+                        .store_by_key(TentativeMatch(execution_id), context.timestamp() + dispute_window)
+                        .expect("failed to record tentative match");
+                }
             } else {
                 // Results don't match - store mismatch and trigger challenge
-                context
-                    .store_by_key(ExecutionMismatches(execution_id), (sgx.clone(), sev.clone()))
-                    .expect("failed to store mismatch");
+                store_execution_mismatch(context, execution_id, sgx.clone(), sev.clone());
                 
-                handle_execution_mismatch(context, execution_id);
+                if load_system_config(context).auto_challenge_on_mismatch {
+                    escalate_execution_mismatch(context, execution_id, &sgx, &sev);
+                } else {
+                    context
+                        .emit_event("ExecutionMismatchFlagged", &execution_id)
+                        .expect("failed to emit mismatch event");
+                }
+                record_mismatch(context);
                 
                 // Log mismatch
                 log_verification_failure(context, execution_id, &sgx, &sev);
@@ -107,78 +473,874 @@ fn verify_execution_match(context: &mut Context, execution_id: u128) {
         .expect("failed to update pending verifications");
 }
 
-fn handle_execution_mismatch(context: &mut Context, execution_id: u128) {
-    // Transition to challenge phase
+fn finalize_verified_execution(
+    context: &mut Context,
+    execution_id: u128,
+    sgx: &ExecutionResult,
+    sev: &ExecutionResult,
+) {
     context
-        .store_by_key(CurrentPhase(), Phase::ChallengeExecutor)
-        .expect("failed to update phase");
+        .store_by_key(ExecutionVerified(execution_id), true)
+        .expect("failed to mark verification");
 
-    // Create challenges for both executors to provide proof of their results
-    let (sgx, sev) = context
-        .get(ExecutionMismatches(execution_id))
-        .expect("state corrupt")
-        .expect("no mismatch found");
+    increment_keep_execution_count(context, sgx.executor);
+    increment_keep_execution_count(context, sev.executor);
+    reset_mismatch_counters(context, sgx.executor);
+    reset_mismatch_counters(context, sev.executor);
 
-    // Create challenge for verification
-    let challenge_data = create_verification_challenge(execution_id, &sgx, &sev);
+    record_verified_execution(context, execution_id);
 
-    // Store challenge for both executors
-    create_dual_challenge(context, sgx.executor, sev.executor, challenge_data);
+    // Log successful verification
+    log_verification_success(context, execution_id, sgx, sev);
+
+    distribute_rewards_if_due(context);
 }
 
+/// Marks a tentative match as verified once its dispute window has passed
+/// without a watchdog challenge. Callable by anyone.
 #[public]
-pub fn verify_execution(
-    context: &mut Context,
-    execution_id: u128,
-) -> bool {
+pub fn finalize_execution(context: &mut Context, execution_id: u128) {
+    ensure_initialized(context);
+
+    let dispute_deadline = context
+        .get(TentativeMatch(execution_id))
+        .expect("state corrupt")
+        .expect("no tentative match");
+    assert!(context.timestamp() > dispute_deadline, "dispute window still open");
+    assert!(
+        context.get(ExecutionDispute(execution_id)).expect("state corrupt").is_none(),
+        "execution disputed"
+    );
+
+    let sgx = get_executor_result(context, execution_id, EnclaveType::IntelSGX)
+        .expect("no SGX result found");
+    let sev = get_executor_result(context, execution_id, EnclaveType::AMDSEV)
+        .expect("no SEV result found");
+
     context
-        .get(ExecutionVerified(execution_id))
+        .remove(TentativeMatch(execution_id))
+        .expect("failed to clear tentative match");
+    finalize_verified_execution(context, execution_id, &sgx, &sev);
+}
+
+/// Whether a watchdog can still dispute the execution: its results matched,
+/// its dispute window is open and no dispute has been raised yet
+#[public]
+pub fn is_execution_disputable(context: &mut Context, execution_id: u128) -> bool {
+    let Some(dispute_deadline) = context
+        .get(TentativeMatch(execution_id))
         .expect("state corrupt")
-        .unwrap_or(false)
+    else {
+        return false;
+    };
+
+    context.timestamp() <= dispute_deadline
+        && context.get(ExecutionDispute(execution_id)).expect("state corrupt").is_none()
 }
 
+/// Lets a watchdog challenge a tentative match before its dispute window
+/// closes, e.g. when it suspects both executors colluded. Both executors are
+/// challenged with the same evidence, one challenge each in SGX, SEV order,
+/// and the disputed execution can no longer be finalized.
 #[public]
-pub fn get_execution_result(
+pub fn dispute_execution(
     context: &mut Context,
     execution_id: u128,
-) -> Option<ExecutionResult> {
+    evidence: ChallengeEvidence,
+) -> Vec<Challenge> {
+    ensure_initialized(context);
+
+    let dispute_deadline = context
+        .get(TentativeMatch(execution_id))
+        .expect("state corrupt")
+        .expect("no tentative match");
+    assert!(context.timestamp() <= dispute_deadline, "dispute window closed");
+    assert!(
+        context.get(ExecutionDispute(execution_id)).expect("state corrupt").is_none(),
+        "execution already disputed"
+    );
+
+    let ChallengeEvidence::ExecutionEvidence { result_hash, execution_proof, keep_measurement } =
+        evidence
+    else {
+        panic!("dispute rejected: execution evidence required");
+    };
+
+    let sgx = get_executor_result(context, execution_id, EnclaveType::IntelSGX)
+        .expect("no SGX result found");
+    let sev = get_executor_result(context, execution_id, EnclaveType::AMDSEV)
+        .expect("no SEV result found");
+
+    let mut challenges = Vec::with_capacity(2);
+    for executor in [sgx.executor, sev.executor] {
+        let evidence = ChallengeEvidence::ExecutionEvidence {
+            result_hash: result_hash.clone(),
+            execution_proof: execution_proof.clone(),
+            keep_measurement: keep_measurement.clone(),
+        };
+        let challenge = challenge_executor(context, executor, ChallengeType::Execution, evidence)
+            .unwrap_or_else(|err| panic!("dispute rejected: {}", err));
+        context
+            .store_by_key(DisputedExecution(challenge.id), execution_id)
+            .expect("failed to record dispute");
+        challenges.push(challenge);
+    }
+
+    let challenge_ids: Vec<u128> = challenges.iter().map(|challenge| challenge.id).collect();
+    context
+        .store_by_key(ExecutionDispute(execution_id), challenge_ids.clone())
+        .expect("failed to record dispute");
+    context
+        .emit_event("ExecutionDisputed", &(execution_id, challenge_ids))
+        .expect("failed to emit dispute event");
+
+    challenges
+}
+
+/// Settles a dispute once the challenges against both executors have
+/// resolved; until then each resolution is only recorded. The dispute is
+/// refuted only if both executors were cleared, which clears the way for
+/// finalization, immediately if the window has already passed; otherwise it
+/// is upheld and both results are discarded so the execution is re-run.
+pub(crate) fn settle_execution_dispute(context: &mut Context, challenge: &Challenge) {
+    let Some(execution_id) = context
+        .get(DisputedExecution(challenge.id))
+        .expect("state corrupt")
+    else {
+        return;
+    };
+
+    context
+        .remove(DisputedExecution(challenge.id))
+        .expect("failed to clear disputed execution");
+
+    let challenge_ids = context
+        .get(ExecutionDispute(execution_id))
+        .expect("state corrupt")
+        .unwrap_or_default();
+    let mut upheld = challenge.status != ChallengeStatus::Verified;
+    for other_id in challenge_ids.iter().filter(|id| **id != challenge.id) {
+        // The other executor's challenge is still open
+        if context.get(DisputedExecution(*other_id)).expect("state corrupt").is_some() {
+            return;
+        }
+        let other_status = context
+            .get(Challenge(*other_id))
+            .expect("state corrupt")
+            .map(|other| other.status);
+        upheld |= other_status != Some(ChallengeStatus::Verified);
+    }
+
+    context
+        .remove(ExecutionDispute(execution_id))
+        .expect("failed to clear dispute");
+
+    let Some(dispute_deadline) = context
+        .get(TentativeMatch(execution_id))
+        .expect("state corrupt")
+    else {
+        return;
+    };
+    let sgx = get_executor_result(context, execution_id, EnclaveType::IntelSGX)
+        .expect("no SGX result found");
+    let sev = get_executor_result(context, execution_id, EnclaveType::AMDSEV)
+        .expect("no SEV result found");
+
+    if !upheld {
+        if context.timestamp() > dispute_deadline {
+            context
+                .remove(TentativeMatch(execution_id))
+                .expect("failed to clear tentative match");
+            finalize_verified_execution(context, execution_id, &sgx, &sev);
+        }
+    } else {
+        context
+            .remove(TentativeMatch(execution_id))
+            .expect("failed to clear tentative match");
+        for executor in [sgx.executor, sev.executor] {
+            context
+                .remove(SubmittedResult(execution_id, executor))
+                .expect("failed to clear submission");
+        }
+        for enclave_type in [EnclaveType::IntelSGX, EnclaveType::AMDSEV] {
+            context
+                .remove(ExecutionResult(execution_id, enclave_type))
+                .expect("failed to clear execution result");
+        }
+    }
+
     context
-        .get(ExecutionResult(execution_id))
+        .emit_event("ExecutionDisputeSettled", &(execution_id, challenge_ids, upheld))
+        .expect("failed to emit dispute settlement event");
+}
+
+/// Counts a mismatch the executor lost. Past the configured threshold the
+/// executor is suspended and challenged, since a keep that keeps ending up on
+/// the losing side is likely faulty.
+fn record_lost_mismatch(context: &mut Context, execution_id: u128, executor: Address) {
+    let lost = context
+        .get(ConsecutiveMismatches(executor))
         .expect("state corrupt")
+        .unwrap_or(0)
+        + 1;
+    context
+        .store_by_key(ConsecutiveMismatches(executor), lost)
+        .expect("failed to record lost mismatch");
+
+    if lost < load_system_config(context).mismatch_suspension_threshold {
+        return;
+    }
+
+    context
+        .store_by_key(ExecutorState(executor), ExecutorStatus::Suspended)
+        .expect("failed to suspend executor");
+    context
+        .emit_event("ExecutorSuspended", &(executor, lost))
+        .expect("failed to emit suspension event");
+    log_error!("Executor {:?} suspended after {} lost mismatches", executor, lost);
+
+    if let Err(err) = open_system_challenge(
+        context,
+        executor,
+        ChallengeType::Execution,
+        execution_id.to_le_bytes().to_vec(),
+    ) {
+        log_warn!("Could not challenge suspended executor {:?}: {}", executor, err);
+    }
+}
+
+fn reset_mismatch_counters(context: &mut Context, executor: Address) {
+    context
+        .remove(ConsecutiveMismatches(executor))
+        .expect("failed to reset consecutive mismatches");
+    context
+        .remove(MismatchStrikes(executor))
+        .expect("failed to reset mismatch strikes");
 }
 
+/// Unresolved verification mismatches recorded against `executor`
 #[public]
-pub fn get_pending_verifications(
-    context: &mut Context,
-) -> Vec<u128> {
+pub fn get_mismatch_strikes(context: &mut Context, executor: Address) -> u64 {
     context
-        .get(PendingVerifications())
+        .get(MismatchStrikes(executor))
         .expect("state corrupt")
-        .unwrap_or_default()
+        .unwrap_or(0)
+}
+
+/// Appends to the verified list and the block-height log used for throughput,
+/// dropping log entries older than `MAX_THROUGHPUT_WINDOW`
+fn record_verified_execution(context: &mut Context, execution_id: u128) {
+    let block_height = context.block_height();
+
+    let mut verified = context
+        .get(VerifiedExecutions())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    verified.push(execution_id);
+
+    let mut verification_blocks = context
+        .get(VerificationBlocks())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    verification_blocks.retain(|&height| block_height.saturating_sub(height) < MAX_THROUGHPUT_WINDOW);
+    verification_blocks.push(block_height);
+
+    context
+        .store((
+            (VerifiedExecutions(), verified),
+            (VerificationBlocks(), verification_blocks),
+        ))
+        .expect("failed to record verified execution");
 }
 
+/// Number of executions verified within the last `window_blocks` blocks,
+/// including the current one. Windows are capped at `MAX_THROUGHPUT_WINDOW`.
 #[public]
-pub fn get_verification_mismatch(
-    context: &mut Context,
-    execution_id: u128,
-) -> Option<(ExecutionResult, ExecutionResult)> {
+pub fn get_verification_throughput(context: &mut Context, window_blocks: u64) -> u64 {
+    let block_height = context.block_height();
+    let window = window_blocks.min(MAX_THROUGHPUT_WINDOW);
+
     context
-        .get(ExecutionMismatches(execution_id))
+        .get(VerificationBlocks())
         .expect("state corrupt")
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|&height| block_height.saturating_sub(height) < window)
+        .count() as u64
 }
 
-// Helper functions
-fn get_executor_result(
+/// Graduated response to a mismatch. A mismatch can't tell which side is at
+/// fault, so both executors take a strike: early strikes warn and clear the
+/// submissions so the pair re-executes, later ones challenge the pair, and
+/// persistent mismatches also mark the execution so the executor the
+/// tiebreak finds at fault is removed.
+fn escalate_execution_mismatch(
     context: &mut Context,
     execution_id: u128,
-    enclave_type: EnclaveType,
-) -> Option<ExecutionResult> {
-    if let Some(result) = context.get(ExecutionResult(execution_id)).expect("state corrupt") {
-        if result.enclave_type == enclave_type {
-            return Some(result);
+    sgx: &ExecutionResult,
+    sev: &ExecutionResult,
+) {
+    let config = load_system_config(context);
+    let mut strikes = 0;
+    for executor in [sgx.executor, sev.executor] {
+        let executor_strikes = get_mismatch_strikes(context, executor) + 1;
+        context
+            .store_by_key(MismatchStrikes(executor), executor_strikes)
+            .expect("failed to record mismatch strike");
+        strikes = strikes.max(executor_strikes);
+    }
+
+    if strikes >= config.mismatch_removal_threshold {
+        context
+            .store_by_key(MismatchRemovalPending(execution_id), strikes)
+            .expect("failed to record pending removal");
+        handle_execution_mismatch(context, execution_id);
+    } else if strikes >= config.mismatch_challenge_threshold {
+        handle_execution_mismatch(context, execution_id);
+    } else {
+        for executor in [sgx.executor, sev.executor] {
+            context
+                .remove(SubmittedResult(execution_id, executor))
+                .expect("failed to clear submission");
+        }
+        for enclave_type in [EnclaveType::IntelSGX, EnclaveType::AMDSEV] {
+            context
+                .remove(ExecutionResult(execution_id, enclave_type))
+                .expect("failed to clear execution result");
+        }
+        context
+            .remove(ExecutionMismatches(execution_id))
+            .expect("failed to clear mismatch");
+        context
+            .emit_event("ExecutionMismatchWarning", &(execution_id, strikes))
+            .expect("failed to emit mismatch warning");
+        log_warn!("Execution {} mismatch warning, requesting re-execution", execution_id);
+    }
+}
+
+/// Marks the executor failed and promotes a ready TEE in its place. Without
+/// an eligible replacement the pair can't verify anything, so the system
+/// enters the crashed phase.
+fn remove_mismatched_executor(context: &mut Context, executor: Address, strikes: u64) {
+    context
+        .store_by_key(ExecutorState(executor), ExecutorStatus::Failed)
+        .expect("failed to mark executor failed");
+    context
+        .emit_event("ExecutorRemoved", &(executor, strikes))
+        .expect("failed to emit removal event");
+    log_error!("Executor {:?} removed after {} mismatch strikes", executor, strikes);
+
+    if let Err(err) = promote_replacement(context, executor) {
+        log_warn!("No replacement for removed executor {:?}: {}", executor, err);
+        enter_crashed_phase(context);
+    }
+}
+
+fn handle_execution_mismatch(context: &mut Context, execution_id: u128) {
+    // Transition to challenge phase
+    context
+        .store_by_key(CurrentPhase(), Phase::ChallengeExecutor)
+        .expect("failed to update phase");
+
+    // Create challenges for both executors to provide proof of their results
+    let (sgx, sev) = load_execution_mismatch(context, execution_id)
+        .expect("no mismatch found");
+
+    // Create challenge for verification
+    let challenge_data = create_verification_challenge(execution_id, &sgx, &sev);
+
+    // Store challenge for both executors
+    create_dual_challenge(context, execution_id, sgx.executor, sev.executor, challenge_data);
+}
+
+/// Tracks mismatch timestamps in a rolling window and pauses the system when
+/// the configured threshold is reached, since a burst of disagreements points
+/// at a systemic compromise. Only governance can unpause.
+fn record_mismatch(context: &mut Context) {
+    let config = load_system_config(context);
+    let now = context.timestamp();
+
+    let mut recent = context
+        .get(RecentMismatches())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    recent.retain(|&timestamp| now.saturating_sub(timestamp) < config.mismatch_pause_window);
+    recent.push(now);
+
+    let paused = recent.len() >= config.mismatch_pause_threshold;
+    let mismatch_count = recent.len();
+    context
+        .store_by_key(RecentMismatches(), recent)
+        .expect("failed to record mismatch");
+
+    if paused {
+        context
+            .store_by_key(SystemPaused(), true)
+            .expect("failed to pause system");
+        context
+            .emit_event("SystemPausedAlert", &(mismatch_count, now))
+            .expect("failed to emit pause alert");
+        log_error!("system paused after {} mismatches", mismatch_count);
+    }
+}
+
+/// Lets a ready TEE from the watchdog pool break an SGX/SEV disagreement by
+/// executing the same payload. A vote matching neither side is discarded so
+/// the tiebreak can be retried. Once `TIEBREAKER_QUORUM` ready TEEs agree with
+/// one side and the agreeing results satisfy the enclave diversity policy, the
+/// execution is verified toward that side, the minority executor is slashed
+/// and the mismatch challenges are closed. A minority past the removal
+/// threshold is removed as well. Returns whether the mismatch was resolved.
+#[public]
+pub fn submit_tiebreaker_result(
+    context: &mut Context,
+    execution_id: u128,
+    result_hash: Vec<u8>,
+) -> bool {
+    ensure_initialized(context);
+    let caller = context.actor();
+
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+    let tiebreaker_type = watchdog_pool
+        .ready_tees
+        .iter()
+        .find(|(addr, _)| *addr == caller)
+        .map(|(_, enclave_type)| enclave_type.clone())
+        .expect("unauthorized tiebreaker");
+
+    let (sgx, sev) = load_execution_mismatch(context, execution_id)
+        .expect("no mismatch to resolve");
+    assert!(
+        caller != sgx.executor && caller != sev.executor,
+        "tiebreaker must be a third party"
+    );
+    assert!(!verify_execution(context, execution_id), "execution already verified");
+
+    let mut votes = context
+        .get(TiebreakerVotes(execution_id))
+        .expect("state corrupt")
+        .unwrap_or_default();
+    assert!(
+        votes.iter().all(|(voter, _, _)| *voter != caller),
+        "tiebreaker already submitted"
+    );
+
+    let (majority, minority) = if result_hash == sgx.result_hash {
+        (sgx, sev)
+    } else if result_hash == sev.result_hash {
+        (sev, sgx)
+    } else {
+        log_warn!(
+            "Tiebreaker matched neither executor: id={} hash={:?}",
+            execution_id,
+            result_hash,
+        );
+        return false;
+    };
+
+    votes.push((caller, tiebreaker_type, result_hash.clone()));
+    context
+        .store_by_key(TiebreakerVotes(execution_id), votes.clone())
+        .expect("failed to store tiebreaker vote");
+
+    let mut agreeing_types = vec![majority.enclave_type.clone()];
+    agreeing_types.extend(
+        votes
+            .into_iter()
+            .filter(|(_, _, hash)| *hash == result_hash)
+            .map(|(_, enclave_type, _)| enclave_type),
+    );
+    if agreeing_types.len() - 1 < TIEBREAKER_QUORUM {
+        return false;
+    }
+
+    if !quorum_meets_diversity(context, &agreeing_types) {
+        log_warn!(
+            "Tiebreaker quorum lacks enclave diversity: id={} enclave={:?}",
+            execution_id,
+            majority.enclave_type,
+        );
+        return false;
+    }
+
+    context
+        .store_by_key(ExecutionVerified(execution_id), true)
+        .expect("failed to mark verification");
+    increment_keep_execution_count(context, majority.executor);
+
+    record_verified_execution(context, execution_id);
+
+    let slashed = slash_stake(context, minority.executor);
+    log_info!(
+        "Tiebreaker resolved execution: id={} majority={:?} slashed={:?} amount={}",
+        execution_id,
+        majority.enclave_type,
+        minority.enclave_type,
+        slashed,
+    );
+
+    reset_mismatch_counters(context, majority.executor);
+    record_lost_mismatch(context, execution_id, minority.executor);
+    close_mismatch_challenges(context, execution_id, majority.executor);
+
+    if let Some(strikes) = context
+        .get(MismatchRemovalPending(execution_id))
+        .expect("state corrupt")
+    {
+        context
+            .remove(MismatchRemovalPending(execution_id))
+            .expect("failed to clear pending removal");
+        remove_mismatched_executor(context, minority.executor, strikes);
+    }
+
+    true
+}
+
+/// Resolves the challenges opened for a mismatch in favour of `majority` and
+/// leaves the challenge phase once nothing else is outstanding
+fn close_mismatch_challenges(context: &mut Context, execution_id: u128, majority: Address) {
+    let challenge_ids = context
+        .get(MismatchChallenges(execution_id))
+        .expect("state corrupt")
+        .unwrap_or_default();
+
+    for challenge_id in challenge_ids {
+        let Some(mut challenge) = context.get(Challenge(challenge_id)).expect("state corrupt") else {
+            continue;
+        };
+        if !matches!(challenge.status, ChallengeStatus::Pending | ChallengeStatus::Responded) {
+            continue;
+        }
+
+        challenge.status = if challenge.challenged == majority {
+            ChallengeStatus::Verified
+        } else {
+            ChallengeStatus::Failed
+        };
+        notify_challenge_resolved(context, &challenge);
+        context
+            .store_by_key(Challenge(challenge_id), challenge)
+            .expect("failed to update challenge");
+    }
+    context
+        .remove(MismatchChallenges(execution_id))
+        .expect("failed to clear mismatch challenges");
+
+    let phase = context.get(CurrentPhase()).expect("state corrupt");
+    if phase == Some(Phase::ChallengeExecutor) && unresolved_challenges(context).is_empty() {
+        context
+            .store_by_key(CurrentPhase(), Phase::Executing)
+            .expect("failed to update phase");
+    }
+}
+
+#[public]
+pub fn verify_execution(
+    context: &mut Context,
+    execution_id: u128,
+) -> bool {
+    context
+        .get(ExecutionVerified(execution_id))
+        .expect("state corrupt")
+        .unwrap_or(false)
+}
+
+/// Result submitted for an execution by the executor of `enclave_type`
+#[public]
+pub fn get_execution_result(
+    context: &mut Context,
+    execution_id: u128,
+    enclave_type: EnclaveType,
+) -> Option<ExecutionResult> {
+    context
+        .get(ExecutionResult(execution_id, enclave_type))
+        .expect("state corrupt")
+}
+
+#[public]
+pub fn get_pending_verifications(
+    context: &mut Context,
+) -> Vec<u128> {
+    context
+        .get(PendingVerifications())
+        .expect("state corrupt")
+        .unwrap_or_default()
+}
+
+/// Each pending verification with the seconds elapsed since its first result
+/// was submitted, in pending order
+#[public]
+pub fn get_pending_verification_ages(context: &mut Context) -> Vec<(u128, u64)> {
+    let now = context.timestamp();
+
+    get_pending_verifications(context)
+        .into_iter()
+        .filter_map(|execution_id| {
+            let first_submitted = [EnclaveType::IntelSGX, EnclaveType::AMDSEV]
+                .into_iter()
+                .filter_map(|enclave_type| get_executor_result(context, execution_id, enclave_type))
+                .map(|result| result.timestamp)
+                .min()?;
+            Some((execution_id, now.saturating_sub(first_submitted)))
+        })
+        .collect()
+}
+
+/// Mismatched results for an execution, ordered `(SGX, SEV)`
+#[public]
+pub fn get_verification_mismatch(
+    context: &mut Context,
+    execution_id: u128,
+) -> Option<(ExecutionResult, ExecutionResult)> {
+    load_execution_mismatch(context, execution_id)
+}
+
+/// Both mismatched results together with the execution's payload hash, or
+/// `None` if the execution did not mismatch
+#[public]
+pub fn get_mismatch_evidence(
+    context: &mut Context,
+    execution_id: u128,
+) -> Option<MismatchEvidence> {
+    let (sgx_result, sev_result) = load_execution_mismatch(context, execution_id)?;
+    let payload_hash = context
+        .get(ExecutionPayloadHash(execution_id))
+        .expect("state corrupt")
+        .unwrap_or_else(|| sgx_result.payload_hash.clone());
+
+    Some(MismatchEvidence {
+        execution_id,
+        sgx_result,
+        sev_result,
+        payload_hash,
+    })
+}
+
+/// Returns the verification outcome for each id: `None` while pending,
+/// `Some(true)` once verified and `Some(false)` if the results mismatched
+#[public]
+pub fn get_verification_statuses(
+    context: &mut Context,
+    execution_ids: Vec<u128>,
+) -> Vec<(u128, Option<bool>)> {
+    execution_ids
+        .into_iter()
+        .map(|execution_id| {
+            let verified = context
+                .get(ExecutionVerified(execution_id))
+                .expect("state corrupt")
+                .unwrap_or(false);
+
+            let status = if verified {
+                Some(true)
+            } else if context
+                .get(ExecutionMismatches(execution_id))
+                .expect("state corrupt")
+                .is_some()
+            {
+                Some(false)
+            } else {
+                None
+            };
+
+            (execution_id, status)
+        })
+        .collect()
+}
+
+#[public]
+pub fn get_keep_execution_count(
+    context: &mut Context,
+    executor: Address,
+) -> u64 {
+    context
+        .get(KeepExecutionCount(executor))
+        .expect("state corrupt")
+        .unwrap_or(0)
+}
+
+/// Removes verified execution records below `older_than_block`. Each pruned
+/// record is folded into `PrunedExecutionsDigest` so history remains auditable.
+/// Pending and unverified executions are never pruned.
+#[public]
+pub fn prune_executions(
+    context: &mut Context,
+    older_than_block: u64,
+) -> usize {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let verified = context
+        .get(VerifiedExecutions())
+        .expect("state corrupt")
+        .unwrap_or_default();
+
+    let mut digest = context
+        .get(PrunedExecutionsDigest())
+        .expect("state corrupt")
+        .unwrap_or_default();
+
+    let mut retained = Vec::new();
+    let mut pruned = 0;
+
+    for execution_id in verified {
+        if prune_execution_record(context, execution_id, older_than_block, &mut digest) {
+            pruned += 1;
+        } else {
+            retained.push(execution_id);
+        }
+    }
+
+    context
+        .store((
+            (VerifiedExecutions(), retained),
+            (PrunedExecutionsDigest(), digest),
+        ))
+        .expect("failed to update pruned executions");
+
+    pruned
+}
+
+/// Like `prune_executions`, but examines at most `max_batch` records per call
+/// and stores a resume cursor so large histories can be pruned across several
+/// transactions. Returns whether the sweep has reached the end; changing
+/// `older_than_block` restarts it from the beginning.
+#[public]
+pub fn prune_executions_batch(
+    context: &mut Context,
+    older_than_block: u64,
+    max_batch: usize,
+) -> bool {
+    ensure_initialized(context);
+    ensure_governance(context);
+    assert!(max_batch > 0, "batch size must be positive");
+
+    let mut position = context
+        .get(PruneCursor())
+        .expect("state corrupt")
+        .filter(|(cursor_block, _)| *cursor_block == older_than_block)
+        .map_or(0, |(_, position)| position);
+
+    let mut verified = context
+        .get(VerifiedExecutions())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    let mut digest = context
+        .get(PrunedExecutionsDigest())
+        .expect("state corrupt")
+        .unwrap_or_default();
+
+    let mut examined = 0;
+    while position < verified.len() && examined < max_batch {
+        let execution_id = verified[position];
+        if prune_execution_record(context, execution_id, older_than_block, &mut digest) {
+            verified.remove(position);
+        } else {
+            position += 1;
         }
+        examined += 1;
+    }
+
+    let complete = position >= verified.len();
+    context
+        .store((
+            (VerifiedExecutions(), verified),
+            (PrunedExecutionsDigest(), digest),
+        ))
+        .expect("failed to update pruned executions");
+    if complete {
+        context
+            .remove(PruneCursor())
+            .expect("failed to clear prune cursor");
+    } else {
+        context
+            .store_by_key(PruneCursor(), (older_than_block, position))
+            .expect("failed to store prune cursor");
+    }
+
+    complete
+}
+
+/// Removes a verified execution's records if it is older than
+/// `older_than_block`, folding it into `digest`. Returns whether it was pruned.
+fn prune_execution_record(
+    context: &mut Context,
+    execution_id: u128,
+    older_than_block: u64,
+    digest: &mut Vec<u8>,
+) -> bool {
+    let results: Vec<ExecutionResult> = [EnclaveType::IntelSGX, EnclaveType::AMDSEV]
+        .into_iter()
+        .filter_map(|enclave_type| get_executor_result(context, execution_id, enclave_type))
+        .collect();
+
+    match results.iter().map(|result| result.block_height).max() {
+        Some(block_height) if block_height < older_than_block => {
+            digest.extend(&execution_id.to_le_bytes());
+            for result in &results {
+                digest.extend(&result.result_hash);
+            }
+            *digest = hash_message(digest);
+
+            for result in results {
+                context
+                    .remove(ExecutionResult(execution_id, result.enclave_type))
+                    .expect("failed to prune execution result");
+            }
+            context
+                .remove(ExecutionVerified(execution_id))
+                .expect("failed to prune verification flag");
+            context
+                .remove(ExpectedHashLength(execution_id))
+                .expect("failed to prune expected hash length");
+            true
+        },
+        _ => false,
+    }
+}
+
+#[public]
+pub fn get_pruned_executions_digest(context: &mut Context) -> Vec<u8> {
+    context
+        .get(PrunedExecutionsDigest())
+        .expect("state corrupt")
+        .unwrap_or_default()
+}
+
+// Helper functions
+
+/// Cross-TEE assurance requires the agreeing results to come from at least
+/// two distinct enclave types, unless the policy is disabled
+fn quorum_meets_diversity(context: &Context, agreeing: &[EnclaveType]) -> bool {
+    if !load_system_config(context).require_enclave_diversity {
+        return true;
     }
-    None
+
+    agreeing.iter().any(|enclave_type| *enclave_type != agreeing[0])
+}
+
+fn increment_keep_execution_count(context: &mut Context, executor: Address) {
+    let count = context
+        .get(KeepExecutionCount(executor))
+        .expect("state corrupt")
+        .unwrap_or(0);
+
+    context
+        .store_by_key(KeepExecutionCount(executor), count + 1)
+        .expect("failed to update keep execution count");
+}
+
+fn get_executor_result(
+    context: &mut Context,
+    execution_id: u128,
+    enclave_type: EnclaveType,
+) -> Option<ExecutionResult> {
+    context
+        .get(ExecutionResult(execution_id, enclave_type))
+        .expect("state corrupt")
 }
 
 fn create_verification_challenge(
@@ -199,25 +1361,36 @@ fn create_verification_challenge(
 
 fn create_dual_challenge(
     context: &mut Context,
+    execution_id: u128,
     sgx_executor: Address,
     sev_executor: Address,
     challenge_ Vec<u8>,
 ) {
+    let mut challenge_ids = Vec::new();
+
     // Create challenge for SGX executor
-    challenge_executor(
+    if let Ok(challenge) = challenge_executor(
         context,
         sgx_executor,
         ChallengeType::ExecutionVerification,
         challenge_data.clone(),
-    );
+    ) {
+        challenge_ids.push(challenge.id);
+    }
 
     // Create challenge for SEV executor
-    challenge_executor(
+    if let Ok(challenge) = challenge_executor(
         context,
         sev_executor,
         ChallengeType::ExecutionVerification,
         challenge_data,
-    );
+    ) {
+        challenge_ids.push(challenge.id);
+    }
+
+    context
+        .store_by_key(MismatchChallenges(execution_id), challenge_ids)
+        .expect("failed to record mismatch challenges");
 }
 
 fn log_verification_success(
@@ -226,8 +1399,8 @@ fn log_verification_success(
     sgx_result: &ExecutionResult,
     sev_result: &ExecutionResult,
 ) {
-    wasmlanche::dbg!(
-        "Execution verification successful",
+    log_info!(
+        "Execution verification successful: id={} sgx_height={} sev_height={}",
         execution_id,
         sgx_result.block_height,
         sev_result.block_height,
@@ -240,8 +1413,8 @@ fn log_verification_failure(
     sgx_result: &ExecutionResult,
     sev_result: &ExecutionResult,
 ) {
-    wasmlanche::dbg!(
-        "Execution verification failed",
+    log_error!(
+        "Execution verification failed: id={} sgx_hash={:?} sev_hash={:?}",
         execution_id,
         sgx_result.result_hash,
         sev_result.result_hash,
@@ -252,6 +1425,10 @@ fn log_verification_failure(
 mod tests {
     use super::*;
     use crate::tests::common::*;
+    use crate::{
+        core::*, external::*, MISMATCH_SUSPENSION_THRESHOLD, MAX_CLOCK_SKEW, MAX_HEARTBEAT_MISSES,
+    };
+    use wasmlanche::testing::TestContext;
 
     #[test]
     fn test_matching_execution_results() {
@@ -263,11 +1440,11 @@ mod tests {
 
         // Submit SGX result
         context.set_caller(sgx_executor);
-        submit_execution_result(&mut context, execution_id, result_hash.clone());
+        submit_execution_result(&mut context, execution_id, result_hash.clone(), payload_hash(), 1);
 
         // Submit matching SEV result
         context.set_caller(sev_executor);
-        submit_execution_result(&mut context, execution_id, result_hash.clone());
+        submit_execution_result(&mut context, execution_id, result_hash.clone(), payload_hash(), 1);
 
         // Verify results matched
         assert!(verify_execution(&mut context, execution_id));
@@ -286,10 +1463,10 @@ mod tests {
         
         // Submit different results
         context.set_caller(sgx_executor);
-        submit_execution_result(&mut context, execution_id, vec![1u8; 32]);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
 
         context.set_caller(sev_executor);
-        submit_execution_result(&mut context, execution_id, vec![2u8; 32]);
+        submit_execution_result(&mut context, execution_id, vec![2u8; 32], payload_hash(), 1);
 
         // Verify mismatch was detected
         assert!(!verify_execution(&mut context, execution_id));
@@ -300,6 +1477,1294 @@ mod tests {
         assert_ne!(sgx.result_hash, sev.result_hash);
     }
 
+    #[test]
+    fn test_mismatch_flagged_without_auto_challenge() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::AutoChallengeOnMismatch(false));
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![2u8; 32], payload_hash(), 1);
+
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+        assert!(get_verification_mismatch(&mut context, 1u128).is_some());
+        assert!(context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "ExecutionMismatchFlagged"));
+    }
+
+    #[test]
+    fn test_mismatch_ordered_sgx_first() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        // SEV submits first
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![2u8; 32], payload_hash(), 1);
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        let (first, second) = get_verification_mismatch(&mut context, 1u128).unwrap();
+        assert_eq!(first.enclave_type, EnclaveType::IntelSGX);
+        assert_eq!(first.result_hash, vec![1u8; 32]);
+        assert_eq!(second.enclave_type, EnclaveType::AMDSEV);
+    }
+
+    #[test]
+    fn test_mismatch_evidence_bundle() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, None, Some(payload_hash()));
+
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, execution_id, vec![2u8; 32], payload_hash(), 1);
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
+
+        let evidence = get_mismatch_evidence(&mut context, execution_id).unwrap();
+        assert_eq!(evidence.execution_id, execution_id);
+        assert_eq!(evidence.payload_hash, payload_hash());
+
+        assert_eq!(evidence.sgx_result.executor, sgx_executor);
+        assert_eq!(evidence.sgx_result.enclave_type, EnclaveType::IntelSGX);
+        assert_eq!(evidence.sgx_result.result_hash, vec![1u8; 32]);
+        assert_eq!(evidence.sgx_result.block_height, context.block_height());
+
+        assert_eq!(evidence.sev_result.executor, sev_executor);
+        assert_eq!(evidence.sev_result.enclave_type, EnclaveType::AMDSEV);
+        assert_eq!(evidence.sev_result.result_hash, vec![2u8; 32]);
+        assert_eq!(evidence.sev_result.block_height, context.block_height());
+    }
+
+    #[test]
+    fn test_both_enclave_results_retained() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        let sgx = get_execution_result(&mut context, 1u128, EnclaveType::IntelSGX).unwrap();
+        let sev = get_execution_result(&mut context, 1u128, EnclaveType::AMDSEV).unwrap();
+        assert_eq!(sgx.executor, sgx_executor);
+        assert_eq!(sev.executor, sev_executor);
+        assert!(verify_execution(&mut context, 1u128));
+    }
+
+    #[test]
+    fn test_no_mismatch_evidence_for_matching_execution() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(get_mismatch_evidence(&mut context, 1u128).is_none());
+    }
+
+    fn setup_tiebreaker_mismatch(
+        context: &mut TestContext,
+        tiebreaker_type: EnclaveType,
+    ) -> (Address, Address, Vec<Address>) {
+        let (sgx_executor, sev_executor, _) = setup_system(context);
+        setup_with_token_contract(context);
+
+        for executor in [sgx_executor, sev_executor] {
+            context.set_caller(executor);
+            stake_tokens(context, 1_000);
+        }
+
+        let tiebreakers: Vec<Address> = (0..TIEBREAKER_QUORUM as u8)
+            .map(|i| Address::from([9u8 + i; 32]))
+            .collect();
+        for (i, tiebreaker) in tiebreakers.iter().enumerate() {
+            let keep_id = format!("keep-tiebreaker-{}", i);
+            register_ready_tee_keep(
+                context,
+                *tiebreaker,
+                tiebreaker_type.clone(),
+                &keep_id,
+            )
+            .unwrap();
+        }
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(context, 1u128, vec![2u8; 32], payload_hash(), 1);
+
+        (sgx_executor, sev_executor, tiebreakers)
+    }
+
+    /// Submits the same tiebreak from every tiebreaker, returning whether the
+    /// last submission resolved the mismatch
+    fn break_tie(
+        context: &mut TestContext,
+        tiebreakers: &[Address],
+        execution_id: u128,
+        result_hash: Vec<u8>,
+    ) -> bool {
+        let mut resolved = false;
+        for tiebreaker in tiebreakers {
+            context.set_caller(*tiebreaker);
+            resolved = submit_tiebreaker_result(context, execution_id, result_hash.clone());
+        }
+        resolved
+    }
+
+    #[test]
+    fn test_tiebreaker_agrees_with_sgx() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+
+        assert!(break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]));
+
+        assert!(verify_execution(&mut context, 1u128));
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 1_000);
+        assert_eq!(get_staked_amount(&mut context, sev_executor), 0);
+        assert_eq!(get_reward_reserve(&mut context), 1_000);
+    }
+
+    #[test]
+    fn test_single_tiebreaker_cannot_slash() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+
+        context.set_caller(tiebreakers[0]);
+        assert!(!submit_tiebreaker_result(&mut context, 1u128, vec![1u8; 32]));
+
+        assert!(!verify_execution(&mut context, 1u128));
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 1_000);
+        assert_eq!(get_staked_amount(&mut context, sev_executor), 1_000);
+    }
+
+    #[test]
+    fn test_tiebreak_closes_mismatch_challenges() {
+        let mut context = setup();
+        let (sgx_executor, _, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+        let challenge_ids = context.get(MismatchChallenges(1u128)).unwrap().unwrap_or_default();
+
+        assert!(break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]));
+
+        for challenge_id in challenge_ids {
+            let challenge = context.get(Challenge(challenge_id)).unwrap().unwrap();
+            let expected = if challenge.challenged == sgx_executor {
+                ChallengeStatus::Verified
+            } else {
+                ChallengeStatus::Failed
+            };
+            assert_eq!(challenge.status, expected);
+        }
+        assert!(context.get(MismatchChallenges(1u128)).unwrap().is_none());
+        assert_eq!(context.get(CurrentPhase()).unwrap(), Some(Phase::Executing));
+    }
+
+    #[test]
+    fn test_consecutive_lost_mismatches_suspend_executor() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+
+        break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]);
+        for execution_id in 2..=MISMATCH_SUSPENSION_THRESHOLD as u128 {
+            assert_eq!(
+                context.get(ExecutorState(sev_executor)).unwrap(),
+                Some(ExecutorStatus::Active)
+            );
+
+            context.set_caller(sgx_executor);
+            submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), execution_id as u64);
+            context.set_caller(sev_executor);
+            submit_execution_result(&mut context, execution_id, vec![2u8; 32], payload_hash(), execution_id as u64);
+            break_tie(&mut context, &tiebreakers, execution_id, vec![1u8; 32]);
+        }
+
+        assert_eq!(
+            context.get(ConsecutiveMismatches(sev_executor)).unwrap(),
+            Some(MISMATCH_SUSPENSION_THRESHOLD)
+        );
+        assert_eq!(
+            context.get(ExecutorState(sev_executor)).unwrap(),
+            Some(ExecutorStatus::Suspended)
+        );
+        assert_eq!(
+            context.get(ExecutorState(sgx_executor)).unwrap(),
+            Some(ExecutorStatus::Active)
+        );
+
+        let challenge_id = context.get(ChallengeCount()).unwrap().unwrap();
+        let challenge = context.get(Challenge(challenge_id)).unwrap().unwrap();
+        assert_eq!(challenge.challenged, sev_executor);
+    }
+
+    #[test]
+    fn test_clean_match_resets_mismatch_streak() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+
+        break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]);
+        assert_eq!(context.get(ConsecutiveMismatches(sev_executor)).unwrap(), Some(1));
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 2u128, vec![3u8; 32], payload_hash(), 2);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 2u128, vec![3u8; 32], payload_hash(), 2);
+
+        assert_eq!(context.get(ConsecutiveMismatches(sev_executor)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_tiebreaker_agrees_with_sev() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::IntelSGX);
+
+        assert!(break_tie(&mut context, &tiebreakers, 1u128, vec![2u8; 32]));
+
+        assert!(verify_execution(&mut context, 1u128));
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 0);
+        assert_eq!(get_staked_amount(&mut context, sev_executor), 1_000);
+    }
+
+    #[test]
+    fn test_single_enclave_quorum_rejected() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::IntelSGX);
+
+        // Only SGX results agree, which is not a cross-TEE quorum
+        assert!(!break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]));
+
+        assert!(!verify_execution(&mut context, 1u128));
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 1_000);
+        assert_eq!(get_staked_amount(&mut context, sev_executor), 1_000);
+    }
+
+    #[test]
+    fn test_single_enclave_quorum_allowed_when_policy_disabled() {
+        let mut context = setup();
+        let (_, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::IntelSGX);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::RequireEnclaveDiversity(false));
+
+        assert!(break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]));
+        assert_eq!(get_staked_amount(&mut context, sev_executor), 0);
+    }
+
+    #[test]
+    fn test_tiebreaker_matching_neither_side() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::IntelSGX);
+
+        assert!(!break_tie(&mut context, &tiebreakers, 1u128, vec![3u8; 32]));
+
+        assert!(!verify_execution(&mut context, 1u128));
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 1_000);
+        assert_eq!(get_staked_amount(&mut context, sev_executor), 1_000);
+    }
+
+    #[test]
+    fn test_tiebreak_retryable_after_matching_neither_side() {
+        let mut context = setup();
+        let (_, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+
+        assert!(!break_tie(&mut context, &tiebreakers, 1u128, vec![3u8; 32]));
+        assert!(break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]));
+
+        assert!(verify_execution(&mut context, 1u128));
+        assert_eq!(get_staked_amount(&mut context, sev_executor), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized tiebreaker")]
+    fn test_tiebreaker_must_be_ready_tee() {
+        let mut context = setup();
+        setup_tiebreaker_mismatch(&mut context, EnclaveType::IntelSGX);
+
+        context.set_caller(Address::from([20u8; 32]));
+        submit_tiebreaker_result(&mut context, 1u128, vec![1u8; 32]);
+    }
+
+    #[test]
+    fn test_mismatch_emits_error_log() {
+        use crate::logging::{take_captured_logs, LogLevel};
+
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        take_captured_logs();
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![2u8; 32], payload_hash(), 1);
+
+        let logs = take_captured_logs();
+        assert!(logs.iter().any(|(level, message)| {
+            *level == LogLevel::Error && message.contains("Execution verification failed")
+        }));
+    }
+
+    #[test]
+    fn test_allocated_ids_are_sequential() {
+        let mut context = setup();
+        context.set_caller(governance_address());
+
+        assert_eq!(allocate_execution_id(&mut context, None, None), 1);
+        assert_eq!(allocate_execution_id(&mut context, Some(48), None), 2);
+    }
+
+    #[test]
+    fn test_peek_matches_next_allocation() {
+        let mut context = setup();
+        context.set_caller(governance_address());
+        allocate_execution_id(&mut context, None, None);
+
+        let peeked = peek_next_execution_id(&mut context);
+        assert_eq!(peek_next_execution_id(&mut context), peeked);
+        assert_eq!(allocate_execution_id(&mut context, None, None), peeked);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_allocation_requires_governance() {
+        let mut context = setup();
+        context.set_caller(Address::from([20u8; 32]));
+
+        allocate_execution_id(&mut context, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "execution id not allocated")]
+    fn test_submission_to_unallocated_id_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, PREALLOCATED_EXECUTION_IDS + 1, vec![1u8; 32], payload_hash(), 1);
+    }
+
+    #[test]
+    fn test_expected_hash_length_accepted() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, Some(48), None);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 48], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 48], payload_hash(), 1);
+
+        assert!(verify_execution(&mut context, execution_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected result hash length")]
+    fn test_wrong_hash_length_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, Some(48), None);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
+    }
+
+    #[test]
+    fn test_committed_payload_hash_accepted() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, None, Some(payload_hash()));
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(verify_execution(&mut context, execution_id));
+        assert!(!has_input_mismatch(&mut context, execution_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "payload hash does not match allocation")]
+    fn test_uncommitted_payload_hash_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, None, Some(payload_hash()));
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], vec![8u8; 32], 1);
+    }
+
+    #[test]
+    fn test_divergent_payloads_flagged_as_input_mismatch() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        let execution_id = 1u128;
+
+        // Same output, different inputs: must not count as verified
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], vec![8u8; 32], 1);
+
+        assert!(has_input_mismatch(&mut context, execution_id));
+        assert!(!verify_execution(&mut context, execution_id));
+        assert!(get_pending_verifications(&mut context).is_empty());
+        assert!(context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "ExecutionInputMismatch"));
+    }
+
+    fn submit_matching_results_apart(context: &mut TestContext, gap: u64) -> u128 {
+        let (sgx_executor, sev_executor, _) = setup_system(context);
+        let execution_id = 1u128;
+
+        // Both land in the same block; only the attested times differ
+        context.set_timestamp(context.timestamp() + gap);
+        let executed_at = context.timestamp();
+        context.set_caller(sgx_executor);
+        submit_timed_execution_result(context, execution_id, vec![1u8; 32], payload_hash(), 1, executed_at - gap);
+        context.set_caller(sev_executor);
+        submit_timed_execution_result(context, execution_id, vec![1u8; 32], payload_hash(), 1, executed_at);
+        execution_id
+    }
+
+    #[test]
+    fn test_match_within_clock_skew_verified() {
+        let mut context = setup();
+        let execution_id = submit_matching_results_apart(&mut context, MAX_CLOCK_SKEW);
+
+        assert!(verify_execution(&mut context, execution_id));
+        assert!(!has_clock_skew(&mut context, execution_id));
+    }
+
+    #[test]
+    fn test_match_beyond_clock_skew_flagged() {
+        let mut context = setup();
+        let execution_id = submit_matching_results_apart(&mut context, MAX_CLOCK_SKEW + 1);
+
+        assert!(has_clock_skew(&mut context, execution_id));
+        assert!(!verify_execution(&mut context, execution_id));
+        assert!(get_pending_verifications(&mut context).contains(&execution_id));
+        assert!(context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "ExecutionClockSkewExceeded"));
+    }
+
+    #[test]
+    fn test_skewed_match_resubmitted() {
+        let mut context = setup();
+        let execution_id = submit_matching_results_apart(&mut context, MAX_CLOCK_SKEW + 1);
+        let executed_at = context.timestamp();
+
+        let pool = context.get(ExecutorPool()).unwrap().unwrap();
+        for executor in [pool.sgx_executor.unwrap(), pool.sev_executor.unwrap()] {
+            context.set_caller(executor);
+            submit_timed_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 2, executed_at);
+        }
+
+        assert!(verify_execution(&mut context, execution_id));
+        assert!(!has_clock_skew(&mut context, execution_id));
+    }
+
+    #[test]
+    fn test_relay_delay_is_not_clock_skew() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        let executed_at = context.timestamp();
+
+        context.set_caller(sgx_executor);
+        submit_timed_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1, executed_at);
+        context.set_timestamp(executed_at + MAX_CLOCK_SKEW + 100);
+        context.set_caller(sev_executor);
+        submit_timed_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1, executed_at);
+
+        assert!(verify_execution(&mut context, 1u128));
+        assert!(!has_clock_skew(&mut context, 1u128));
+    }
+
+    fn tentative_match(context: &mut TestContext) -> (u128, Vec<Address>) {
+        let (sgx_executor, sev_executor, watchdogs) = setup_full_system(context);
+
+        context.set_caller(governance_address());
+        update_system_config(context, ConfigUpdate::ExecutionDisputeWindow(50));
+
+        let execution_id = 1u128;
+        context.set_caller(sgx_executor);
+        submit_execution_result(context, execution_id, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(context, execution_id, vec![1u8; 32], payload_hash(), 1);
+
+        (execution_id, watchdogs)
+    }
+
+    fn dispute_evidence() -> ChallengeEvidence {
+        ChallengeEvidence::ExecutionEvidence {
+            result_hash: vec![1u8; 32],
+            execution_proof: vec![2u8; 64],
+            keep_measurement: vec![3u8; 32],
+        }
+    }
+
+    #[test]
+    fn test_undisputed_match_finalized_after_window() {
+        let mut context = setup();
+        let (execution_id, _) = tentative_match(&mut context);
+
+        // Not verified until the dispute window has elapsed
+        assert!(!verify_execution(&mut context, execution_id));
+        assert!(get_pending_verifications(&mut context).is_empty());
+
+        context.set_timestamp(context.timestamp() + 51);
+        finalize_execution(&mut context, execution_id);
+
+        assert!(verify_execution(&mut context, execution_id));
+    }
+
+    #[test]
+    fn test_unmatched_execution_not_disputable() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_full_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::ExecutionDisputeWindow(50));
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 2u128, vec![1u8; 32], payload_hash(), 2);
+        assert!(!is_execution_disputable(&mut context, 2u128));
+    }
+
+    #[test]
+    fn test_disputable_until_finalized() {
+        let mut context = setup();
+        let (execution_id, _) = tentative_match(&mut context);
+
+        assert!(is_execution_disputable(&mut context, execution_id));
+
+        context.set_timestamp(context.timestamp() + 51);
+        assert!(!is_execution_disputable(&mut context, execution_id));
+
+        finalize_execution(&mut context, execution_id);
+        assert!(!is_execution_disputable(&mut context, execution_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "dispute window still open")]
+    fn test_finalization_waits_for_window() {
+        let mut context = setup();
+        let (execution_id, _) = tentative_match(&mut context);
+
+        finalize_execution(&mut context, execution_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "execution disputed")]
+    fn test_timely_dispute_blocks_finalization() {
+        let mut context = setup();
+        let (execution_id, watchdogs) = tentative_match(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenges = dispute_execution(&mut context, execution_id, dispute_evidence());
+        assert!(challenges.iter().all(|c| c.challenge_type == ChallengeType::Execution));
+
+        context.set_timestamp(context.timestamp() + 51);
+        finalize_execution(&mut context, execution_id);
+    }
+
+    fn settle_dispute(context: &mut TestContext, watchdogs: &[Address], challenge: &Challenge, upheld: bool) {
+        context.set_caller(challenge.challenged);
+        respond_to_challenge(
+            context,
+            challenge.id,
+            vec![1u8; 16],
+            ChallengeProof {
+                challenge_id: challenge.id,
+                proof_data: vec![1u8; 32],
+                timestamp: 0,
+                witness_signatures: watchdogs.iter().map(|w| (*w, vec![0u8; 64])).collect(),
+                drawbridge_token: Vec::new(),
+            },
+        );
+
+        let required = get_required_verifications(context);
+        for watchdog in &watchdogs[..required] {
+            context.set_caller(*watchdog);
+            verify_challenge_response(context, challenge.id, !upheld, vec![1u8; 32]);
+        }
+    }
+
+    #[test]
+    fn test_refuted_dispute_allows_finalization() {
+        let mut context = setup();
+        let (execution_id, watchdogs) = tentative_match(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        for challenge in dispute_execution(&mut context, execution_id, dispute_evidence()) {
+            settle_dispute(&mut context, &watchdogs, &challenge, false);
+        }
+
+        context.set_timestamp(context.timestamp() + 51);
+        finalize_execution(&mut context, execution_id);
+        assert!(verify_execution(&mut context, execution_id));
+    }
+
+    #[test]
+    fn test_upheld_dispute_discards_results() {
+        let mut context = setup();
+        let (execution_id, watchdogs) = tentative_match(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        for challenge in dispute_execution(&mut context, execution_id, dispute_evidence()) {
+            settle_dispute(&mut context, &watchdogs, &challenge, true);
+        }
+
+        assert!(!verify_execution(&mut context, execution_id));
+        assert!(!is_execution_disputable(&mut context, execution_id));
+        assert!(get_execution_result(&mut context, execution_id, EnclaveType::IntelSGX).is_none());
+        assert!(get_execution_result(&mut context, execution_id, EnclaveType::AMDSEV).is_none());
+    }
+
+    #[test]
+    fn test_dispute_challenges_both_executors() {
+        let mut context = setup();
+        let (execution_id, watchdogs) = tentative_match(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenges = dispute_execution(&mut context, execution_id, dispute_evidence());
+
+        let challenged: Vec<Address> = challenges.iter().map(|c| c.challenged).collect();
+        assert_eq!(challenged, vec![Address::from([3u8; 32]), Address::from([4u8; 32])]);
+        assert_eq!(
+            context.get(ExecutionDispute(execution_id)).unwrap(),
+            Some(challenges.iter().map(|c| c.id).collect())
+        );
+    }
+
+    #[test]
+    fn test_dispute_settles_after_both_verdicts() {
+        let mut context = setup();
+        let (execution_id, watchdogs) = tentative_match(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenges = dispute_execution(&mut context, execution_id, dispute_evidence());
+
+        // Clearing the SGX executor alone leaves the dispute open
+        settle_dispute(&mut context, &watchdogs, &challenges[0], false);
+        assert!(context.get(ExecutionDispute(execution_id)).unwrap().is_some());
+        assert!(get_execution_result(&mut context, execution_id, EnclaveType::IntelSGX).is_some());
+
+        // The SEV executor losing upholds the dispute
+        settle_dispute(&mut context, &watchdogs, &challenges[1], true);
+        assert!(context.get(ExecutionDispute(execution_id)).unwrap().is_none());
+        assert!(!verify_execution(&mut context, execution_id));
+        assert!(get_execution_result(&mut context, execution_id, EnclaveType::IntelSGX).is_none());
+        assert!(get_execution_result(&mut context, execution_id, EnclaveType::AMDSEV).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "dispute window closed")]
+    fn test_late_dispute_rejected() {
+        let mut context = setup();
+        let (execution_id, watchdogs) = tentative_match(&mut context);
+
+        context.set_timestamp(context.timestamp() + 51);
+        context.set_caller(watchdogs[0]);
+        dispute_execution(&mut context, execution_id, dispute_evidence());
+    }
+
+    fn submit_mismatch(
+        context: &mut TestContext,
+        sgx_executor: Address,
+        sev_executor: Address,
+        execution_id: u128,
+    ) {
+        context.set_caller(sgx_executor);
+        submit_execution_result(context, execution_id, vec![1u8; 32], payload_hash(), execution_id as u64);
+        context.set_caller(sev_executor);
+        submit_execution_result(context, execution_id, vec![2u8; 32], payload_hash(), execution_id as u64);
+    }
+
+    #[test]
+    fn test_mismatch_penalties_escalate() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::MismatchEscalation { challenge_after: 2, remove_after: 3 },
+        );
+
+        // First strike: warning and re-execution, no challenge
+        submit_mismatch(&mut context, sgx_executor, sev_executor, 1);
+        assert_eq!(get_mismatch_strikes(&mut context, sgx_executor), 1);
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+        assert!(get_execution_result(&mut context, 1u128, EnclaveType::IntelSGX).is_none());
+        assert!(get_execution_result(&mut context, 1u128, EnclaveType::AMDSEV).is_none());
+        assert!(get_mismatch_evidence(&mut context, 1u128).is_none());
+        assert!(context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "ExecutionMismatchWarning"));
+
+        // Second strike: the pair is challenged
+        submit_mismatch(&mut context, sgx_executor, sev_executor, 2);
+        assert_eq!(get_current_phase(&mut context), Phase::ChallengeExecutor);
+        assert_eq!(
+            context.get(ExecutorState(sgx_executor)).unwrap(),
+            Some(ExecutorStatus::Active)
+        );
+
+        // Third strike: the pair is challenged again and whichever side the
+        // tiebreak finds at fault will be removed, but neither is removed yet
+        submit_mismatch(&mut context, sgx_executor, sev_executor, 3);
+        assert_eq!(context.get(MismatchRemovalPending(3u128)).unwrap(), Some(3));
+        assert_eq!(get_current_phase(&mut context), Phase::ChallengeExecutor);
+        for executor in [sgx_executor, sev_executor] {
+            assert_eq!(
+                context.get(ExecutorState(executor)).unwrap(),
+                Some(ExecutorStatus::Active)
+            );
+        }
+        assert!(!context.events().iter().any(|(name, _)| name == "ExecutorRemoved"));
+    }
+
+    #[test]
+    fn test_tiebreak_removes_only_faulty_executor() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+        context.store_by_key(MismatchRemovalPending(1u128), 5).unwrap();
+
+        assert!(break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]));
+
+        let pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(pool.sgx_executor, Some(sgx_executor));
+        assert_ne!(pool.sev_executor, Some(sev_executor));
+        assert_eq!(
+            context.get(ExecutorState(sgx_executor)).unwrap(),
+            Some(ExecutorStatus::Active)
+        );
+        assert_eq!(context.get(CurrentPhase()).unwrap(), Some(Phase::Executing));
+        assert!(context.get(MismatchRemovalPending(1u128)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_removal_without_replacement_crashes() {
+        let mut context = setup();
+        let (_, sev_executor, tiebreakers) =
+            setup_tiebreaker_mismatch(&mut context, EnclaveType::AMDSEV);
+        context.store_by_key(MismatchRemovalPending(1u128), 5).unwrap();
+        for tiebreaker in &tiebreakers {
+            context.store_by_key(HeartbeatMisses(*tiebreaker), MAX_HEARTBEAT_MISSES).unwrap();
+        }
+
+        assert!(break_tie(&mut context, &tiebreakers, 1u128, vec![1u8; 32]));
+
+        assert_eq!(
+            context.get(ExecutorState(sev_executor)).unwrap(),
+            Some(ExecutorStatus::Failed)
+        );
+        assert_eq!(context.get(CurrentPhase()).unwrap(), Some(Phase::Crashed));
+    }
+
+    #[test]
+    fn test_mismatch_burst_pauses_system() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::MismatchPause { threshold: 3, window: 600 },
+        );
+
+        for execution_id in 1..3u128 {
+            submit_mismatch(&mut context, sgx_executor, sev_executor, execution_id);
+        }
+        assert!(!is_system_paused(&mut context));
+
+        submit_mismatch(&mut context, sgx_executor, sev_executor, 3);
+        assert!(is_system_paused(&mut context));
+        assert!(context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "SystemPausedAlert"));
+
+        context.set_caller(governance_address());
+        unpause_system(&mut context);
+        assert!(!is_system_paused(&mut context));
+    }
+
+    #[test]
+    fn test_mismatches_outside_window_not_counted() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::MismatchPause { threshold: 2, window: 100 },
+        );
+
+        submit_mismatch(&mut context, sgx_executor, sev_executor, 1);
+        context.set_timestamp(context.timestamp() + 100);
+        submit_mismatch(&mut context, sgx_executor, sev_executor, 2);
+
+        assert!(!is_system_paused(&mut context));
+    }
+
+    #[test]
+    #[should_panic(expected = "system paused")]
+    fn test_paused_system_rejects_results() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::MismatchPause { threshold: 1, window: 600 },
+        );
+        submit_mismatch(&mut context, sgx_executor, sev_executor, 1);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 2u128, vec![1u8; 32], payload_hash(), 2);
+    }
+
+    #[test]
+    fn test_keep_execution_count() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        for execution_id in 1..=3u128 {
+            context.set_caller(sgx_executor);
+            submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), execution_id as u64);
+
+            context.set_caller(sev_executor);
+            submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), execution_id as u64);
+        }
+
+        assert_eq!(get_keep_execution_count(&mut context, sgx_executor), 3);
+        assert_eq!(get_keep_execution_count(&mut context, sev_executor), 3);
+    }
+
+    #[test]
+    fn test_batch_verification_statuses() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        // Execution 1 verifies, execution 2 mismatches, execution 3 stays pending
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        submit_execution_result(&mut context, 2u128, vec![1u8; 32], payload_hash(), 2);
+        submit_execution_result(&mut context, 3u128, vec![1u8; 32], payload_hash(), 3);
+
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        submit_execution_result(&mut context, 2u128, vec![2u8; 32], payload_hash(), 2);
+
+        let statuses = get_verification_statuses(&mut context, vec![1u128, 2u128, 3u128]);
+        assert_eq!(
+            statuses,
+            vec![(1u128, Some(true)), (2u128, Some(false)), (3u128, None)]
+        );
+    }
+
+    #[test]
+    fn test_prune_old_verified_executions() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        // Old verified execution and old pending execution
+        context.set_block_height(10);
+        for executor in [sgx_executor, sev_executor] {
+            context.set_caller(executor);
+            submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        }
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 2u128, vec![1u8; 32], payload_hash(), 2);
+
+        // Recent verified execution
+        context.set_block_height(100);
+        for executor in [sgx_executor, sev_executor] {
+            context.set_caller(executor);
+            submit_execution_result(&mut context, 3u128, vec![3u8; 32], payload_hash(), 3);
+        }
+
+        context.set_caller(governance_address());
+        assert_eq!(prune_executions(&mut context, 50), 1);
+
+        assert!(get_execution_result(&mut context, 1u128, EnclaveType::IntelSGX).is_none());
+        assert!(get_execution_result(&mut context, 1u128, EnclaveType::AMDSEV).is_none());
+        assert!(!verify_execution(&mut context, 1u128));
+        assert!(!get_pruned_executions_digest(&mut context).is_empty());
+
+        // Pending and recent records are untouched
+        assert!(get_execution_result(&mut context, 2u128, EnclaveType::IntelSGX).is_some());
+        assert!(get_pending_verifications(&mut context).contains(&2u128));
+        assert!(get_execution_result(&mut context, 3u128, EnclaveType::IntelSGX).is_some());
+        assert!(get_execution_result(&mut context, 3u128, EnclaveType::AMDSEV).is_some());
+        assert!(verify_execution(&mut context, 3u128));
+    }
+
+    #[test]
+    fn test_relayed_result_keeps_supplied_height() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_block_height(10);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, None, None);
+
+        context.set_block_height(20);
+        context.set_caller(sgx_executor);
+        submit_execution_result_at(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1, 15);
+
+        assert_eq!(
+            get_execution_result(&mut context, execution_id, EnclaveType::IntelSGX).unwrap().block_height,
+            15
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "block height before allocation")]
+    fn test_backdated_block_height_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_block_height(10);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, None, None);
+
+        context.set_block_height(20);
+        context.set_caller(sgx_executor);
+        submit_execution_result_at(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1, 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "block height in the future")]
+    fn test_future_block_height_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_block_height(20);
+        context.set_caller(sgx_executor);
+        submit_execution_result_at(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1, 21);
+    }
+
+    #[test]
+    fn test_batched_prune_resumes_from_cursor() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_block_height(10);
+        for execution_id in 1..=100u128 {
+            for executor in [sgx_executor, sev_executor] {
+                context.set_caller(executor);
+                submit_execution_result(
+                    &mut context,
+                    execution_id,
+                    vec![1u8; 32],
+                    payload_hash(),
+                    execution_id as u64,
+                );
+            }
+        }
+        context.set_block_height(100);
+
+        context.set_caller(governance_address());
+        for call in 1..=4 {
+            let complete = prune_executions_batch(&mut context, 50, 25);
+            assert_eq!(complete, call == 4);
+            assert_eq!(
+                context.get(VerifiedExecutions()).unwrap().unwrap().len(),
+                100 - call * 25
+            );
+        }
+
+        assert!(get_execution_result(&mut context, 100u128, EnclaveType::IntelSGX).is_none());
+        assert!(context.get(PruneCursor()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_verification_throughput_window() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        for (execution_id, block_height) in [(1u128, 10), (2u128, 20), (3u128, 25), (4u128, 30)] {
+            context.set_block_height(block_height);
+            for executor in [sgx_executor, sev_executor] {
+                context.set_caller(executor);
+                submit_execution_result(
+                    &mut context,
+                    execution_id,
+                    vec![1u8; 32],
+                    payload_hash(),
+                    execution_id as u64,
+                );
+            }
+        }
+
+        // Blocks 21..=30 cover the last two verifications
+        assert_eq!(get_verification_throughput(&mut context, 10), 2);
+        assert_eq!(get_verification_throughput(&mut context, 11), 3);
+        assert_eq!(get_verification_throughput(&mut context, 100), 4);
+        assert_eq!(get_verification_throughput(&mut context, 0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_prune_requires_governance() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        prune_executions(&mut context, 50);
+    }
+
+    #[test]
+    fn test_pending_verification_ages() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        context.set_timestamp(context.timestamp() + 30);
+        submit_execution_result(&mut context, 2u128, vec![1u8; 32], payload_hash(), 2);
+        context.set_timestamp(context.timestamp() + 10);
+
+        assert_eq!(get_pending_verification_ages(&mut context), vec![(1u128, 40), (2u128, 10)]);
+    }
+
+    #[test]
+    fn test_try_submit_accepts_active_executor() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        assert!(try_submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1).is_ok());
+        assert_eq!(get_pending_verifications(&mut context), vec![1u128]);
+    }
+
+    #[test]
+    fn test_try_submit_rejects_non_executor() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        context.set_caller(Address::from([42u8; 32]));
+        let result = try_submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(result, Err(Error::ExecutorError(msg)) if msg == "unauthorized executor"));
+        assert!(get_pending_verifications(&mut context).is_empty());
+    }
+
+    #[test]
+    fn test_try_submit_rejects_inactive_executor() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context
+            .store_by_key(ExecutorState(sgx_executor), ExecutorStatus::Suspended)
+            .unwrap();
+
+        context.set_caller(sgx_executor);
+        let result = try_submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(result, Err(Error::ExecutorNotActive)));
+    }
+
+    #[test]
+    fn test_try_submit_rejects_unattested_executor() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context.store_by_key(AttestationStatus(sgx_executor), false).unwrap();
+
+        context.set_caller(sgx_executor);
+        let result = try_submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(result, Err(Error::InvalidAttestation)));
+    }
+
+    #[test]
+    fn test_try_submit_rejects_paused_system() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context.store_by_key(SystemPaused(), true).unwrap();
+
+        context.set_caller(sgx_executor);
+        let result = try_submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(result, Err(Error::SystemPaused)));
+        assert_eq!(get_last_nonce(&mut context, sgx_executor), 0);
+    }
+
+    #[test]
+    fn test_try_submit_rejects_wrong_phase() {
+        let mut context = setup();
+        let sgx_executor = Address::from([3u8; 32]);
+        context.set_caller(sgx_executor);
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            SGX_OPERATOR.to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+        preallocate_execution_ids(&mut context);
+
+        context.set_caller(sgx_executor);
+        let result = try_submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(result, Err(Error::InvalidPhase(Phase::Creation))));
+        assert!(get_pending_verifications(&mut context).is_empty());
+    }
+
+    #[test]
+    fn test_try_submit_rejects_unallocated_id() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        let result = try_submit_execution_result(&mut context, u128::MAX, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(result, Err(Error::ExecutionNotFound)));
+        assert_eq!(get_last_nonce(&mut context, sgx_executor), 0);
+    }
+
+    #[test]
+    fn test_try_submit_rejects_stale_nonce() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        try_submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 3).unwrap();
+        let result = try_submit_execution_result(&mut context, 2u128, vec![1u8; 32], payload_hash(), 3);
+
+        assert!(matches!(result, Err(Error::StaleNonce)));
+        assert_eq!(get_pending_verifications(&mut context), vec![1u128]);
+    }
+
+    #[test]
+    fn test_try_submit_rejects_wrong_hash_length() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, Some(48), None);
+
+        context.set_caller(sgx_executor);
+        let result = try_submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(
+            result,
+            Err(Error::UnexpectedHashLength { expected: 48, actual: 32 })
+        ));
+        // The rejected submission leaves its nonce usable
+        assert_eq!(get_last_nonce(&mut context, sgx_executor), 0);
+    }
+
+    #[test]
+    fn test_try_submit_rejects_payload_mismatch() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context.set_caller(governance_address());
+        let execution_id = allocate_execution_id(&mut context, None, Some(vec![9u8; 32]));
+
+        context.set_caller(sgx_executor);
+        let result = try_submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
+
+        assert!(matches!(result, Err(Error::PayloadMismatch)));
+        assert_eq!(get_last_nonce(&mut context, sgx_executor), 0);
+    }
+
+    #[test]
+    fn test_identical_resubmission_is_noop() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        let pending_before = get_pending_verifications(&mut context);
+        let result_before = get_execution_result(&mut context, 1u128, EnclaveType::IntelSGX);
+
+        context.set_timestamp(context.timestamp() + 5);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 2);
+
+        assert_eq!(get_pending_verifications(&mut context), pending_before);
+        assert_eq!(get_execution_result(&mut context, 1u128, EnclaveType::IntelSGX), result_before);
+        assert!(!verify_execution(&mut context, 1u128));
+    }
+
+    #[test]
+    fn test_submission_after_verification_rejected() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        assert!(verify_execution(&mut context, 1u128));
+
+        let result_before = get_execution_result(&mut context, 1u128, EnclaveType::IntelSGX);
+
+        context.set_caller(sgx_executor);
+        let resubmitted = try_submit_execution_result(&mut context, 1u128, vec![2u8; 32], payload_hash(), 2);
+
+        assert!(matches!(resubmitted, Err(Error::ExecutionSettled)));
+        assert!(verify_execution(&mut context, 1u128));
+        assert_eq!(get_execution_result(&mut context, 1u128, EnclaveType::IntelSGX), result_before);
+        assert!(get_pending_verifications(&mut context).is_empty());
+        assert_eq!(get_mismatch_strikes(&mut context, sgx_executor), 0);
+        assert_eq!(get_last_nonce(&mut context, sgx_executor), 1);
+    }
+
+    #[test]
+    fn test_incrementing_nonces_accepted() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        submit_execution_result(&mut context, 2u128, vec![1u8; 32], payload_hash(), 2);
+        submit_execution_result(&mut context, 3u128, vec![1u8; 32], payload_hash(), 5);
+
+        assert_eq!(get_last_nonce(&mut context, sgx_executor), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "stale nonce")]
+    fn test_replayed_nonce_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+    }
+
     #[test]
     #[should_panic(expected = "unauthorized executor")]
     fn test_unauthorized_result_submission() {
@@ -307,7 +2772,7 @@ mod tests {
         let unauthorized = Address::from([99u8; 32]);
 
         context.set_caller(unauthorized);
-        submit_execution_result(&mut context, 1u128, vec![0u8; 32]);
+        submit_execution_result(&mut context, 1u128, vec![0u8; 32], payload_hash(), 1);
     }
 
     #[test]
@@ -319,7 +2784,7 @@ mod tests {
 
         // Submit only SGX result
         context.set_caller(sgx_executor);
-        submit_execution_result(&mut context, execution_id, vec![1u8; 32]);
+        submit_execution_result(&mut context, execution_id, vec![1u8; 32], payload_hash(), 1);
 
         // Verify still pending
         let pending = get_pending_verifications(&mut context);
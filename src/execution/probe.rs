@@ -0,0 +1,197 @@
+use wasmlanche::{public, Context, Address};
+use crate::{
+    types::*,
+    state::*,
+};
+
+/// Opens a determinism probe: both executors run the payload identified by
+/// `probe_payload_hash` and must report `expected_hash`. Only governance or a
+/// watchdog may open one, since a divergent result flags the executor.
+#[public]
+pub fn run_determinism_probe(
+    context: &mut Context,
+    probe_payload_hash: Vec<u8>,
+    expected_hash: Vec<u8>,
+) -> u128 {
+    ensure_initialized(context);
+    ensure_probe_requester(context);
+    assert!(!expected_hash.is_empty(), "expected hash required");
+
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    assert!(
+        executor_pool.sgx_executor.is_some() && executor_pool.sev_executor.is_some(),
+        "executor pair not registered"
+    );
+
+    let probe_id = context
+        .get(ProbeCount())
+        .expect("state corrupt")
+        .unwrap_or(0)
+        + 1;
+
+    let probe = DeterminismProbe {
+        payload_hash: probe_payload_hash,
+        expected_hash,
+        sgx_matched: None,
+        sev_matched: None,
+        created_at: context.timestamp(),
+    };
+
+    context
+        .store((
+            (ProbeCount(), probe_id),
+            (DeterminismProbe(probe_id), probe),
+        ))
+        .expect("failed to store probe");
+
+    probe_id
+}
+
+/// Records an executor's probe result, flagging the executor if it diverges
+#[public]
+pub fn submit_probe_result(
+    context: &mut Context,
+    probe_id: u128,
+    result_hash: Vec<u8>,
+) {
+    ensure_initialized(context);
+    let caller = context.actor();
+
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    let mut probe = context
+        .get(DeterminismProbe(probe_id))
+        .expect("state corrupt")
+        .expect("probe not found");
+
+    let matched = result_hash == probe.expected_hash;
+
+    if Some(caller) == executor_pool.sgx_executor {
+        assert!(probe.sgx_matched.is_none(), "probe result already submitted");
+        probe.sgx_matched = Some(matched);
+    } else if Some(caller) == executor_pool.sev_executor {
+        assert!(probe.sev_matched.is_none(), "probe result already submitted");
+        probe.sev_matched = Some(matched);
+    } else {
+        panic!("unauthorized executor");
+    }
+
+    if !matched {
+        flag_divergent_executor(context, probe_id, caller);
+    }
+
+    context
+        .store_by_key(DeterminismProbe(probe_id), probe)
+        .expect("failed to update probe");
+}
+
+/// `Some(true)` once both executors matched, `Some(false)` if either diverged,
+/// `None` while results are outstanding
+#[public]
+pub fn get_probe_status(context: &mut Context, probe_id: u128) -> Option<bool> {
+    let probe = context
+        .get(DeterminismProbe(probe_id))
+        .expect("state corrupt")?;
+
+    match (probe.sgx_matched, probe.sev_matched) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    }
+}
+
+#[public]
+pub fn is_determinism_flagged(context: &mut Context, executor: Address) -> bool {
+    context
+        .get(DeterminismFlagged(executor))
+        .expect("state corrupt")
+        .unwrap_or(false)
+}
+
+fn ensure_probe_requester(context: &mut Context) {
+    let caller = context.actor();
+
+    let is_governance = context
+        .get(GovernanceContract())
+        .expect("state corrupt")
+        == Some(caller);
+    let is_watchdog = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized")
+        .watchdogs
+        .iter()
+        .any(|(addr, _)| *addr == caller);
+
+    assert!(is_governance || is_watchdog, "unauthorized probe request");
+}
+
+fn flag_divergent_executor(context: &mut Context, probe_id: u128, executor: Address) {
+    context
+        .store_by_key(DeterminismFlagged(executor), true)
+        .expect("failed to flag executor");
+
+    log_warn!("Determinism probe {} diverged for executor {:?}", probe_id, executor);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::common::*;
+
+    #[test]
+    fn test_probe_passes_when_both_match() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdog) = setup_system(&mut context);
+
+        context.set_caller(watchdog);
+        let probe_id = run_determinism_probe(&mut context, vec![7u8; 32], vec![9u8; 32]);
+
+        context.set_caller(sgx_executor);
+        submit_probe_result(&mut context, probe_id, vec![9u8; 32]);
+        assert_eq!(get_probe_status(&mut context, probe_id), None);
+
+        context.set_caller(sev_executor);
+        submit_probe_result(&mut context, probe_id, vec![9u8; 32]);
+
+        assert_eq!(get_probe_status(&mut context, probe_id), Some(true));
+        assert!(!is_determinism_flagged(&mut context, sgx_executor));
+        assert!(!is_determinism_flagged(&mut context, sev_executor));
+    }
+
+    #[test]
+    fn test_probe_flags_divergent_executor() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        let probe_id = run_determinism_probe(&mut context, vec![7u8; 32], vec![9u8; 32]);
+
+        context.set_caller(sgx_executor);
+        submit_probe_result(&mut context, probe_id, vec![9u8; 32]);
+
+        context.set_caller(sev_executor);
+        submit_probe_result(&mut context, probe_id, vec![8u8; 32]);
+
+        assert_eq!(get_probe_status(&mut context, probe_id), Some(false));
+        assert!(!is_determinism_flagged(&mut context, sgx_executor));
+        assert!(is_determinism_flagged(&mut context, sev_executor));
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized probe request")]
+    fn test_probe_requires_governance_or_watchdog() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        run_determinism_probe(&mut context, vec![7u8; 32], vec![9u8; 32]);
+    }
+}
@@ -39,7 +39,7 @@ impl ExecutorWorker {
                         },
                         _ => {
                             // Log other errors
-                            error!("Execution failed: {:?}", e);
+                            log_error!("Execution failed: {:?}", e);
                         }
                     }
                 }
@@ -1,7 +1,7 @@
 mod pool;
 mod metrics;
 
-pub use pool::ExecutorPool;
+pub use pool::{ExecutorPool, SelectionStrategy};
 use crate::enarx::{Keep, EnarxConfig, DrawbridgeToken};
 use crate::types::{EnclaveType, ExecutionResult};
 use crate::error::{Error, Result};
@@ -11,9 +11,22 @@ pub struct Executor {
     keep: Keep,
     enclave_type: EnclaveType,
     drawbridge_token: DrawbridgeToken,
+    max_payload_size: usize,
     active: bool,
 }
 
+/// Rejects payloads larger than the configured limit before they reach a keep
+pub fn ensure_payload_size(payload: &[u8], max_payload_size: usize) -> Result<()> {
+    if payload.len() > max_payload_size {
+        return Err(Error::ExecutionFailed(format!(
+            "payload of {} bytes exceeds limit of {} bytes",
+            payload.len(),
+            max_payload_size
+        )));
+    }
+    Ok(())
+}
+
 impl Executor {
     pub async fn new(config: &EnarxConfig, enclave_type: EnclaveType) -> Result<Self> {
         // Initialize Enarx Keep
@@ -30,6 +43,7 @@ impl Executor {
             keep,
             enclave_type,
             drawbridge_token,
+            max_payload_size: config.max_payload_size,
             active: true,
         })
     }
@@ -40,6 +54,8 @@ impl Executor {
         execution_id: u128,
         payload: Vec<u8>,
     ) -> Result<ExecutionResult> {
+        ensure_payload_size(&payload, self.max_payload_size)?;
+
         // Check Keep status
         self.verify_keep_status(context).await?;
         
@@ -93,3 +109,19 @@ impl Executor {
         &self.keep
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_within_limit_accepted() {
+        assert!(ensure_payload_size(&[0u8; 1024], 1024).is_ok());
+    }
+
+    #[test]
+    fn test_oversized_payload_rejected() {
+        let result = ensure_payload_size(&[0u8; 1025], 1024);
+        assert!(matches!(result, Err(Error::ExecutionFailed(_))));
+    }
+}
@@ -1,16 +1,30 @@
 use crate::enarx::{EnarxManager, Keep, EnarxConfig, DrawbridgeToken};
-use crate::types::{EnclaveType, ExecutionResult};
+use crate::types::{ChallengeResolved, ChallengeStatus, EnclaveType, ExecutionResult};
 use crate::error::{Error, Result};
+use crate::metrics::PoolMetrics;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
 
+/// How the pool picks which keep of an enclave type runs an execution when
+/// several (e.g. warm standbys) are registered
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectionStrategy {
+    /// Rotate through the active keeps in registration order
+    RoundRobin,
+    /// Pick the active keep with the fewest executions in flight
+    LeastLoaded,
+}
+
 pub struct ExecutorPool {
     enarx_manager: EnarxManager,
-    sgx_executor: Option<ExecutorInstance>,
-    sev_executor: Option<ExecutorInstance>,
+    sgx_executors: Vec<ExecutorInstance>,
+    sev_executors: Vec<ExecutorInstance>,
+    strategy: SelectionStrategy,
     config: EnarxConfig,
     state: Arc<RwLock<PoolState>>,
+    metrics: Arc<PoolMetrics>,
 }
 
 struct ExecutorInstance {
@@ -20,17 +34,66 @@ struct ExecutorInstance {
     status: ExecutorStatus,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 enum ExecutorStatus {
     Active,
     Failed,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct PoolState {
     execution_count: u64,
     last_sync_height: u64,
     verification_results: HashMap<u128, VerificationPair>,
+    /// Round-robin positions for the SGX and SEV keeps
+    sgx_cursor: usize,
+    sev_cursor: usize,
+    /// Executions currently running on each keep, by keep id
+    in_flight: HashMap<String, usize>,
+}
+
+impl PoolState {
+    /// Chooses one of `candidates` (keep ids of the active keeps of
+    /// `enclave_type`) and records the assignment. Returns its position.
+    fn select_keep(
+        &mut self,
+        strategy: SelectionStrategy,
+        enclave_type: EnclaveType,
+        candidates: &[String],
+    ) -> Option<usize> {
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let index = match strategy {
+            SelectionStrategy::RoundRobin => {
+                let cursor = match enclave_type {
+                    EnclaveType::IntelSGX => &mut self.sgx_cursor,
+                    EnclaveType::AMDSEV => &mut self.sev_cursor,
+                };
+                let index = *cursor % candidates.len();
+                *cursor = cursor.wrapping_add(1);
+                index
+            }
+            SelectionStrategy::LeastLoaded => candidates
+                .iter()
+                .enumerate()
+                .min_by_key(|(index, keep_id)| {
+                    (self.in_flight.get(*keep_id).copied().unwrap_or(0), *index)
+                })
+                .map(|(index, _)| index)?,
+        };
+
+        *self.in_flight.entry(candidates[index].clone()).or_insert(0) += 1;
+        Some(index)
+    }
+
+    /// Marks an execution on `keep_id` as finished
+    fn release_keep(&mut self, keep_id: &str) {
+        if let Some(count) = self.in_flight.get_mut(keep_id) {
+            *count = count.saturating_sub(1);
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -41,17 +104,21 @@ struct VerificationPair {
 }
 
 impl ExecutorPool {
-    pub async fn new(config: EnarxConfig) -> Result<Self> {
+    /// `metrics` is shared by every pool in the process: `PoolMetrics::new`
+    /// registers its collectors globally, so it is built once by the caller.
+    pub async fn new(
+        config: EnarxConfig,
+        strategy: SelectionStrategy,
+        metrics: Arc<PoolMetrics>,
+    ) -> Result<Self> {
         Ok(Self {
             enarx_manager: EnarxManager::new(config.clone()).await?,
-            sgx_executor: None,
-            sev_executor: None,
+            sgx_executors: Vec::new(),
+            sev_executors: Vec::new(),
+            strategy,
             config,
-            state: Arc::new(RwLock::new(PoolState {
-                execution_count: 0,
-                last_sync_height: 0,
-                verification_results: HashMap::new(),
-            })),
+            state: Arc::new(RwLock::new(PoolState::default())),
+            metrics,
         })
     }
 
@@ -70,15 +137,10 @@ impl ExecutorPool {
             status: ExecutorStatus::Active,
         };
 
+        // Additional keeps of a type serve as warm standbys in the rotation
         match enclave_type {
-            EnclaveType::IntelSGX => {
-                assert!(self.sgx_executor.is_none(), "SGX executor already registered");
-                self.sgx_executor = Some(instance);
-            }
-            EnclaveType::AMDSEV => {
-                assert!(self.sev_executor.is_none(), "SEV executor already registered");
-                self.sev_executor = Some(instance);
-            }
+            EnclaveType::IntelSGX => self.sgx_executors.push(instance),
+            EnclaveType::AMDSEV => self.sev_executors.push(instance),
         }
 
         Ok(())
@@ -89,8 +151,10 @@ impl ExecutorPool {
         execution_id: u128,
         payload: Vec<u8>,
     ) -> Result<ExecutionResult> {
+        super::ensure_payload_size(&payload, self.config.max_payload_size)?;
+
         // Ensure both executors are available
-        let (sgx_executor, sev_executor) = self.get_active_executors()?;
+        let (sgx_executor, sev_executor) = self.select_executors().await?;
 
         // Execute on both SGX and SEV
         let (sgx_result, sev_result) = tokio::join!(
@@ -100,6 +164,8 @@ impl ExecutorPool {
 
         // Store results for verification
         let mut state = self.state.write().await;
+        state.release_keep(sgx_executor.keep.id());
+        state.release_keep(sev_executor.keep.id());
         state.verification_results.insert(
             execution_id,
             VerificationPair {
@@ -119,6 +185,8 @@ impl ExecutorPool {
         execution_id: u128,
         payload: Vec<u8>,
     ) -> Result<ExecutionResult> {
+        let requested_at = Instant::now();
+
         // Verify Keep health before execution
         let health = instance.keep.health_check().await?;
         if !self.enarx_manager.verify_keep_health(&health) {
@@ -130,6 +198,10 @@ impl ExecutorPool {
 
         // Execute with verification
         let result = instance.keep.execute_with_verification(payload).await?;
+        self.metrics.observe_verification_latency(
+            &instance.keep.enclave_type(),
+            requested_at.elapsed().as_secs_f64(),
+        );
 
         Ok(ExecutionResult {
             execution_id,
@@ -141,16 +213,100 @@ impl ExecutorPool {
         })
     }
 
-    async fn get_active_executors(&self) -> Result<(&ExecutorInstance, &ExecutorInstance)> {
-        match (&self.sgx_executor, &self.sev_executor) {
-            (Some(sgx), Some(sev)) => {
-                if sgx.status == ExecutorStatus::Active && sev.status == ExecutorStatus::Active {
-                    Ok((sgx, sev))
-                } else {
-                    Err(Error::ExecutorNotActive)
-                }
+    /// Applies a `ChallengeResolved` event from the contract: the outcome is
+    /// counted and a keep that failed its challenge leaves the rotation
+    pub fn handle_challenge_resolved(&mut self, resolved: &ChallengeResolved) {
+        self.metrics.observe_challenge_resolved(resolved);
+
+        if resolved.outcome != ChallengeStatus::Failed {
+            return;
+        }
+        for instance in self.sgx_executors.iter_mut().chain(self.sev_executors.iter_mut()) {
+            if instance.address == resolved.challenged {
+                instance.status = ExecutorStatus::Failed;
             }
-            _ => Err(Error::ExecutorNotFound)
         }
     }
+
+    /// Picks one active keep of each type per the pool's selection strategy
+    async fn select_executors(&self) -> Result<(&ExecutorInstance, &ExecutorInstance)> {
+        if self.sgx_executors.is_empty() || self.sev_executors.is_empty() {
+            return Err(Error::ExecutorNotFound);
+        }
+
+        let sgx_active = active_instances(&self.sgx_executors);
+        let sev_active = active_instances(&self.sev_executors);
+
+        let mut state = self.state.write().await;
+        let sgx_index = state
+            .select_keep(self.strategy, EnclaveType::IntelSGX, &keep_ids(&sgx_active))
+            .ok_or(Error::ExecutorNotActive)?;
+        let Some(sev_index) =
+            state.select_keep(self.strategy, EnclaveType::AMDSEV, &keep_ids(&sev_active))
+        else {
+            state.release_keep(sgx_active[sgx_index].keep.id());
+            return Err(Error::ExecutorNotActive);
+        };
+
+        Ok((sgx_active[sgx_index], sev_active[sev_index]))
+    }
+}
+
+fn active_instances(instances: &[ExecutorInstance]) -> Vec<&ExecutorInstance> {
+    instances
+        .iter()
+        .filter(|instance| instance.status == ExecutorStatus::Active)
+        .collect()
+}
+
+fn keep_ids(instances: &[&ExecutorInstance]) -> Vec<String> {
+    instances.iter().map(|instance| instance.keep.id().to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keeps(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_round_robin_alternates_keeps() {
+        let mut state = PoolState::default();
+        let sgx_keeps = keeps(&["sgx-active", "sgx-warm"]);
+        let sev_keeps = keeps(&["sev-active", "sev-warm"]);
+
+        let strategy = SelectionStrategy::RoundRobin;
+        let mut picks = Vec::new();
+        for _ in 0..4 {
+            let sgx = state.select_keep(strategy, EnclaveType::IntelSGX, &sgx_keeps);
+            let sev = state.select_keep(strategy, EnclaveType::AMDSEV, &sev_keeps);
+            picks.push((sgx.unwrap(), sev.unwrap()));
+        }
+
+        assert_eq!(picks, vec![(0, 0), (1, 1), (0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn test_least_loaded_prefers_idle_keep() {
+        let mut state = PoolState::default();
+        let sgx_keeps = keeps(&["sgx-active", "sgx-warm"]);
+        let select = |state: &mut PoolState| {
+            state.select_keep(SelectionStrategy::LeastLoaded, EnclaveType::IntelSGX, &sgx_keeps)
+        };
+
+        assert_eq!(select(&mut state), Some(0));
+        assert_eq!(select(&mut state), Some(1));
+
+        state.release_keep("sgx-warm");
+        assert_eq!(select(&mut state), Some(1));
+    }
+
+    #[test]
+    fn test_no_active_keep_selects_nothing() {
+        let mut state = PoolState::default();
+        let selected = state.select_keep(SelectionStrategy::RoundRobin, EnclaveType::AMDSEV, &[]);
+        assert_eq!(selected, None);
+    }
 }
@@ -1,4 +1,4 @@
-use wasmlanche::{public, Context, ExternalCallContext};
+use wasmlanche::{public, Context, Address, ExternalCallContext};
 use crate::{
     types::*,
     state::*,
@@ -14,12 +14,17 @@ pub fn get_governance_context(context: &mut Context) -> ExternalCallContext {
     context.to_extern(call_args_from_address(governance_address))
 }
 
+/// Opens a proposal with the governance contract responsible for `domain`.
+/// The domain is recorded under the returned proposal id so only that
+/// contract can execute the decision; the call to the governance contract
+/// itself is unchanged.
 #[public]
 pub fn create_governance_proposal(
     context: &mut Context,
+    domain: GovernanceDomain,
     proposal_type: Vec<u8>,
     proposal_ Vec<u8>,
-) {
+) -> u128 {
     ensure_initialized(context);
     let caller = context.actor();
 
@@ -40,8 +45,21 @@ pub fn create_governance_proposal(
 
     assert!(is_participant, "unauthorized proposer");
 
-    // Forward to governance contract
-    let governance_context = get_governance_context(context);
+    let proposal_id = context
+        .get(ProposalCounter())
+        .expect("state corrupt")
+        .unwrap_or(0)
+        + 1;
+    context
+        .store((
+            (ProposalCounter(), proposal_id),
+            (ProposalDomain(proposal_id), domain.clone()),
+        ))
+        .expect("failed to record proposal");
+
+    // Forward to the domain's governance contract
+    let governance_address = get_domain_governance(context, domain);
+    let governance_context = context.to_extern(call_args_from_address(governance_address));
     let result = context.call(
         governance_context,
         "create_proposal",
@@ -49,8 +67,57 @@ pub fn create_governance_proposal(
     );
 
     assert!(result.is_ok(), "governance proposal creation failed");
+
+    proposal_id
+}
+
+/// Assigns a governance contract to a domain. Only callable by the primary
+/// governance contract.
+#[public]
+pub fn set_domain_governance(
+    context: &mut Context,
+    domain: GovernanceDomain,
+    governance_address: Address,
+) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let mut contracts = context
+        .get(GovernanceContracts())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    contracts.retain(|(existing, _)| *existing != domain);
+    contracts.push((domain, governance_address));
+
+    context
+        .store_by_key(GovernanceContracts(), contracts)
+        .expect("failed to store domain governance");
 }
 
+/// Governance contract responsible for `domain`
+#[public]
+pub fn get_domain_governance(context: &mut Context, domain: GovernanceDomain) -> Address {
+    let domain_governance = context
+        .get(GovernanceContracts())
+        .expect("state corrupt")
+        .unwrap_or_default()
+        .into_iter()
+        .find(|(existing, _)| *existing == domain)
+        .map(|(_, address)| address);
+
+    match domain_governance {
+        Some(address) => address,
+        None => context
+            .get(GovernanceContract())
+            .expect("state corrupt")
+            .expect("governance contract not initialized"),
+    }
+}
+
+/// Executes a decision on a proposal. Only the governance contract of the
+/// domain the proposal was created under may execute it, and only once;
+/// proposals without a recorded domain, e.g. ones opened directly on the
+/// primary governance contract, are executed by the primary contract.
 #[public]
 pub fn execute_governance_decision(
     context: &mut Context,
@@ -58,15 +125,21 @@ pub fn execute_governance_decision(
     execution_ Vec<u8>,
 ) {
     ensure_initialized(context);
-    
-    // Verify caller is governance contract
-    let governance_address = context
-        .get(GovernanceContract())
-        .expect("state corrupt")
-        .expect("governance contract not initialized");
 
+    // Verify caller governs the proposal's domain
+    let governance_address = match context.get(ProposalDomain(proposal_id)).expect("state corrupt") {
+        Some(domain) => get_domain_governance(context, domain),
+        None => context
+            .get(GovernanceContract())
+            .expect("state corrupt")
+            .expect("governance contract not initialized"),
+    };
     assert!(context.actor() == governance_address, "unauthorized executor");
 
+    context
+        .remove(ProposalDomain(proposal_id))
+        .expect("failed to close proposal");
+
     // Execute decision based on proposal type
     execute_governance_action(context, proposal_id, &execution_data);
 }
@@ -76,5 +149,147 @@ fn execute_governance_action(
     proposal_id: u128,
     execution_ &[u8],
 ) {
-    update_global_state(context);
+    update_global_state(context, "execute_governance_action", &[&proposal_id.to_le_bytes(), execution_data]);
+}
+
+#[public]
+pub fn get_system_config(context: &mut Context) -> SystemConfig {
+    load_system_config(context)
+}
+
+/// Applies a single field update to the system configuration
+#[public]
+pub fn update_system_config(context: &mut Context, update: ConfigUpdate) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let mut config = load_system_config(context);
+
+    match update {
+        ConfigUpdate::MaxActiveChallenges(limit) => {
+            config.max_active_challenges = limit;
+        },
+        ConfigUpdate::MinStake(min_stake) => {
+            config.min_stake = min_stake;
+        },
+        ConfigUpdate::Quorum { numerator, denominator } => {
+            config.quorum_numerator = numerator;
+            config.quorum_denominator = denominator;
+        },
+        ConfigUpdate::RewardPolicy(policy) => {
+            config.reward_policy = policy;
+        },
+        ConfigUpdate::CompressProofs(enabled) => {
+            config.compress_proofs = enabled;
+        },
+        ConfigUpdate::MinHeartbeatSpacing(blocks) => {
+            config.min_heartbeat_spacing = blocks;
+        },
+        ConfigUpdate::MaxWatchdogs(limit) => {
+            config.max_watchdogs = limit;
+        },
+        ConfigUpdate::HashDrawbridgeTokens(enabled) => {
+            config.hash_drawbridge_tokens = enabled;
+        },
+        ConfigUpdate::MaxAttestationReportLen(max_len) => {
+            config.max_attestation_report_len = max_len;
+        },
+        ConfigUpdate::RequireEnclaveDiversity(required) => {
+            config.require_enclave_diversity = required;
+        },
+        ConfigUpdate::AttestationValidity { enclave_type, period } => {
+            match enclave_type {
+                EnclaveType::IntelSGX => config.sgx_attestation_validity = period,
+                EnclaveType::AMDSEV => config.sev_attestation_validity = period,
+            }
+        },
+        ConfigUpdate::MismatchPause { threshold, window } => {
+            config.mismatch_pause_threshold = threshold;
+            config.mismatch_pause_window = window;
+        },
+        ConfigUpdate::StakeHandoffPolicy(policy) => {
+            config.stake_handoff_policy = policy;
+        },
+        ConfigUpdate::VoteTiePolicy(policy) => {
+            config.vote_tie_policy = policy;
+        },
+        ConfigUpdate::CrashRecoveryCooldown(seconds) => {
+            config.crash_recovery_cooldown = seconds;
+        },
+        ConfigUpdate::MinProofWitnesses(count) => {
+            config.min_proof_witnesses = count;
+        },
+        ConfigUpdate::ExecutionDisputeWindow(seconds) => {
+            config.execution_dispute_window = seconds;
+        },
+        ConfigUpdate::TimeoutInterval(seconds) => {
+            config.timeout_interval = seconds;
+        },
+        ConfigUpdate::ChallengeResponseWindow(seconds) => {
+            config.challenge_response_window = seconds;
+        },
+        ConfigUpdate::AutoChallengeOnMismatch(enabled) => {
+            config.auto_challenge_on_mismatch = enabled;
+        },
+        ConfigUpdate::MaxPayloadSize { enclave_type, size } => {
+            match enclave_type {
+                EnclaveType::IntelSGX => config.sgx_max_payload_size = size,
+                EnclaveType::AMDSEV => config.sev_max_payload_size = size,
+            }
+        },
+        ConfigUpdate::MismatchSuspensionThreshold(threshold) => {
+            config.mismatch_suspension_threshold = threshold;
+        },
+        ConfigUpdate::UptimeMultipliers(steps) => {
+            config.uptime_multipliers = steps;
+        },
+        ConfigUpdate::MaxClockSkew(skew) => {
+            config.max_clock_skew = skew;
+        },
+        ConfigUpdate::MismatchEscalation { challenge_after, remove_after } => {
+            config.mismatch_challenge_threshold = challenge_after;
+            config.mismatch_removal_threshold = remove_after;
+        },
+        ConfigUpdate::RegistrationCosigns(cosigns) => {
+            let watchdog_count = context
+                .get(WatchdogPool())
+                .expect("state corrupt")
+                .map_or(0, |pool| pool.watchdogs.len());
+            assert!(cosigns <= watchdog_count, "co-sign requirement exceeds watchdog pool");
+            config.registration_cosigns = cosigns;
+        },
+        ConfigUpdate::RewardCadence(cadence) => {
+            config.reward_cadence = cadence;
+        },
+        ConfigUpdate::VerificationMode(mode) => {
+            config.verification_mode = mode;
+        },
+    }
+
+    config.validate();
+
+    context
+        .store_by_key(SystemConfig(), config)
+        .expect("failed to update system config");
+}
+
+/// Lifts an automatic pause once the cause of the mismatch burst is understood
+#[public]
+pub fn unpause_system(context: &mut Context) {
+    ensure_governance(context);
+
+    context
+        .store((
+            (SystemPaused(), false),
+            (RecentMismatches(), Vec::new()),
+        ))
+        .expect("failed to unpause system");
+}
+
+#[public]
+pub fn is_system_paused(context: &mut Context) -> bool {
+    context
+        .get(SystemPaused())
+        .expect("state corrupt")
+        .unwrap_or(false)
 }
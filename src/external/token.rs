@@ -2,7 +2,10 @@ use wasmlanche::{public, Context, Address, ContractId, ExternalCallContext};
 use crate::{
     types::*,
     state::*,
-    core::utils::call_args_from_address,
+    core::{utils::call_args_from_address, get_enclave_type, get_uptime_ratio},
+    MIN_DISTRIBUTABLE_REWARD,
+    UPTIME_BASIS_POINTS,
+    MAX_STAKE_PAGE_SIZE,
 };
 
 pub fn get_token_context(context: &mut Context) -> ExternalCallContext {
@@ -81,8 +84,17 @@ pub fn stake_tokens(context: &mut Context, amount: u64) {
     record_token_interaction(context, caller, interaction);
 }
 
+/// Credits the distributable balance to executors and watchdogs. Anyone may
+/// trigger a payout, but only governance starts a new uptime window, so a
+/// well-timed call can't cut a participant's window short.
 #[public]
 pub fn distribute_rewards(context: &mut Context) {
+    let governance = context.get(GovernanceContract()).expect("state corrupt");
+    let start_new_period = governance == Some(context.actor());
+    pay_out_rewards(context, start_new_period);
+}
+
+fn pay_out_rewards(context: &mut Context, start_new_period: bool) {
     ensure_initialized(context);
     ensure_phase(context, Phase::Executing);
 
@@ -99,39 +111,185 @@ pub fn distribute_rewards(context: &mut Context) {
     let token_context = get_token_context(context);
     let contract_balance = token::balance_of(token_context, context.contract_address());
 
-    // Calculate rewards
-    let executor_reward = contract_balance / 3; // 1/3 for executors
-    let watchdog_reward = contract_balance / 3; // 1/3 for watchdogs
-    // 1/3 remains in contract for future operations
-
-    // Distribute to executors
-    if let Some(sgx_executor) = executor_pool.sgx_executor {
-        token::transfer(
-            token_context,
-            sgx_executor,
-            executor_reward / 2,
-        );
+    // Stakes, the existing reserve and unclaimed rewards are never distributed
+    let total_staked = context.get(TotalStaked()).expect("state corrupt").unwrap_or(0);
+    let reserve = context.get(RewardReserve()).expect("state corrupt").unwrap_or(0);
+    let total_pending = context.get(TotalPendingRewards()).expect("state corrupt").unwrap_or(0);
+    let distributable = contract_balance.saturating_sub(total_staked + reserve + total_pending);
+
+    // Dust balances would round every share down to zero
+    if distributable < MIN_DISTRIBUTABLE_REWARD {
+        context
+            .emit_event("RewardDistributionSkipped", &distributable)
+            .expect("failed to emit distribution skipped event");
+        return;
     }
-    if let Some(sev_executor) = executor_pool.sev_executor {
-        token::transfer(
-            token_context,
-            sev_executor,
-            executor_reward / 2,
-        );
+
+    // Calculate rewards per the configured policy; the remainder stays in
+    // the contract as reserve for future operations
+    let config = load_system_config(context);
+    let policy = config.reward_policy.clone();
+    let executor_reward =
+        (distributable as u128 * policy.executor_share as u128 / policy.total_shares as u128) as u64;
+    let watchdog_reward =
+        (distributable as u128 * policy.watchdog_share as u128 / policy.total_shares as u128) as u64;
+
+    // Credit executors in proportion to their verified executions, or
+    // equally if neither has any yet; rewards are pulled with `claim_rewards`
+    let executors: Vec<(Address, u64)> = executor_pool
+        .sgx_executor
+        .into_iter()
+        .chain(executor_pool.sev_executor)
+        .map(|executor| {
+            let count = context
+                .get(KeepExecutionCount(executor))
+                .expect("state corrupt")
+                .unwrap_or(0);
+            (executor, count)
+        })
+        .collect();
+    let total_executions: u64 = executors.iter().map(|(_, count)| count).sum();
+
+    // Each share is scaled by the participant's uptime multiplier; whatever
+    // low uptime forfeits is added to the reserve
+    let mut credited = 0;
+    for (executor, count) in executors {
+        let share = if total_executions == 0 {
+            executor_reward / 2
+        } else {
+            (executor_reward as u128 * count as u128 / total_executions as u128) as u64
+        };
+        let reward = apply_uptime_multiplier(context, &config, executor, share);
+        if reward > 0 {
+            credit_reward(context, executor, reward);
+            credited += reward;
+        }
     }
 
-    // Distribute to watchdogs
+    // Credit watchdogs
     let watchdog_count = watchdog_pool.watchdogs.len();
     if watchdog_count > 0 {
         let reward_per_watchdog = watchdog_reward / watchdog_count as u64;
-        for (watchdog, _) in watchdog_pool.watchdogs {
-            token::transfer(
-                token_context,
-                watchdog,
-                reward_per_watchdog,
-            );
+        for (watchdog, _) in watchdog_pool.watchdogs.iter() {
+            let reward = apply_uptime_multiplier(context, &config, *watchdog, reward_per_watchdog);
+            if reward > 0 {
+                credit_reward(context, *watchdog, reward);
+                credited += reward;
+            }
         }
     }
+
+    context
+        .store_by_key(RewardReserve(), reserve + distributable - credited)
+        .expect("failed to update reward reserve");
+
+    if !start_new_period {
+        return;
+    }
+
+    // Start a fresh uptime window for the next period
+    context
+        .store_by_key(RewardPeriodStart(), context.timestamp())
+        .expect("failed to start reward period");
+    for participant in executor_pool
+        .sgx_executor
+        .into_iter()
+        .chain(executor_pool.sev_executor)
+        .chain(watchdog_pool.watchdogs.into_iter().map(|(watchdog, _)| watchdog))
+    {
+        context
+            .remove(PeriodHeartbeats(participant))
+            .expect("failed to reset period heartbeats");
+    }
+}
+
+/// Distributes rewards once the configured `RewardCadence` has elapsed.
+/// Called after every verified execution so rewards flow without an
+/// external keeper.
+pub fn distribute_rewards_if_due(context: &mut Context) {
+    let cadence = load_system_config(context).reward_cadence;
+    let verified = context
+        .get(VerifiedSinceDistribution())
+        .expect("state corrupt")
+        .unwrap_or(0)
+        + 1;
+    let last_block = context
+        .get(LastDistributionBlock())
+        .expect("state corrupt")
+        .unwrap_or(0);
+
+    let due = match cadence {
+        RewardCadence::Manual => return,
+        RewardCadence::VerifiedExecutions(count) => verified >= count,
+        RewardCadence::Blocks(blocks) => context.block_height().saturating_sub(last_block) >= blocks,
+    };
+    let phase = context.get(CurrentPhase()).expect("state corrupt");
+    if !due || phase != Some(Phase::Executing) {
+        context
+            .store_by_key(VerifiedSinceDistribution(), verified)
+            .expect("failed to update distribution counter");
+        return;
+    }
+
+    context
+        .store((
+            (VerifiedSinceDistribution(), 0),
+            (LastDistributionBlock(), context.block_height()),
+        ))
+        .expect("failed to reset distribution counters");
+    pay_out_rewards(context, true);
+}
+
+fn apply_uptime_multiplier(
+    context: &mut Context,
+    config: &SystemConfig,
+    participant: Address,
+    share: u64,
+) -> u64 {
+    let multiplier = config.uptime_multiplier(get_uptime_ratio(context, participant));
+    (share as u128 * multiplier as u128 / UPTIME_BASIS_POINTS as u128) as u64
+}
+
+/// Transfers the caller's pending rewards. The ledger entry is zeroed before
+/// the external transfer so a retried claim cannot pay twice; if the transfer
+/// fails the entry is restored and the amount remains claimable.
+#[public]
+pub fn claim_rewards(context: &mut Context) -> u64 {
+    ensure_initialized(context);
+    let caller = context.actor();
+
+    let amount = get_pending_reward(context, caller);
+    assert!(amount > 0, "no rewards to claim");
+
+    let total_pending = context.get(TotalPendingRewards()).expect("state corrupt").unwrap_or(0);
+    context
+        .store((
+            (PendingReward(caller), 0),
+            (TotalPendingRewards(), total_pending.saturating_sub(amount)),
+        ))
+        .expect("failed to clear pending reward");
+
+    let token_context = get_token_context(context);
+    if !token::transfer(token_context, caller, amount) {
+        context
+            .store((
+                (PendingReward(caller), amount),
+                (TotalPendingRewards(), total_pending),
+            ))
+            .expect("failed to restore pending reward");
+        log_warn!("Reward claim transfer failed: amount={}", amount);
+        return 0;
+    }
+
+    amount
+}
+
+#[public]
+pub fn get_pending_reward(context: &mut Context, address: Address) -> u64 {
+    context
+        .get(PendingReward(address))
+        .expect("state corrupt")
+        .unwrap_or(0)
 }
 
 #[public]
@@ -148,19 +306,261 @@ pub fn get_total_staked(context: &mut Context) -> u64 {
     token::balance_of(token_context, context.contract_address())
 }
 
+/// Splits the contract's holdings per ledger. Anything in the token balance
+/// beyond the sum of these is undistributed rewards.
+#[public]
+pub fn get_balance_breakdown(context: &mut Context) -> BalanceBreakdown {
+    BalanceBreakdown {
+        staked: context.get(TotalStaked()).expect("state corrupt").unwrap_or(0),
+        reserve: get_reward_reserve(context),
+        pending_rewards: context.get(TotalPendingRewards()).expect("state corrupt").unwrap_or(0),
+    }
+}
+
 #[public]
 pub fn has_minimum_stake(context: &mut Context, address: Address) -> bool {
     ensure_initialized(context);
     let token_context = get_token_context(context);
     let balance = token::balance_of(token_context, address);
     
-    let min_stake = match context.get(EnclaveType(address)) {
-        Ok(Some(EnclaveType::IntelSGX)) => 1000,
-        Ok(Some(EnclaveType::AMDSEV)) => 1000,
-        _ => return false,
+    if get_enclave_type(context, address).is_none() {
+        return false;
+    }
+
+    balance >= load_system_config(context).min_stake
+}
+
+#[public]
+pub fn get_reward_reserve(context: &mut Context) -> u64 {
+    context
+        .get(RewardReserve())
+        .expect("state corrupt")
+        .unwrap_or(0)
+}
+
+#[public]
+pub fn get_staked_amount(context: &mut Context, address: Address) -> u64 {
+    context
+        .get(StakedAmount(address))
+        .expect("state corrupt")
+        .unwrap_or(0)
+}
+
+/// One `(participant, staked amount)` row per executor then watchdog,
+/// starting at `offset`. At most `MAX_STAKE_PAGE_SIZE` rows are returned per
+/// call.
+#[public]
+pub fn get_all_stakes(context: &mut Context, offset: usize, limit: usize) -> Vec<(Address, u64)> {
+    ensure_initialized(context);
+
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+
+    executor_pool
+        .sgx_executor
+        .into_iter()
+        .chain(executor_pool.sev_executor)
+        .chain(watchdog_pool.watchdogs.into_iter().map(|(watchdog, _)| watchdog))
+        .skip(offset)
+        .take(limit.min(MAX_STAKE_PAGE_SIZE))
+        .map(|participant| (participant, get_staked_amount(context, participant)))
+        .collect()
+}
+
+/// Switches to a new token contract. Every stake is refunded to its staker in
+/// the old token and only the surplus goes to the plan's recipient; the new
+/// contract must already hold enough of the new token to cover the reserve
+/// and unclaimed rewards. All checks run before any state changes so a
+/// migration either completes or leaves the old token in place. Only callable
+/// by governance.
+#[public]
+pub fn migrate_token_contract(
+    context: &mut Context,
+    new_contract: Address,
+    migration_plan: TokenMigrationPlan,
+) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let old_contract = context
+        .get(TokenContract())
+        .expect("state corrupt")
+        .expect("token contract not initialized");
+    assert!(new_contract != old_contract, "token contract unchanged");
+
+    let total_staked = context.get(TotalStaked()).expect("state corrupt").unwrap_or(0);
+    let total_pending = context.get(TotalPendingRewards()).expect("state corrupt").unwrap_or(0);
+    let obligations = total_staked + get_reward_reserve(context) + total_pending;
+    assert!(
+        migration_plan.expected_obligations == obligations,
+        "migration plan does not match ledger"
+    );
+
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+    let stakers: Vec<Address> = executor_pool
+        .sgx_executor
+        .into_iter()
+        .chain(executor_pool.sev_executor)
+        .chain(watchdog_pool.watchdogs.into_iter().map(|(watchdog, _)| watchdog))
+        .chain(watchdog_pool.ready_tees.into_iter().map(|(tee, _)| tee))
+        .collect();
+
+    // Stake held by an address outside the pools would be stranded on the
+    // old contract and then swept into the surplus, so refuse up front
+    let refundable: u64 = stakers
+        .iter()
+        .map(|staker| get_staked_amount(context, *staker))
+        .sum();
+    assert!(refundable == total_staked, "stake ledger not covered by participants");
+
+    let new_token_context = context.to_extern(call_args_from_address(new_contract));
+    let new_balance = token::balance_of(new_token_context, context.contract_address());
+    assert!(
+        new_balance >= obligations - total_staked,
+        "new token balance cannot cover obligations"
+    );
+
+    let old_token_context = get_token_context(context);
+    let old_balance = token::balance_of(old_token_context, context.contract_address());
+    assert!(old_balance >= total_staked, "old token balance cannot cover stakes");
+
+    let refunded: u64 = stakers
+        .into_iter()
+        .map(|staker| refund_stake(context, staker))
+        .sum();
+
+    let surplus = old_balance - refunded;
+    if surplus > 0 {
+        assert!(
+            token::transfer(old_token_context, migration_plan.old_balance_recipient, surplus),
+            "failed to move old token balance"
+        );
+    }
+
+    context
+        .store_by_key(TokenContract(), new_contract)
+        .expect("failed to store token contract");
+    context
+        .emit_event("TokenContractMigrated", &(old_contract, new_contract))
+        .expect("failed to emit token migration event");
+
+    update_global_state(context, "migrate_token_contract", &[new_contract.to_string().as_bytes()]);
+}
+
+/// Withdraws from the reward reserve. Only callable by the governance contract
+/// once a proposal has passed; active stakes are never touched. A failed
+/// transfer aborts so the reserve is left as it was.
+#[public]
+pub fn withdraw_reserve(context: &mut Context, to: Address, amount: u64) {
+    ensure_initialized(context);
+    ensure_governance(context);
+
+    let reserve = get_reward_reserve(context);
+    assert!(amount <= reserve, "amount exceeds reserve");
+
+    context
+        .store_by_key(RewardReserve(), reserve - amount)
+        .expect("failed to update reward reserve");
+
+    let token_context = get_token_context(context);
+    assert!(
+        token::transfer(token_context, to, amount),
+        "reserve withdrawal transfer failed"
+    );
+}
+
+fn credit_reward(context: &mut Context, address: Address, amount: u64) {
+    let pending = get_pending_reward(context, address);
+    let total_pending = context.get(TotalPendingRewards()).expect("state corrupt").unwrap_or(0);
+
+    context
+        .store((
+            (PendingReward(address), pending + amount),
+            (TotalPendingRewards(), total_pending + amount),
+        ))
+        .expect("failed to credit reward");
+}
+
+/// Returns the participant's entire stake to them and returns the refunded
+/// amount. A failed transfer aborts so the stake stays on the ledger.
+pub fn refund_stake(context: &mut Context, address: Address) -> u64 {
+    let staked = get_staked_amount(context, address);
+    if staked == 0 {
+        return 0;
+    }
+
+    let token_context = get_token_context(context);
+    assert!(
+        token::transfer(token_context, address, staked),
+        "stake refund transfer failed"
+    );
+
+    let interaction = TokenInteraction {
+        token_address: token_context.contract_address,
+        amount: staked,
+        interaction_type: TokenInteractionType::Unstake,
     };
+    record_token_interaction(context, address, interaction);
 
-    balance >= min_stake
+    staked
+}
+
+/// Forfeits the offender's entire stake to the reward reserve and returns the
+/// slashed amount. The tokens stay in the contract; only accounting changes.
+pub fn slash_stake(context: &mut Context, offender: Address) -> u64 {
+    let staked = get_staked_amount(context, offender);
+    let total_staked = context.get(TotalStaked()).expect("state corrupt").unwrap_or(0);
+    let reserve = get_reward_reserve(context);
+
+    context
+        .store((
+            (StakedAmount(offender), 0),
+            (TotalStaked(), total_staked.saturating_sub(staked)),
+            (RewardReserve(), reserve + staked),
+        ))
+        .expect("failed to slash stake");
+
+    staked
+}
+
+/// Moves the entire stake of `from` to `to` and returns the moved amount
+pub fn transfer_stake(context: &mut Context, from: Address, to: Address) -> u64 {
+    let staked = get_staked_amount(context, from);
+    let recipient_staked = get_staked_amount(context, to);
+
+    context
+        .store((
+            (StakedAmount(from), 0),
+            (StakedAmount(to), recipient_staked + staked),
+        ))
+        .expect("failed to transfer stake");
+
+    staked
+}
+
+/// Settles a replaced executor's stake according to the configured
+/// `StakeHandoffPolicy` and returns the amount moved
+pub fn hand_off_stake(context: &mut Context, failed_executor: Address, replacement: Address) -> u64 {
+    match load_system_config(context).stake_handoff_policy {
+        StakeHandoffPolicy::SlashAndRedistribute => slash_stake(context, failed_executor),
+        StakeHandoffPolicy::TransferToReplacement => {
+            transfer_stake(context, failed_executor, replacement)
+        },
+    }
 }
 
 fn record_token_interaction(
@@ -168,5 +568,28 @@ fn record_token_interaction(
     address: Address,
     interaction: TokenInteraction,
 ) {
-    update_global_state(context);
+    let staked = context.get(StakedAmount(address)).expect("state corrupt").unwrap_or(0);
+    let total_staked = context.get(TotalStaked()).expect("state corrupt").unwrap_or(0);
+
+    let (staked, total_staked) = match interaction.interaction_type {
+        TokenInteractionType::Stake => (staked + interaction.amount, total_staked + interaction.amount),
+        TokenInteractionType::Unstake => (
+            staked.saturating_sub(interaction.amount),
+            total_staked.saturating_sub(interaction.amount),
+        ),
+        TokenInteractionType::Reward => (staked, total_staked),
+    };
+
+    context
+        .store((
+            (StakedAmount(address), staked),
+            (TotalStaked(), total_staked),
+        ))
+        .expect("failed to record token interaction");
+
+    update_global_state(
+        context,
+        "record_token_interaction",
+        &[address.to_string().as_bytes(), &interaction.amount.to_le_bytes()],
+    );
 }
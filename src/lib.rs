@@ -1,6 +1,8 @@
 // Copyright (C) 2024, Rhombus Tech. All rights reserved.
 // See the file LICENSE for licensing terms.
 
+#[macro_use]
+pub mod logging;
 pub mod types;
 pub mod state;
 pub mod core;
@@ -8,7 +10,10 @@ pub mod challenge;
 pub mod external;
 pub mod execution;
 pub mod error;
-pub use error::{Error, Result};
+pub use error::{Error, Result, AttestationFailure};
+
+#[cfg(test)]
+mod tests;
 
 pub use types::*;
 pub use state::*;
@@ -19,3 +24,32 @@ pub const ZERO: u64 = 0;
 pub const TIMEOUT_INTERVAL: u64 = 15;
 pub const CHALLENGE_RESPONSE_WINDOW: u64 = 100;
 pub const MIN_WATCHDOGS: usize = 3;
+pub const MAX_WATCHDOGS: usize = 64;
+pub const MAX_ACTIVE_CHALLENGES: usize = 10;
+pub const MIN_STAKE: u64 = 1000;
+pub const CHALLENGE_DEADLINE_EXTENSION: u64 = 50;
+pub const MIN_HEARTBEAT_SPACING: u64 = 5;
+pub const HEALTH_MEMORY_THRESHOLD_PERCENT: usize = 90;
+pub const MAX_ATTESTATION_REPORT_LEN: usize = 4096;
+pub const REATTESTATION_WINDOW: u64 = 100;
+pub const CHALLENGE_REVEAL_WINDOW: u64 = 20;
+pub const ATTESTATION_VALIDITY_PERIOD: u64 = 86400;
+pub const MISMATCH_PAUSE_THRESHOLD: usize = 5;
+pub const MISMATCH_PAUSE_WINDOW: u64 = 600;
+pub const CURRENT_STATE_VERSION: u32 = 1;
+pub const MIGRATION_ROLLBACK_WINDOW: u64 = 3600;
+pub const MAX_CHALLENGE_PAGE_SIZE: usize = 50;
+pub const CRASH_RECOVERY_COOLDOWN: u64 = 3600;
+pub const MAX_HEARTBEAT_MISSES: u64 = 3;
+pub const HEARTBEAT_RECOVERY_STREAK: u64 = 10;
+pub const MIN_DISTRIBUTABLE_REWARD: u64 = 100;
+pub const MAX_THROUGHPUT_WINDOW: u64 = 10_000;
+pub const MAX_PAYLOAD_SIZE: usize = 1_048_576;
+pub const MISMATCH_SUSPENSION_THRESHOLD: u64 = 3;
+pub const UPTIME_BASIS_POINTS: u64 = 10_000;
+pub const MAX_CLOCK_SKEW: u64 = 10;
+pub const MAX_REPLACEMENT_PAGE_SIZE: usize = 50;
+pub const MISMATCH_REMOVAL_THRESHOLD: u64 = 5;
+pub const MAX_STAKE_PAGE_SIZE: usize = 50;
+pub const TIEBREAKER_QUORUM: usize = 2;
+pub const PENDING_REGISTRATION_TTL: u64 = 3600;
@@ -0,0 +1,59 @@
+// Lightweight logging facade shared by the contract and the off-chain executor.
+//
+// Messages are forwarded to `wasmlanche::dbg!` tagged with their severity.
+// Logging is enabled in debug builds and compiled out in release builds
+// unless the `release-logging` feature is set.
+
+#[cfg(test)]
+use std::cell::RefCell;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+pub const LOGGING_ENABLED: bool = cfg!(any(debug_assertions, feature = "release-logging"));
+
+#[cfg(test)]
+thread_local! {
+    static CAPTURED_LOGS: RefCell<Vec<(LogLevel, String)>> = RefCell::new(Vec::new());
+}
+
+pub fn emit(level: LogLevel, message: String) {
+    #[cfg(test)]
+    CAPTURED_LOGS.with(|logs| logs.borrow_mut().push((level, message.clone())));
+
+    wasmlanche::dbg!(level, message);
+}
+
+/// Drains the log records captured on the current thread
+#[cfg(test)]
+pub fn take_captured_logs() -> Vec<(LogLevel, String)> {
+    CAPTURED_LOGS.with(|logs| logs.borrow_mut().drain(..).collect())
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        if $crate::logging::LOGGING_ENABLED {
+            $crate::logging::emit($crate::logging::LogLevel::Info, format!($($arg)*));
+        }
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        if $crate::logging::LOGGING_ENABLED {
+            $crate::logging::emit($crate::logging::LogLevel::Warn, format!($($arg)*));
+        }
+    };
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        if $crate::logging::LOGGING_ENABLED {
+            $crate::logging::emit($crate::logging::LogLevel::Error, format!($($arg)*));
+        }
+    };
+}
@@ -1,4 +1,10 @@
-use prometheus::{Counter, Histogram, register_counter, register_histogram};
+use prometheus::{
+    Counter, CounterVec, Histogram, HistogramOpts, Opts,
+    register_counter, register_counter_vec, register_histogram,
+};
+use crate::types::{ChallengeResolved, ChallengeStatus, ChallengeType, EnclaveType};
+
+const CHALLENGE_OUTCOME_LABELS: [&str; 2] = ["challenge_type", "outcome"];
 
 pub struct PoolMetrics {
     pub execution_time: Histogram,
@@ -6,17 +12,181 @@ pub struct PoolMetrics {
     pub failed_executions: Counter,
     pub successful_challenges: Counter,
     pub failed_challenges: Counter,
+    /// Resolved challenges by `challenge_type` and `outcome`
+    pub challenge_outcomes: CounterVec,
     pub executor_replacements: Counter,
+    /// Time from execution request to the SGX executor's result submission
+    pub sgx_verification_latency: Histogram,
+    /// Time from execution request to the SEV executor's result submission
+    pub sev_verification_latency: Histogram,
 }
 
 impl PoolMetrics {
+    /// Registers the collectors in the default registry. Registering the
+    /// same names twice fails, so build this once and share it.
     pub fn new() -> Self {
         Self {
             execution_time: register_histogram!(
                 "executor_execution_time_seconds",
                 "Time spent executing requests"
             ).unwrap(),
+            sgx_verification_latency: register_histogram!(
+                verification_latency_opts("sgx")
+            ).unwrap(),
+            sev_verification_latency: register_histogram!(
+                verification_latency_opts("sev")
+            ).unwrap(),
+            challenge_outcomes: register_counter_vec!(
+                challenge_outcome_opts(),
+                &CHALLENGE_OUTCOME_LABELS
+            ).unwrap(),
             // ... other metrics
         }
     }
+
+    /// Records how long an enclave type took to submit its result
+    pub fn observe_verification_latency(&self, enclave_type: &EnclaveType, seconds: f64) {
+        self.verification_latency(enclave_type).observe(seconds);
+    }
+
+    pub fn verification_latency(&self, enclave_type: &EnclaveType) -> &Histogram {
+        match enclave_type {
+            EnclaveType::IntelSGX => &self.sgx_verification_latency,
+            EnclaveType::AMDSEV => &self.sev_verification_latency,
+        }
+    }
+
+    /// Counts a challenge the contract reported resolved through its
+    /// `ChallengeResolved` event. Only verified and failed challenges carry
+    /// a verdict; any other status is ignored.
+    pub fn observe_challenge_resolved(&self, resolved: &ChallengeResolved) {
+        match resolved.outcome {
+            ChallengeStatus::Verified => self.record_challenge_outcome(&resolved.challenge_type, true),
+            ChallengeStatus::Failed => self.record_challenge_outcome(&resolved.challenge_type, false),
+            _ => {}
+        }
+    }
+
+    /// Counts a resolved challenge in the aggregate and per-type counters.
+    /// `succeeded` means the challenged party was verified.
+    pub fn record_challenge_outcome(&self, challenge_type: &ChallengeType, succeeded: bool) {
+        if succeeded {
+            self.successful_challenges.inc();
+        } else {
+            self.failed_challenges.inc();
+        }
+        inc_challenge_outcome(&self.challenge_outcomes, challenge_type, succeeded);
+    }
+}
+
+fn inc_challenge_outcome(outcomes: &CounterVec, challenge_type: &ChallengeType, succeeded: bool) {
+    let outcome = if succeeded { "success" } else { "failure" };
+    outcomes
+        .with_label_values(&[challenge_type_label(challenge_type), outcome])
+        .inc();
+}
+
+fn challenge_type_label(challenge_type: &ChallengeType) -> &'static str {
+    match challenge_type {
+        ChallengeType::Attestation => "attestation",
+        ChallengeType::Execution => "execution",
+        ChallengeType::StateVerification => "state_verification",
+        ChallengeType::HeartbeatMissed => "heartbeat_missed",
+        ChallengeType::HealthDegradation => "health_degradation",
+        ChallengeType::WatchdogMisbehavior => "watchdog_misbehavior",
+    }
+}
+
+fn challenge_outcome_opts() -> Opts {
+    Opts::new("executor_challenge_outcomes_total", "Resolved challenges by type and outcome")
+}
+
+fn verification_latency_opts(enclave_type: &str) -> HistogramOpts {
+    HistogramOpts::new(
+        "executor_verification_latency_seconds",
+        "Time from execution request to result submission",
+    )
+    .const_label("enclave_type", enclave_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasmlanche::Address;
+
+    #[test]
+    fn test_verification_latency_per_enclave_type() {
+        let sgx = Histogram::with_opts(verification_latency_opts("sgx")).unwrap();
+        let sev = Histogram::with_opts(verification_latency_opts("sev")).unwrap();
+
+        sgx.observe(0.5);
+        sgx.observe(1.5);
+        sev.observe(3.0);
+
+        assert_eq!(sgx.get_sample_count(), 2);
+        assert_eq!(sgx.get_sample_sum(), 2.0);
+        assert_eq!(sev.get_sample_count(), 1);
+        assert_eq!(sev.get_sample_sum(), 3.0);
+
+        let label = &sgx.desc()[0].const_label_pairs[0];
+        assert_eq!(label.get_name(), "enclave_type");
+        assert_eq!(label.get_value(), "sgx");
+    }
+
+    /// Metrics backed by unregistered collectors, so tests don't clash in
+    /// the global registry
+    fn unregistered_metrics() -> PoolMetrics {
+        let counter = |name: &str| Counter::new(name, name).unwrap();
+        let histogram = |name: &str| Histogram::with_opts(HistogramOpts::new(name, name)).unwrap();
+        PoolMetrics {
+            execution_time: histogram("execution_time"),
+            successful_executions: counter("successful_executions"),
+            failed_executions: counter("failed_executions"),
+            successful_challenges: counter("successful_challenges"),
+            failed_challenges: counter("failed_challenges"),
+            challenge_outcomes: CounterVec::new(challenge_outcome_opts(), &CHALLENGE_OUTCOME_LABELS)
+                .unwrap(),
+            executor_replacements: counter("executor_replacements"),
+            sgx_verification_latency: Histogram::with_opts(verification_latency_opts("sgx")).unwrap(),
+            sev_verification_latency: Histogram::with_opts(verification_latency_opts("sev")).unwrap(),
+        }
+    }
+
+    fn resolved(challenge_type: ChallengeType, outcome: ChallengeStatus) -> ChallengeResolved {
+        ChallengeResolved {
+            challenge_id: 1,
+            challenged: Address::from([3u8; 32]),
+            challenge_type,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_challenge_outcomes_per_type() {
+        let metrics = unregistered_metrics();
+
+        metrics.observe_challenge_resolved(&resolved(ChallengeType::Attestation, ChallengeStatus::Failed));
+        metrics.observe_challenge_resolved(&resolved(ChallengeType::Execution, ChallengeStatus::Verified));
+        metrics.observe_challenge_resolved(&resolved(ChallengeType::Execution, ChallengeStatus::Expired));
+
+        let count = |challenge_type, outcome| {
+            metrics.challenge_outcomes.with_label_values(&[challenge_type, outcome]).get()
+        };
+        assert_eq!(count("attestation", "failure"), 1.0);
+        assert_eq!(count("attestation", "success"), 0.0);
+        assert_eq!(count("execution", "success"), 1.0);
+        assert_eq!(count("execution", "failure"), 0.0);
+        assert_eq!(metrics.successful_challenges.get(), 1.0);
+        assert_eq!(metrics.failed_challenges.get(), 1.0);
+    }
+
+    #[test]
+    fn test_observed_latency_lands_in_enclave_histogram() {
+        let metrics = unregistered_metrics();
+
+        metrics.observe_verification_latency(&EnclaveType::AMDSEV, 2.0);
+
+        assert_eq!(metrics.sev_verification_latency.get_sample_count(), 1);
+        assert_eq!(metrics.sgx_verification_latency.get_sample_count(), 0);
+    }
 }
@@ -1,21 +1,67 @@
 use wasmlanche::{state_schema, Address};
-use crate::types::*;
+use crate::{
+    types::*,
+    core::{compress_proof, decompress_proof, encode_tx_link, hash_incremental, drawbridge_token_hash},
+};
 
 state_schema! {
     /// System state
     CurrentPhase() => Phase,
     SystemInitialized() => bool,
     LastGlobalUpdate() => u64,
+    SystemConfig() => SystemConfig,
+    /// When the system last entered `Phase::Crashed`
+    CrashedAt() => u64,
+    /// Phase `challenge_watchdog` replaced, restored once no watchdog
+    /// challenge remains open
+    PhaseBeforeWatchdogChallenge() => Phase,
+    /// Set when removing a misbehaving watchdog left fewer than
+    /// `MIN_WATCHDOGS`; new challenges are refused until the pool is refilled
+    WatchdogPoolDegraded() => bool,
+    /// Schema version of the stored state
+    StateVersion() => u32,
+    /// Snapshot taken by the last migration, until rolled back or expired
+    MigrationBackup() => MigrationBackup,
+    /// Set when a burst of verification mismatches halts result submission
+    SystemPaused() => bool,
+    /// Timestamps of verification mismatches inside the pause window
+    RecentMismatches() => Vec<u64>,
 
     /// Pools
     ExecutorPool() => ExecutorPool,
     WatchdogPool() => WatchdogPool,
+    /// Executor replacements in the order they happened
+    ReplacementLog(u64) => ReplacementRecord,
+    ReplacementCount() => u64,
 
     /// Operator and enclave data
     EnclaveType(Address) => EnclaveType,
     OperatorData(String) => Operator,
+    /// Ids of every operator registered at init, in registration order
+    OperatorIds() => Vec<String>,
     AttestationStatus(Address) => bool,
+    LastAttestationTime(Address) => u64,
+    ExecutorState(Address) => ExecutorStatus,
+    /// Executor registrations awaiting watchdog co-signs
+    PendingRegistration(Address) => PendingExecutor,
+    /// Deadline for executors to renew after a forced re-attestation
+    ReattestationDeadline() => u64,
     HeartbeatTimestamp(Address) => u64,
+    /// Block height of the last accepted heartbeat
+    LastHeartbeatBlock(Address) => u64,
+    /// Liveness misses recorded by `check_liveness`; survives re-registration
+    HeartbeatMisses(Address) => u64,
+    /// Heartbeat timestamp and the missed intervals already counted against it
+    CountedHeartbeatMisses(Address) => (u64, u64),
+    /// Consecutive on-time heartbeats since the last miss
+    HealthyHeartbeatStreak(Address) => u64,
+    /// Distinct timeout intervals with a heartbeat since the current reward
+    /// period started
+    PeriodHeartbeats(Address) => u64,
+    /// Reward period start and interval of the last heartbeat counted towards uptime
+    LastUptimeInterval(Address) => (u64, u64),
+    /// Timestamp at which the current reward period started
+    RewardPeriodStart() => u64,
 
     /// Contract management
     Contract(u128) => Contract,
@@ -26,9 +72,21 @@ state_schema! {
     Challenge(u128) => Challenge,
     ActiveChallenges() => Vec<u128>,
     ChallengeCount() => u128,
+    /// A watchdog's not-yet-revealed challenge commitments and when each was
+    /// made; expired entries are pruned on the watchdog's next commit
+    ChallengeCommitments(Address) => Vec<([u8; 32], u64)>,
+    /// Most recent challenge resolution against each address
+    LastChallengeOutcome(Address) => ChallengeResolved,
+    /// Set once a challenge's deadline has been extended
+    ChallengeDeadlineExtended(u128) => bool,
+    /// Partial proof recorded with a deadline extension, kept apart from
+    /// the witness proofs counted toward quorum
+    ChallengePartialProof(u128) => Vec<u8>,
 
     /// Verification and security
     OperatorHash() => Vec<u8>,
+    /// Head of the incremental transaction hash chain
+    IncrementalTxHash() => Vec<u8>,
     StateRoot() => Vec<u8>,
     VerificationProof(u128) => Vec<u8>,
 
@@ -36,19 +94,97 @@ state_schema! {
     TokenContract() => Address,
     GovernanceContract() => Address,
 
+    /// Token accounting
+    StakedAmount(Address) => u64,
+    TotalStaked() => u64,
+    /// Undistributed share of rewards held back by the contract
+    RewardReserve() => u64,
+    /// Rewards credited to each participant and not yet claimed
+    PendingReward(Address) => u64,
+    TotalPendingRewards() => u64,
+    /// Verified executions since rewards were last distributed automatically
+    VerifiedSinceDistribution() => u64,
+    /// Block height of the last automatic reward distribution
+    LastDistributionBlock() => u64,
+
      /// Enarx Keep identifiers
     KeepId(Address) => String,
     /// Drawbridge attestation tokens
     DrawbridgeToken(Address) => Vec<u8>,
+    /// SHA-256 of the Drawbridge token when only a commitment is kept on-chain
+    DrawbridgeTokenHash(Address) => [u8; 32],
+    /// Drawbridge tokens, by SHA-256, that may no longer be used to attest
+    RevokedDrawbridgeToken([u8; 32]) => bool,
 
-    /// Stores execution results for verification
-    ExecutionResult(u128) => ExecutionResult,
+    /// Last execution id handed out by `allocate_execution_id`
+    ExecutionCounter() => u128,
+    /// Result hash length an execution's submissions must have, if registered
+    ExpectedHashLength(u128) => usize,
+    /// Hash of the payload committed at allocation, if registered
+    ExecutionPayloadHash(u128) => Vec<u8>,
+    /// Block height at which each execution id was allocated
+    ExecutionAllocatedAt(u128) => u64,
+    /// Domain-specific governance contracts; domains without an entry fall
+    /// back to `GovernanceContract`
+    GovernanceContracts() => Vec<(GovernanceDomain, Address)>,
+    /// Last governance proposal id handed out
+    ProposalCounter() => u128,
+    /// Domain each open proposal was created under
+    ProposalDomain(u128) => GovernanceDomain,
+    /// Set when the two executors reported running different payloads
+    ExecutionInputMismatch(u128) => bool,
+    /// Set when matching results were attested further apart than `max_clock_skew`
+    ExecutionClockSkewed(u128) => bool,
+    /// End of the dispute window for an execution whose results matched
+    TentativeMatch(u128) => u64,
+    /// Challenges, one per executor, opened by a watchdog against a tentative match
+    ExecutionDispute(u128) => Vec<u128>,
+    /// Execution disputed by each dispute challenge, cleared once it resolves
+    DisputedExecution(u128) => u128,
+    /// Stores each enclave type's execution result for verification
+    ExecutionResult(u128, crate::types::EnclaveType) => ExecutionResult,
     /// Maps execution IDs to verification status
     ExecutionVerified(u128) => bool,
+    /// Verification vote cast by each watchdog on a challenge
+    ChallengeVote(u128, Address) => bool,
+    /// Approving and rejecting votes on a challenge
+    ChallengeVoteTally(u128) => (usize, usize),
+    /// Highest result-submission nonce accepted from each executor
+    ExecutorNonce(Address) => u64,
+    /// Result hash each executor submitted for an execution
+    SubmittedResult(u128, Address) => Vec<u8>,
     /// Tracks pending verifications
     PendingVerifications() => Vec<u128>,
-    /// Stores mismatched executions for analysis
+    /// Stores mismatched executions for analysis as `(SGX, SEV)`; result hashes
+    /// are stored encoded, use `load_execution_mismatch` to read them
     ExecutionMismatches(u128) => (ExecutionResult, ExecutionResult),
+    /// Tiebreaker votes for a mismatch that matched one side, as
+    /// `(tiebreaker, enclave type, result hash)`
+    TiebreakerVotes(u128) => Vec<(Address, crate::types::EnclaveType, Vec<u8>)>,
+    /// Challenges opened against both executors for a mismatch
+    MismatchChallenges(u128) => Vec<u128>,
+    /// Strikes of a mismatch that reached the removal threshold; the side
+    /// the tiebreak finds at fault is removed once it resolves
+    MismatchRemovalPending(u128) => u64,
+    /// Verified execution ids still held in state, oldest first
+    VerifiedExecutions() => Vec<u128>,
+    /// Block heights of recent verifications, for throughput queries
+    VerificationBlocks() => Vec<u64>,
+    /// Mismatches an executor has lost in a row; reset on a clean match
+    ConsecutiveMismatches(Address) => u64,
+    /// Unresolved verification mismatches since the executor's last clean match
+    MismatchStrikes(Address) => u64,
+    /// Cutoff block and list position a batched prune resumes from
+    PruneCursor() => (u64, usize),
+    /// Running digest over execution records removed by pruning
+    PrunedExecutionsDigest() => Vec<u8>,
+    /// Determinism probes and their per-executor outcomes
+    DeterminismProbe(u128) => DeterminismProbe,
+    ProbeCount() => u128,
+    /// Executors whose probe result diverged from the expected hash
+    DeterminismFlagged(Address) => bool,
+    /// Number of verified executions performed by each executor's keep
+    KeepExecutionCount(Address) => u64,
 
      /// Pool configuration
     PoolConfig() => EnarxConfig,
@@ -68,6 +204,13 @@ pub fn ensure_initialized(context: &mut wasmlanche::Context) {
     );
 }
 
+pub fn ensure_not_paused(context: &mut wasmlanche::Context) {
+    assert!(
+        !context.get(SystemPaused()).expect("state corrupt").unwrap_or(false),
+        "system paused"
+    );
+}
+
 pub fn ensure_phase(context: &mut wasmlanche::Context, expected_phase: Phase) {
     let current_phase = context
         .get(CurrentPhase())
@@ -81,8 +224,190 @@ pub fn ensure_phase(context: &mut wasmlanche::Context, expected_phase: Phase) {
     );
 }
 
-pub fn update_global_state(context: &mut wasmlanche::Context) {
+/// Current system configuration, falling back to defaults if unset
+pub fn load_system_config(context: &wasmlanche::Context) -> SystemConfig {
+    context
+        .get(SystemConfig())
+        .expect("state corrupt")
+        .unwrap_or_default()
+}
+
+/// Encodes a proof for storage according to the `compress_proofs` setting
+pub fn encode_proof(context: &wasmlanche::Context, proof: Vec<u8>) -> Vec<u8> {
+    compress_proof(&proof, load_system_config(context).compress_proofs)
+}
+
+/// Records a mismatch, always ordered SGX first regardless of argument order
+pub fn store_execution_mismatch(
+    context: &mut wasmlanche::Context,
+    execution_id: u128,
+    mut sgx: ExecutionResult,
+    mut sev: ExecutionResult,
+) {
+    if sgx.enclave_type != crate::types::EnclaveType::IntelSGX {
+        std::mem::swap(&mut sgx, &mut sev);
+    }
+    sgx.result_hash = encode_proof(context, sgx.result_hash);
+    sev.result_hash = encode_proof(context, sev.result_hash);
+
+    context
+        .store_by_key(ExecutionMismatches(execution_id), (sgx, sev))
+        .expect("failed to store mismatch");
+}
+
+pub fn load_execution_mismatch(
+    context: &wasmlanche::Context,
+    execution_id: u128,
+) -> Option<(ExecutionResult, ExecutionResult)> {
+    context
+        .get(ExecutionMismatches(execution_id))
+        .expect("state corrupt")
+        .map(|(mut sgx, mut sev)| {
+            sgx.result_hash = decompress_proof(&sgx.result_hash);
+            sev.result_hash = decompress_proof(&sev.result_hash);
+            (sgx, sev)
+        })
+}
+
+/// Stores a participant's Drawbridge token, or only its hash when
+/// `hash_drawbridge_tokens` is enabled. The other form is cleared so a token
+/// left over from before the flag changed can't shadow the new one.
+pub fn store_drawbridge_token(
+    context: &mut wasmlanche::Context,
+    address: Address,
+    drawbridge_token: Vec<u8>,
+) {
+    if load_system_config(context).hash_drawbridge_tokens {
+        context
+            .store_by_key(DrawbridgeTokenHash(address), drawbridge_token_hash(&drawbridge_token))
+            .expect("failed to store drawbridge token hash");
+        context.remove(DrawbridgeToken(address)).expect("failed to clear drawbridge token");
+    } else {
+        context
+            .store_by_key(DrawbridgeToken(address), drawbridge_token)
+            .expect("failed to store drawbridge token");
+        context.remove(DrawbridgeTokenHash(address)).expect("failed to clear drawbridge token hash");
+    }
+}
+
+/// Checks a presented token against whichever form is stored for the address
+pub fn drawbridge_token_matches(
+    context: &wasmlanche::Context,
+    address: Address,
+    drawbridge_token: &[u8],
+) -> bool {
+    if let Some(hash) = context.get(DrawbridgeTokenHash(address)).expect("state corrupt") {
+        return hash == drawbridge_token_hash(drawbridge_token);
+    }
+
+    context
+        .get(DrawbridgeToken(address))
+        .expect("state corrupt")
+        .map_or(false, |stored| stored == drawbridge_token)
+}
+
+/// Addresses of all registered executors and watchdogs
+pub fn participant_addresses(context: &mut wasmlanche::Context) -> Vec<Address> {
+    let executor_pool = context
+        .get(ExecutorPool())
+        .expect("state corrupt")
+        .expect("executor pool not initialized");
+
+    let watchdog_pool = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized");
+
+    executor_pool
+        .sgx_executor
+        .into_iter()
+        .chain(executor_pool.sev_executor)
+        .chain(watchdog_pool.watchdogs.iter().map(|(addr, _)| *addr))
+        .collect()
+}
+
+/// Clears per-address registration state so the address can later re-register cleanly
+pub fn clear_participant_state(context: &mut wasmlanche::Context, address: Address) {
+    context.remove(EnclaveType(address)).expect("failed to clear enclave type");
+    context.remove(KeepId(address)).expect("failed to clear keep id");
+    context.remove(DrawbridgeToken(address)).expect("failed to clear drawbridge token");
+    context.remove(DrawbridgeTokenHash(address)).expect("failed to clear drawbridge token hash");
+    context.remove(AttestationStatus(address)).expect("failed to clear attestation status");
+    context.remove(ExecutorState(address)).expect("failed to clear executor state");
+    context.remove(HeartbeatTimestamp(address)).expect("failed to clear heartbeat");
+    context.remove(KeepHealth(address)).expect("failed to clear keep health");
+    context.remove(KeepMeasurement(address)).expect("failed to clear keep measurement");
+    context.remove(ChallengeCommitments(address)).expect("failed to clear challenge commitments");
+}
+
+/// Tracked challenges still awaiting a response or a verdict
+pub fn unresolved_challenges(context: &wasmlanche::Context) -> Vec<crate::types::Challenge> {
+    context
+        .get(ActiveChallenges())
+        .expect("state corrupt")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|id| context.get(Challenge(*id)).expect("state corrupt"))
+        .filter(|challenge| {
+            matches!(challenge.status, ChallengeStatus::Pending | ChallengeStatus::Responded)
+        })
+        .collect()
+}
+
+/// Moves the system to `Phase::Crashed` and records when it happened
+pub fn enter_crashed_phase(context: &mut wasmlanche::Context) {
+    context
+        .store((
+            (CurrentPhase(), Phase::Crashed),
+            (CrashedAt(), context.timestamp()),
+        ))
+        .expect("failed to update phase");
+}
+
+/// Records a lost challenge in the executor's on-chain status
+pub fn mark_executor_failed(context: &mut wasmlanche::Context, executor: Address) {
+    if context.get(ExecutorState(executor)).expect("state corrupt").is_some() {
+        context
+            .store_by_key(ExecutorState(executor), ExecutorStatus::Failed)
+            .expect("failed to update executor state");
+    }
+}
+
+/// Rejects oversized attestation reports before any verification work is done
+pub fn ensure_attestation_report_size(context: &wasmlanche::Context, attestation_report: &[u8]) {
+    assert!(
+        attestation_report.len() <= load_system_config(context).max_attestation_report_len,
+        "attestation report too large"
+    );
+}
+
+pub fn ensure_governance(context: &mut wasmlanche::Context) {
+    let governance_address = context
+        .get(GovernanceContract())
+        .expect("state corrupt")
+        .expect("governance contract not initialized");
+    assert!(context.actor() == governance_address, "unauthorized governance call");
+}
+
+/// Records a significant state change: bumps the global update time and
+/// advances the incremental transaction hash chain with a link covering the
+/// acting address, the operation, its arguments and the current state root
+pub fn update_global_state(context: &mut wasmlanche::Context, selector: &str, args: &[&[u8]]) {
+    let previous_hash = context
+        .get(IncrementalTxHash())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    let state_root = context
+        .get(StateRoot())
+        .expect("state corrupt")
+        .unwrap_or_default();
+    let link = encode_tx_link(&context.actor().to_string(), selector, args, &state_root);
+    let tx_hash = hash_incremental(previous_hash, &link);
+
     context
-        .store_by_key(LastGlobalUpdate(), context.timestamp())
+        .store((
+            (LastGlobalUpdate(), context.timestamp()),
+            (IncrementalTxHash(), tx_hash),
+        ))
         .expect("failed to update global state");
 }
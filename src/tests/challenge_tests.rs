@@ -0,0 +1,1424 @@
+use super::common::*;
+use crate::{types::*, state::*, error::Error, MAX_ACTIVE_CHALLENGES};
+
+fn proof_signed_by(challenge_id: u128, witnesses: &[Address]) -> ChallengeProof {
+    ChallengeProof {
+        challenge_id,
+        proof_data: vec![1u8; 32],
+        timestamp: 0,
+        witness_signatures: witnesses
+            .iter()
+            .map(|witness| (*witness, vec![0u8; 64]))
+            .collect(),
+        drawbridge_token: Vec::new(),
+    }
+}
+
+fn execution_evidence() -> ChallengeEvidence {
+    ChallengeEvidence::ExecutionEvidence {
+        result_hash: vec![1u8; 32],
+        execution_proof: vec![2u8; 64],
+        keep_measurement: vec![3u8; 32],
+    }
+}
+
+mod challenge_evidence {
+    use super::*;
+
+    #[test]
+    fn test_well_formed_execution_challenge() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        )
+        .expect("well-formed evidence should be accepted");
+
+        assert_eq!(challenge.challenge_type, ChallengeType::Execution);
+        assert_eq!(challenge.status, ChallengeStatus::Pending);
+    }
+
+    #[test]
+    fn test_empty_execution_proof_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            ChallengeEvidence::ExecutionEvidence {
+                result_hash: Vec::new(),
+                execution_proof: Vec::new(),
+                keep_measurement: vec![3u8; 32],
+            },
+        );
+
+        assert!(matches!(result, Err(Error::ChallengeError(_))));
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_evidence_type_mismatch_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Attestation,
+            execution_evidence(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "evidence does not match challenge type"
+        ));
+    }
+}
+
+mod challenge_deadline_extension {
+    use super::*;
+    use crate::CHALLENGE_DEADLINE_EXTENSION;
+
+    fn open_challenge(context: &mut TestContext) -> (Address, u128) {
+        let (sgx_executor, _, watchdogs) = setup_full_system(context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            context,
+            sgx_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        )
+        .unwrap();
+
+        (sgx_executor, challenge.id)
+    }
+
+    #[test]
+    fn test_single_extension_granted() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = open_challenge(&mut context);
+
+        let original = context.get(Challenge(challenge_id)).unwrap().unwrap();
+
+        context.set_caller(sgx_executor);
+        let new_deadline = extend_challenge_deadline(&mut context, challenge_id, vec![1u8; 16]);
+
+        let challenge = context.get(Challenge(challenge_id)).unwrap().unwrap();
+        assert_eq!(new_deadline, original.response_deadline + CHALLENGE_DEADLINE_EXTENSION);
+        assert_eq!(challenge.response_deadline, new_deadline);
+        assert_eq!(challenge.verification_proofs, original.verification_proofs);
+        assert!(context.get(ChallengePartialProof(challenge_id)).unwrap().is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "deadline already extended")]
+    fn test_second_extension_rejected() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = open_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        extend_challenge_deadline(&mut context, challenge_id, vec![1u8; 16]);
+        extend_challenge_deadline(&mut context, challenge_id, vec![2u8; 16]);
+    }
+}
+
+mod health_degradation {
+    use super::*;
+
+    fn open_health_challenge(context: &mut TestContext) -> (Address, u128) {
+        let (sgx_executor, _, watchdogs) = setup_full_system(context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            context,
+            sgx_executor,
+            ChallengeType::HealthDegradation,
+            ChallengeEvidence::HealthEvidence {
+                memory_usage: MemoryStats { used: 95, total: 100 },
+                failed_self_checks: 0,
+            },
+        )
+        .unwrap();
+
+        (sgx_executor, challenge.id)
+    }
+
+    #[test]
+    fn test_executor_proves_health() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = open_health_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        assert!(respond_with_attested_health(
+            &mut context,
+            challenge_id,
+            SGX_OPERATOR,
+            EnclaveType::IntelSGX,
+            MemoryStats { used: 40, total: 100 },
+            0,
+        ));
+
+        let challenge = context.get(Challenge(challenge_id)).unwrap().unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Verified);
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, Some(sgx_executor));
+    }
+
+    #[test]
+    fn test_degraded_executor_removed() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = open_health_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        assert!(!respond_with_attested_health(
+            &mut context,
+            challenge_id,
+            SGX_OPERATOR,
+            EnclaveType::IntelSGX,
+            MemoryStats { used: 40, total: 100 },
+            2,
+        ));
+
+        let challenge = context.get(Challenge(challenge_id)).unwrap().unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Failed);
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "health metrics not attested")]
+    fn test_self_reported_metrics_rejected() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = open_health_challenge(&mut context);
+
+        // The quote attests a degraded keep, but the response claims health
+        let report = health_quote(
+            EnclaveType::IntelSGX,
+            SGX_OPERATOR,
+            challenge_id,
+            &MemoryStats { used: 95, total: 100 },
+            0,
+        );
+        context.set_caller(sgx_executor);
+        respond_to_health_challenge(
+            &mut context,
+            challenge_id,
+            MemoryStats { used: 40, total: 100 },
+            0,
+            report,
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid drawbridge token")]
+    fn test_health_response_requires_keep_token() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = open_health_challenge(&mut context);
+
+        let memory_usage = MemoryStats { used: 40, total: 100 };
+        let report = health_quote(EnclaveType::IntelSGX, SGX_OPERATOR, challenge_id, &memory_usage, 0);
+        context.set_caller(sgx_executor);
+        respond_to_health_challenge(
+            &mut context,
+            challenge_id,
+            memory_usage,
+            0,
+            report,
+            drawbridge_token_for("other-keep"),
+        );
+    }
+
+    #[test]
+    fn test_large_memory_stats_do_not_overflow() {
+        let healthy = MemoryStats { used: usize::MAX / 2, total: usize::MAX };
+        let degraded = MemoryStats { used: usize::MAX, total: usize::MAX };
+
+        assert!(!is_health_degraded(&healthy, 0));
+        assert!(is_health_degraded(&degraded, 0));
+    }
+
+    #[test]
+    fn test_health_challenge_uses_configured_response_window() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::ChallengeResponseWindow(250));
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::HealthDegradation,
+            ChallengeEvidence::HealthEvidence {
+                memory_usage: MemoryStats { used: 95, total: 100 },
+                failed_self_checks: 2,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(challenge.response_deadline, context.timestamp() + 250);
+        assert!(challenge.verification_proofs.is_empty());
+        let mut expected_data = 95u64.to_le_bytes().to_vec();
+        expected_data.extend_from_slice(&100u64.to_le_bytes());
+        expected_data.extend_from_slice(&2u32.to_le_bytes());
+        assert_eq!(challenge.challenge_data, expected_data);
+        let stored = context.get(Challenge(challenge.id)).unwrap().unwrap();
+        assert_eq!(stored.response_deadline, challenge.response_deadline);
+    }
+
+    #[test]
+    fn test_healthy_evidence_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::HealthDegradation,
+            ChallengeEvidence::HealthEvidence {
+                memory_usage: MemoryStats { used: 10, total: 100 },
+                failed_self_checks: 0,
+            },
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "health evidence does not show degradation"
+        ));
+    }
+}
+
+mod challenge_notifications {
+    use super::*;
+
+    fn resolve_health_challenge(context: &mut TestContext, failed_self_checks: u32) -> (Address, u128) {
+        let (sgx_executor, _, watchdogs) = setup_full_system(context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            context,
+            sgx_executor,
+            ChallengeType::HealthDegradation,
+            ChallengeEvidence::HealthEvidence {
+                memory_usage: MemoryStats { used: 95, total: 100 },
+                failed_self_checks: 0,
+            },
+        )
+        .unwrap();
+
+        context.set_caller(sgx_executor);
+        respond_with_attested_health(
+            context,
+            challenge.id,
+            SGX_OPERATOR,
+            EnclaveType::IntelSGX,
+            MemoryStats { used: 40, total: 100 },
+            failed_self_checks,
+        );
+
+        (sgx_executor, challenge.id)
+    }
+
+    fn assert_resolved(context: &mut TestContext, expected: ChallengeResolved) {
+        assert_eq!(
+            get_last_challenge_outcome(context, expected.challenged),
+            Some(expected.clone())
+        );
+        assert!(context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "ChallengeResolved"));
+    }
+
+    #[test]
+    fn test_verified_challenge_notifies_executor() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = resolve_health_challenge(&mut context, 0);
+
+        assert_resolved(&mut context, ChallengeResolved {
+            challenge_id,
+            challenged: sgx_executor,
+            challenge_type: ChallengeType::HealthDegradation,
+            outcome: ChallengeStatus::Verified,
+        });
+    }
+
+    #[test]
+    fn test_failed_challenge_notifies_executor() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = resolve_health_challenge(&mut context, 1);
+
+        assert_resolved(&mut context, ChallengeResolved {
+            challenge_id,
+            challenged: sgx_executor,
+            challenge_type: ChallengeType::HealthDegradation,
+            outcome: ChallengeStatus::Failed,
+        });
+    }
+
+    #[test]
+    fn test_no_outcome_before_resolution() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_full_system(&mut context);
+
+        assert_eq!(get_last_challenge_outcome(&mut context, sgx_executor), None);
+    }
+}
+
+mod proof_compression {
+    use super::*;
+
+    fn enable_compression(context: &mut TestContext) {
+        context.set_caller(governance_address());
+        update_system_config(context, ConfigUpdate::CompressProofs(true));
+    }
+
+    #[test]
+    fn test_compressible_proof_round_trip() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        enable_compression(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        )
+        .unwrap();
+
+        let proof = vec![7u8; 1024];
+        context.set_caller(sgx_executor);
+        extend_challenge_deadline(&mut context, challenge.id, proof.clone());
+
+        let stored = context.get(Challenge(challenge.id)).unwrap().unwrap();
+        assert!(stored.verification_proofs.last().unwrap().len() < proof.len());
+
+        let proofs = get_verification_proofs(&mut context, challenge.id);
+        assert_eq!(proofs.last().unwrap(), &proof);
+    }
+
+    #[test]
+    fn test_incompressible_proof_round_trip() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        enable_compression(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        )
+        .unwrap();
+
+        let proof: Vec<u8> = (0..=255u8).collect();
+        context.set_caller(sgx_executor);
+        extend_challenge_deadline(&mut context, challenge.id, proof.clone());
+
+        let proofs = get_verification_proofs(&mut context, challenge.id);
+        assert_eq!(proofs.last().unwrap(), &proof);
+    }
+
+    #[test]
+    fn test_compressed_mismatch_round_trip() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        enable_compression(&mut context);
+
+        let sgx_hash = vec![1u8; 512];
+        let sev_hash = vec![2u8; 512];
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, sgx_hash.clone(), payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, sev_hash.clone(), payload_hash(), 1);
+
+        let (stored_sgx, _) = context.get(ExecutionMismatches(1u128)).unwrap().unwrap();
+        assert!(stored_sgx.result_hash.len() < sgx_hash.len());
+
+        let (sgx, sev) = get_verification_mismatch(&mut context, 1u128).unwrap();
+        assert_eq!(sgx.result_hash, sgx_hash);
+        assert_eq!(sev.result_hash, sev_hash);
+    }
+}
+
+mod commit_reveal {
+    use super::*;
+    use crate::CHALLENGE_REVEAL_WINDOW;
+
+    #[test]
+    fn test_valid_commit_reveal_round() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        let evidence = vec![1u8; 32];
+        let salt = vec![7u8; 16];
+
+        context.set_caller(watchdogs[0]);
+        let commitment = challenge_commitment(sgx_executor, &ChallengeType::Execution, &evidence, &salt);
+        commit_challenge(&mut context, commitment).unwrap();
+
+        // Nothing is opened until the reveal
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+
+        context.set_timestamp(context.timestamp() + CHALLENGE_REVEAL_WINDOW);
+        let challenge = reveal_challenge(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            evidence.clone(),
+            salt,
+        )
+        .unwrap();
+
+        assert_eq!(challenge.status, ChallengeStatus::Pending);
+        assert_eq!(challenge.challenged, sgx_executor);
+        assert_eq!(challenge.challenge_data, evidence);
+    }
+
+    #[test]
+    fn test_reveal_with_different_evidence_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        let salt = vec![7u8; 16];
+
+        context.set_caller(watchdogs[0]);
+        let commitment = challenge_commitment(sgx_executor, &ChallengeType::Execution, &[1u8; 32], &salt);
+        commit_challenge(&mut context, commitment).unwrap();
+
+        let result = reveal_challenge(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            vec![2u8; 32],
+            salt,
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "evidence does not match commitment"
+        ));
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_reveal_after_window_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        let evidence = vec![1u8; 32];
+        let salt = vec![7u8; 16];
+
+        context.set_caller(watchdogs[0]);
+        let commitment = challenge_commitment(sgx_executor, &ChallengeType::Execution, &evidence, &salt);
+        commit_challenge(&mut context, commitment).unwrap();
+
+        context.set_timestamp(context.timestamp() + CHALLENGE_REVEAL_WINDOW + 1);
+        let result = reveal_challenge(&mut context, sgx_executor, ChallengeType::Execution, evidence, salt);
+
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "reveal window elapsed"
+        ));
+        assert_eq!(context.get(ChallengeCommitments(watchdogs[0])).unwrap(), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_expired_commitments_pruned_on_commit() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        let salt = vec![7u8; 16];
+
+        context.set_caller(watchdogs[0]);
+        let stale = challenge_commitment(sgx_executor, &ChallengeType::Execution, &[1u8; 32], &salt);
+        commit_challenge(&mut context, stale).unwrap();
+
+        context.set_timestamp(context.timestamp() + CHALLENGE_REVEAL_WINDOW + 1);
+        let fresh = challenge_commitment(sgx_executor, &ChallengeType::Execution, &[2u8; 32], &salt);
+        commit_challenge(&mut context, fresh).unwrap();
+
+        let commitments = context.get(ChallengeCommitments(watchdogs[0])).unwrap().unwrap();
+        assert_eq!(commitments, vec![(fresh, context.timestamp())]);
+    }
+
+    #[test]
+    fn test_pending_commitments_bounded() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        for i in 0..MAX_ACTIVE_CHALLENGES as u8 {
+            let commitment = challenge_commitment(sgx_executor, &ChallengeType::Execution, &[i; 32], &[i]);
+            commit_challenge(&mut context, commitment).unwrap();
+        }
+
+        let commitment = challenge_commitment(sgx_executor, &ChallengeType::Execution, &[0xff; 32], &[0xff]);
+        assert!(matches!(
+            commit_challenge(&mut context, commitment),
+            Err(Error::ChallengeError(msg)) if msg == "too many pending commitments"
+        ));
+    }
+
+    #[test]
+    fn test_commitment_fields_are_delimited() {
+        let executor = Address::from([3u8; 32]);
+
+        assert_ne!(
+            challenge_commitment(executor, &ChallengeType::Execution, &[1, 2], &[3]),
+            challenge_commitment(executor, &ChallengeType::Execution, &[1], &[2, 3]),
+        );
+    }
+}
+
+mod batch_challenges {
+    use super::*;
+
+    #[test]
+    fn test_batch_opens_challenges_against_both_executors() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenges = challenge_executors_batch(
+            &mut context,
+            vec![
+                (sgx_executor, ChallengeType::Execution, vec![1u8; 32]),
+                (sev_executor, ChallengeType::Execution, vec![1u8; 32]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[1].id, challenges[0].id + 1);
+
+        for (challenge, executor) in challenges.iter().zip([sgx_executor, sev_executor]) {
+            let stored = context.get(Challenge(challenge.id)).unwrap().unwrap();
+            assert_eq!(stored.status, ChallengeStatus::Pending);
+            assert_eq!(stored.challenged, executor);
+        }
+    }
+
+    #[test]
+    fn test_batch_is_all_or_nothing() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executors_batch(
+            &mut context,
+            vec![
+                (sgx_executor, ChallengeType::Execution, vec![1u8; 32]),
+                (sev_executor, ChallengeType::Execution, Vec::new()),
+            ],
+        );
+
+        assert!(matches!(result, Err(Error::ChallengeError(_))));
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+    }
+}
+
+mod challenge_time_remaining {
+    use super::*;
+    use crate::CHALLENGE_RESPONSE_WINDOW;
+
+    fn open_challenge(context: &mut TestContext) -> (Address, u128) {
+        let (sgx_executor, _, watchdogs) = setup_full_system(context);
+
+        context.set_caller(watchdogs[0]);
+        let challenges = challenge_executors_batch(
+            context,
+            vec![(sgx_executor, ChallengeType::HealthDegradation, vec![1u8; 32])],
+        )
+        .unwrap();
+
+        (sgx_executor, challenges[0].id)
+    }
+
+    #[test]
+    fn test_fresh_challenge_has_time_remaining() {
+        let mut context = setup();
+        let (_, challenge_id) = open_challenge(&mut context);
+
+        assert_eq!(
+            get_challenge_time_remaining(&mut context, challenge_id),
+            Some(CHALLENGE_RESPONSE_WINDOW)
+        );
+    }
+
+    #[test]
+    fn test_past_deadline_saturates_to_zero() {
+        let mut context = setup();
+        let (_, challenge_id) = open_challenge(&mut context);
+
+        context.set_timestamp(context.timestamp() + CHALLENGE_RESPONSE_WINDOW * 2);
+        assert_eq!(get_challenge_time_remaining(&mut context, challenge_id), Some(0));
+    }
+
+    #[test]
+    fn test_resolved_challenge_has_no_deadline() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id) = open_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        respond_with_attested_health(
+            &mut context,
+            challenge_id,
+            SGX_OPERATOR,
+            EnclaveType::IntelSGX,
+            MemoryStats { used: 40, total: 100 },
+            0,
+        );
+
+        assert_eq!(get_challenge_time_remaining(&mut context, challenge_id), None);
+    }
+}
+
+mod response_window_config {
+    use super::*;
+
+    #[test]
+    fn test_response_window_update_applies_to_new_challenges() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::ChallengeResponseWindow(250));
+
+        context.set_caller(watchdogs[0]);
+        let challenges = challenge_executors_batch(
+            &mut context,
+            vec![(sgx_executor, ChallengeType::Execution, vec![1u8; 32])],
+        )
+        .unwrap();
+
+        assert_eq!(challenges[0].response_deadline, context.timestamp() + 250);
+    }
+
+    #[test]
+    #[should_panic(expected = "response window must be positive")]
+    fn test_zero_response_window_rejected() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::ChallengeResponseWindow(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid quorum")]
+    fn test_zero_quorum_denominator_rejected() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::Quorum { numerator: 0, denominator: 0 });
+    }
+}
+
+mod self_challenges {
+    use super::*;
+
+    #[test]
+    fn test_self_challenge_rejected() {
+        let mut context = setup();
+        let (_, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executor(
+            &mut context,
+            watchdogs[0],
+            ChallengeType::Execution,
+            execution_evidence(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "cannot self-challenge"
+        ));
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_self_targeted_batch_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executors_batch(
+            &mut context,
+            vec![
+                (sgx_executor, ChallengeType::Execution, vec![1u8; 32]),
+                (watchdogs[0], ChallengeType::Execution, vec![1u8; 32]),
+            ],
+        );
+
+        assert!(matches!(result, Err(Error::ChallengeError(_))));
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+    }
+}
+
+mod challenge_targets {
+    use super::*;
+
+    #[test]
+    fn test_current_executor_target_accepted() {
+        let mut context = setup();
+        let (_, sev_executor, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            &mut context,
+            sev_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        )
+        .expect("current executor should be challengeable");
+
+        assert_eq!(challenge.challenged, sev_executor);
+    }
+
+    #[test]
+    fn test_unknown_address_rejected() {
+        let mut context = setup();
+        let (_, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executor(
+            &mut context,
+            Address::from([42u8; 32]),
+            ChallengeType::Execution,
+            execution_evidence(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "target not an executor"
+        ));
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn test_batch_with_unknown_target_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = challenge_executors_batch(
+            &mut context,
+            vec![
+                (sgx_executor, ChallengeType::Execution, vec![1u8; 32]),
+                (Address::from([42u8; 32]), ChallengeType::Execution, vec![1u8; 32]),
+            ],
+        );
+
+        assert!(matches!(result, Err(Error::ChallengeError(_))));
+        assert_eq!(context.get(ChallengeCount()).unwrap().unwrap_or(0), 0);
+    }
+}
+
+mod challenge_limits {
+    use super::*;
+
+    #[test]
+    fn test_active_challenge_limit() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        for _ in 0..MAX_ACTIVE_CHALLENGES {
+            challenge_executor(
+                &mut context,
+                sgx_executor,
+                ChallengeType::Execution,
+                execution_evidence(),
+            )
+            .expect("challenge within limit should open");
+        }
+
+        let result = challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "too many active challenges"
+        ));
+    }
+
+    #[test]
+    fn test_configured_challenge_limit() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MaxActiveChallenges(1));
+
+        context.set_caller(watchdogs[0]);
+        challenge_executor(
+            &mut context,
+            sgx_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        )
+        .expect("first challenge should open");
+
+        let result = challenge_executor(
+            &mut context,
+            sev_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        );
+        assert!(matches!(result, Err(Error::ChallengeError(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_challenge_limit_requires_governance() {
+        let mut context = setup();
+        let (_, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        update_system_config(&mut context, ConfigUpdate::MaxActiveChallenges(1));
+    }
+}
+
+mod watchdog_challenges {
+    use super::*;
+    use crate::MIN_WATCHDOGS;
+
+    #[test]
+    fn test_misbehaving_watchdog_removed() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        register_watchdog_keep(
+            &mut context,
+            Address::from([8u8; 32]),
+            EnclaveType::AMDSEV,
+            "watchdog-keep",
+        );
+
+        context.set_caller(sgx_executor);
+        let challenge = challenge_watchdog(&mut context, watchdogs[0], vec![1u8; 32]).unwrap();
+        assert_eq!(get_current_phase(&mut context), Phase::ChallengeWatchdog);
+
+        context.set_caller(governance_address());
+        resolve_watchdog_challenge(&mut context, challenge.id, true);
+
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+        let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert!(!watchdog_pool.watchdogs.iter().any(|(addr, _)| *addr == watchdogs[0]));
+        assert_eq!(
+            context.get(Challenge(challenge.id)).unwrap().unwrap().status,
+            ChallengeStatus::Failed
+        );
+    }
+
+    #[test]
+    fn test_cleared_watchdog_kept() {
+        let mut context = setup();
+        let (_, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[1]);
+        let challenge = challenge_watchdog(&mut context, watchdogs[0], vec![1u8; 32]).unwrap();
+
+        context.set_caller(governance_address());
+        resolve_watchdog_challenge(&mut context, challenge.id, false);
+
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+        let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert!(watchdog_pool.watchdogs.iter().any(|(addr, _)| *addr == watchdogs[0]));
+    }
+
+    #[test]
+    fn test_removal_below_minimum_degrades_pool() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        stake_tokens(&mut context, 1_000);
+        let reserve_before = get_reward_reserve(&mut context);
+
+        context.set_caller(sgx_executor);
+        let challenge = challenge_watchdog(&mut context, watchdogs[0], vec![1u8; 32]).unwrap();
+
+        context.set_caller(governance_address());
+        resolve_watchdog_challenge(&mut context, challenge.id, true);
+
+        // The verdict stands even though the pool drops below the minimum
+        assert_eq!(
+            context.get(Challenge(challenge.id)).unwrap().unwrap().status,
+            ChallengeStatus::Failed
+        );
+        assert_eq!(get_staked_amount(&mut context, watchdogs[0]), 0);
+        assert_eq!(get_reward_reserve(&mut context), reserve_before + 1_000);
+        let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert_eq!(watchdog_pool.watchdogs.len(), MIN_WATCHDOGS - 1);
+        assert_eq!(context.get(WatchdogPoolDegraded()).unwrap(), Some(true));
+
+        // No new challenge can be opened until the pool is refilled
+        context.set_caller(watchdogs[1]);
+        let result = challenge_executors_batch(
+            &mut context,
+            vec![(sgx_executor, ChallengeType::Execution, vec![1u8; 32])],
+        );
+        assert!(matches!(result, Err(Error::ChallengeError(msg)) if msg == "watchdog pool degraded"));
+
+        register_watchdog_keep(
+            &mut context,
+            Address::from([8u8; 32]),
+            EnclaveType::AMDSEV,
+            "watchdog-keep",
+        );
+        assert!(context.get(WatchdogPoolDegraded()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_phase_entered_during_challenge_kept() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        let challenge = challenge_watchdog(&mut context, watchdogs[0], vec![1u8; 32]).unwrap();
+        context.store_by_key(CurrentPhase(), Phase::ChallengeExecutor).unwrap();
+
+        context.set_caller(governance_address());
+        resolve_watchdog_challenge(&mut context, challenge.id, false);
+
+        assert_eq!(context.get(CurrentPhase()).unwrap(), Some(Phase::ChallengeExecutor));
+        assert!(context.get(PhaseBeforeWatchdogChallenge()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_outsider_cannot_challenge_watchdog() {
+        let mut context = setup();
+        let (_, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(Address::from([30u8; 32]));
+        let result = challenge_watchdog(&mut context, watchdogs[0], vec![1u8; 32]);
+
+        assert!(matches!(result, Err(Error::Unauthorized(_))));
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+    }
+}
+
+mod challenge_quorum {
+    use super::*;
+
+    #[test]
+    fn test_required_verifications_match_verification_path() {
+        let mut context = setup();
+        let (sgx_executor, _, mut watchdogs) = setup_full_system(&mut context);
+
+        for i in 8..10u8 {
+            let watchdog = Address::from([i; 32]);
+            register_watchdog_keep(
+                &mut context,
+                watchdog,
+                EnclaveType::IntelSGX,
+                "watchdog-keep",
+            );
+            watchdogs.push(watchdog);
+        }
+        assert_eq!(watchdogs.len(), 5);
+
+        let required = get_required_verifications(&mut context);
+        assert_eq!(required, 4);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executors_batch(
+            &mut context,
+            vec![(sgx_executor, ChallengeType::Execution, vec![1u8; 32])],
+        )
+        .unwrap()
+        .remove(0);
+
+        context.set_caller(sgx_executor);
+        respond_to_challenge(
+            &mut context,
+            challenge.id,
+            vec![1u8; 16],
+            proof_signed_by(challenge.id, &watchdogs[..3]),
+        );
+
+        for watchdog in &watchdogs[..required - 1] {
+            context.set_caller(*watchdog);
+            verify_challenge_response(&mut context, challenge.id, true, vec![1u8; 32]);
+        }
+        assert_eq!(
+            context.get(Challenge(challenge.id)).unwrap().unwrap().status,
+            ChallengeStatus::Responded
+        );
+
+        context.set_caller(watchdogs[required - 1]);
+        verify_challenge_response(&mut context, challenge.id, true, vec![1u8; 32]);
+        assert_eq!(
+            context.get(Challenge(challenge.id)).unwrap().unwrap().status,
+            ChallengeStatus::Verified
+        );
+    }
+}
+
+mod quorum_reachability {
+    use super::*;
+
+    #[test]
+    fn test_diverse_pool_can_reach_quorum() {
+        let mut context = setup();
+        setup_full_system(&mut context);
+
+        assert!(can_reach_quorum(&mut context));
+    }
+
+    #[test]
+    fn test_single_watchdog_cannot_reach_quorum() {
+        let mut context = setup();
+        register_watchdog_keep(
+            &mut context,
+            Address::from([5u8; 32]),
+            EnclaveType::IntelSGX,
+            "watchdog-keep",
+        );
+
+        assert!(!can_reach_quorum(&mut context));
+    }
+
+    #[test]
+    fn test_single_type_pool_needs_diversity_disabled() {
+        let mut context = setup();
+
+        for i in 8..11u8 {
+            register_watchdog_keep(
+                &mut context,
+                Address::from([i; 32]),
+                EnclaveType::IntelSGX,
+                "watchdog-keep",
+            );
+        }
+        assert!(!can_reach_quorum(&mut context));
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::RequireEnclaveDiversity(false));
+        assert!(can_reach_quorum(&mut context));
+    }
+}
+
+mod vote_ties {
+    use super::*;
+
+    /// Four watchdogs with a quorum of all four, and a responded challenge
+    fn setup_tied_vote(context: &mut TestContext, policy: VoteTiePolicy) -> (u128, Vec<Address>) {
+        let (sgx_executor, _, mut watchdogs) = setup_full_system(context);
+
+        let watchdog = Address::from([8u8; 32]);
+        register_watchdog_keep(context, watchdog, EnclaveType::AMDSEV, "watchdog-keep");
+        watchdogs.push(watchdog);
+
+        context.set_caller(governance_address());
+        update_system_config(context, ConfigUpdate::Quorum { numerator: 3, denominator: 4 });
+        update_system_config(context, ConfigUpdate::VoteTiePolicy(policy));
+        assert_eq!(get_required_verifications(context), 4);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executors_batch(
+            context,
+            vec![(sgx_executor, ChallengeType::Execution, vec![1u8; 32])],
+        )
+        .unwrap()
+        .remove(0);
+
+        context.set_caller(sgx_executor);
+        respond_to_challenge(
+            context,
+            challenge.id,
+            vec![1u8; 16],
+            proof_signed_by(challenge.id, &watchdogs[..3]),
+        );
+
+        (challenge.id, watchdogs)
+    }
+
+    fn cast_votes(context: &mut TestContext, challenge_id: u128, watchdogs: &[Address], votes: [bool; 4]) {
+        for (watchdog, vote) in watchdogs.iter().zip(votes) {
+            context.set_caller(*watchdog);
+            verify_challenge_response(context, challenge_id, vote, vec![1u8; 32]);
+        }
+    }
+
+    #[test]
+    fn test_tie_fails_closed() {
+        let mut context = setup();
+        let (challenge_id, watchdogs) = setup_tied_vote(&mut context, VoteTiePolicy::FailClosed);
+
+        // The last two votes approve; the tie must still fail
+        cast_votes(&mut context, challenge_id, &watchdogs, [false, false, true, true]);
+
+        assert_eq!(
+            context.get(Challenge(challenge_id)).unwrap().unwrap().status,
+            ChallengeStatus::Failed
+        );
+    }
+
+    #[test]
+    fn test_tie_fails_open() {
+        let mut context = setup();
+        let (challenge_id, watchdogs) = setup_tied_vote(&mut context, VoteTiePolicy::FailOpen);
+
+        // The last two votes reject; the tie must still verify
+        cast_votes(&mut context, challenge_id, &watchdogs, [true, true, false, false]);
+
+        assert_eq!(
+            context.get(Challenge(challenge_id)).unwrap().unwrap().status,
+            ChallengeStatus::Verified
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "watchdog already voted")]
+    fn test_duplicate_vote_rejected() {
+        let mut context = setup();
+        let (challenge_id, watchdogs) = setup_tied_vote(&mut context, VoteTiePolicy::FailClosed);
+
+        context.set_caller(watchdogs[0]);
+        verify_challenge_response(&mut context, challenge_id, true, vec![1u8; 32]);
+        verify_challenge_response(&mut context, challenge_id, true, vec![1u8; 32]);
+    }
+}
+
+mod active_challenge_rows {
+    use super::*;
+
+    #[test]
+    fn test_rows_for_each_active_challenge() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        challenge_executors_batch(
+            &mut context,
+            vec![
+                (sgx_executor, ChallengeType::Execution, vec![1u8; 32]),
+                (sev_executor, ChallengeType::HealthDegradation, vec![1u8; 32]),
+            ],
+        )
+        .unwrap();
+
+        context.set_caller(sgx_executor);
+        challenge_watchdog(&mut context, watchdogs[1], vec![1u8; 32]).unwrap();
+
+        let rows = get_active_challenges(&mut context, 0, 10);
+        assert_eq!(
+            rows,
+            vec![
+                (1, ChallengeType::Execution, ChallengeStatus::Pending, sgx_executor),
+                (2, ChallengeType::HealthDegradation, ChallengeStatus::Pending, sev_executor),
+                (3, ChallengeType::WatchdogMisbehavior, ChallengeStatus::Pending, watchdogs[1]),
+            ]
+        );
+
+        assert_eq!(get_active_challenges(&mut context, 2, 10).len(), 1);
+        assert_eq!(get_active_challenges(&mut context, 0, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_resolved_challenges_omitted() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        let challenge = challenge_watchdog(&mut context, watchdogs[1], vec![1u8; 32]).unwrap();
+        assert_eq!(get_active_challenges(&mut context, 0, 10).len(), 1);
+
+        context.set_caller(governance_address());
+        resolve_watchdog_challenge(&mut context, challenge.id, false);
+
+        assert!(get_active_challenges(&mut context, 0, 10).is_empty());
+    }
+}
+
+mod proof_witnesses {
+    use super::*;
+
+    fn open_challenge(context: &mut TestContext) -> (Address, u128, Vec<Address>) {
+        let (sgx_executor, _, watchdogs) = setup_full_system(context);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_executor(
+            context,
+            sgx_executor,
+            ChallengeType::Execution,
+            execution_evidence(),
+        )
+        .unwrap();
+
+        (sgx_executor, challenge.id, watchdogs)
+    }
+
+    #[test]
+    fn test_witness_message_bound_to_challenge() {
+        let proof_data = vec![1u8; 32];
+        let challenged = Address::from([3u8; 32]);
+        let message = witness_message(1, challenged, &proof_data);
+
+        assert_ne!(message, witness_message(2, challenged, &proof_data));
+        assert_ne!(message, witness_message(1, Address::from([4u8; 32]), &proof_data));
+    }
+
+    #[test]
+    #[should_panic(expected = "proof for another challenge")]
+    fn test_proof_for_other_challenge_rejected() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id, watchdogs) = open_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        respond_to_challenge(
+            &mut context,
+            challenge_id,
+            vec![1u8; 16],
+            proof_signed_by(challenge_id + 1, &watchdogs[..3]),
+        );
+    }
+
+    #[test]
+    fn test_three_distinct_witnesses_accepted() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id, watchdogs) = open_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        respond_to_challenge(
+            &mut context,
+            challenge_id,
+            vec![1u8; 16],
+            proof_signed_by(challenge_id, &watchdogs[..3]),
+        );
+
+        let challenge = context.get(Challenge(challenge_id)).unwrap().unwrap();
+        assert_eq!(challenge.status, ChallengeStatus::Responded);
+    }
+
+    #[test]
+    #[should_panic(expected = "insufficient witnesses")]
+    fn test_two_witnesses_rejected() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id, watchdogs) = open_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        respond_to_challenge(
+            &mut context,
+            challenge_id,
+            vec![1u8; 16],
+            proof_signed_by(challenge_id, &watchdogs[..2]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate witness")]
+    fn test_duplicate_witness_rejected() {
+        let mut context = setup();
+        let (sgx_executor, challenge_id, watchdogs) = open_challenge(&mut context);
+
+        context.set_caller(sgx_executor);
+        respond_to_challenge(
+            &mut context,
+            challenge_id,
+            vec![1u8; 16],
+            proof_signed_by(challenge_id, &[watchdogs[0], watchdogs[1], watchdogs[1]]),
+        );
+    }
+}
+
+mod attestation_responses {
+    use super::*;
+
+    /// Opens a missed-reattestation challenge against the SEV executor with
+    /// Drawbridge tokens stored only as hashes
+    fn open_attestation_challenge(context: &mut TestContext) -> (Address, u128, Vec<Address>) {
+        context.set_caller(governance_address());
+        update_system_config(context, ConfigUpdate::HashDrawbridgeTokens(true));
+        let (_, sev_executor, watchdogs) = setup_full_system(context);
+
+        context.set_caller(governance_address());
+        let deadline = force_reattestation(context);
+        context.set_timestamp(deadline + 1);
+
+        context.set_caller(watchdogs[0]);
+        let challenge = challenge_missed_reattestation(context, sev_executor).unwrap();
+
+        (sev_executor, challenge.id, watchdogs)
+    }
+
+    fn attestation_proof(challenge_id: u128, witnesses: &[Address], token: Vec<u8>) -> ChallengeProof {
+        ChallengeProof {
+            challenge_id,
+            proof_data: attestation_report_for(EnclaveType::AMDSEV, SEV_OPERATOR),
+            timestamp: 0,
+            witness_signatures: witnesses
+                .iter()
+                .map(|witness| (*witness, vec![0u8; 64]))
+                .collect(),
+            drawbridge_token: token,
+        }
+    }
+
+    #[test]
+    fn test_presented_token_verified_against_hash() {
+        let mut context = setup();
+        let (sev_executor, challenge_id, watchdogs) = open_attestation_challenge(&mut context);
+
+        context.set_caller(sev_executor);
+        respond_to_challenge(
+            &mut context,
+            challenge_id,
+            vec![1u8; 16],
+            attestation_proof(challenge_id, &watchdogs, drawbridge_token_for(SEV_OPERATOR)),
+        );
+
+        assert!(context.get(AttestationStatus(sev_executor)).unwrap().unwrap());
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sev_executor, Some(sev_executor));
+    }
+
+    #[test]
+    fn test_wrong_token_fails_attestation() {
+        let mut context = setup();
+        let (sev_executor, challenge_id, watchdogs) = open_attestation_challenge(&mut context);
+
+        context.set_caller(sev_executor);
+        respond_to_challenge(
+            &mut context,
+            challenge_id,
+            vec![1u8; 16],
+            attestation_proof(challenge_id, &watchdogs, drawbridge_token_for("other-keep")),
+        );
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sev_executor, None);
+    }
+
+    #[test]
+    fn test_different_keep_measurement_fails_attestation() {
+        let mut context = setup();
+        let (sev_executor, challenge_id, watchdogs) = open_attestation_challenge(&mut context);
+
+        let mut proof = attestation_proof(challenge_id, &watchdogs, drawbridge_token_for(SEV_OPERATOR));
+        proof.proof_data = attestation_report_for(EnclaveType::AMDSEV, "other-keep");
+        context.set_caller(sev_executor);
+        respond_to_challenge(&mut context, challenge_id, vec![1u8; 16], proof);
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sev_executor, None);
+    }
+}
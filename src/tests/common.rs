@@ -1,15 +1,22 @@
 use wasmlanche::testing::{setup_test, TestContext};
+use sha2::{Digest, Sha256, Sha384};
 use crate::{
     types::*,
     state::*,
     core::*,
     challenge::*,
     external::*,
+    error::Result,
+    MIN_WATCHDOGS,
 };
 
 pub const SGX_OPERATOR: &str = "sgx_operator_address";
 pub const SEV_OPERATOR: &str = "sev_operator_address";
 
+/// Execution ids the system fixtures mark as allocated, so tests can submit
+/// to small literal ids without going through governance first
+pub const PREALLOCATED_EXECUTION_IDS: u128 = 128;
+
 pub fn setup() -> TestContext {
     let mut context = setup_test();
     init(
@@ -18,10 +25,143 @@ pub fn setup() -> TestContext {
         SEV_OPERATOR.to_string(),
         Address::from([1u8; 32]), // Mock token contract
         Address::from([2u8; 32]), // Mock governance contract
+        None,
+        operator_signature(),
+        operator_signature(),
     );
     context
 }
 
+pub fn preallocate_execution_ids(context: &mut TestContext) {
+    context
+        .store_by_key(ExecutionCounter(), PREALLOCATED_EXECUTION_IDS)
+        .expect("failed to preallocate execution ids");
+}
+
+/// Has `executor` lose a health challenge opened by the first registered
+/// watchdog, then switches to governance: the state `replace_executor`
+/// expects before it promotes a replacement
+pub fn fail_executor(context: &mut TestContext, executor: Address) {
+    let watchdog = context
+        .get(WatchdogPool())
+        .expect("state corrupt")
+        .expect("watchdog pool not initialized")
+        .watchdogs[0]
+        .0;
+    let keep_id = context
+        .get(KeepId(executor))
+        .expect("state corrupt")
+        .expect("keep not registered");
+    let enclave_type = get_enclave_type(context, executor).expect("enclave type not registered");
+
+    context.set_caller(watchdog);
+    let challenges = challenge_executors_batch(
+        context,
+        vec![(executor, ChallengeType::HealthDegradation, vec![1u8; 32])],
+    )
+    .expect("failed to open health challenge");
+
+    context.set_caller(executor);
+    let healthy = respond_with_attested_health(
+        context,
+        challenges[0].id,
+        &keep_id,
+        enclave_type,
+        MemoryStats { used: 99, total: 100 },
+        0,
+    );
+    assert!(!healthy, "executor passed its health challenge");
+
+    context.set_caller(governance_address());
+}
+
+/// Hash of the payload executors report having run in tests
+pub fn payload_hash() -> Vec<u8> {
+    vec![7u8; 32]
+}
+
+/// Mock operator signature over the init parameters
+pub fn operator_signature() -> Vec<u8> {
+    vec![1u8; 64]
+}
+
+pub fn governance_address() -> Address {
+    Address::from([2u8; 32])
+}
+
+/// Mock Drawbridge token whose proof is bound to the given keep id
+pub fn drawbridge_token_for(keep_id: &str) -> Vec<u8> {
+    let mut token = vec![0u8; 64];
+    token.extend_from_slice(keep_id.as_bytes());
+    token.extend_from_slice(&(keep_id.len() as u32).to_le_bytes());
+    token
+}
+
+/// Mock quote from `keep_id`'s keep whose report data attests the given
+/// health metrics
+pub fn health_quote(
+    enclave_type: EnclaveType,
+    keep_id: &str,
+    challenge_id: u128,
+    memory_usage: &MemoryStats,
+    failed_self_checks: u32,
+) -> Vec<u8> {
+    let offset = match enclave_type {
+        EnclaveType::IntelSGX => 368,
+        EnclaveType::AMDSEV => 0x50,
+    };
+    let mut quote = attestation_report_for(enclave_type, keep_id);
+    quote[offset..offset + 32]
+        .copy_from_slice(&health_report_data(challenge_id, memory_usage, failed_self_checks));
+    quote
+}
+
+/// Answers a health challenge from the caller's keep with attested metrics
+pub fn respond_with_attested_health(
+    context: &mut TestContext,
+    challenge_id: u128,
+    keep_id: &str,
+    enclave_type: EnclaveType,
+    memory_usage: MemoryStats,
+    failed_self_checks: u32,
+) -> bool {
+    let report = health_quote(enclave_type, keep_id, challenge_id, &memory_usage, failed_self_checks);
+    respond_to_health_challenge(
+        context,
+        challenge_id,
+        memory_usage,
+        failed_self_checks,
+        report,
+        drawbridge_token_for(keep_id),
+    )
+}
+
+/// Mock keep measurement, distinct for each keep id and sized like the
+/// measurement field of the enclave type's quote
+pub fn measurement_for(enclave_type: EnclaveType, keep_id: &str) -> Vec<u8> {
+    match enclave_type {
+        EnclaveType::IntelSGX => Sha256::digest(keep_id.as_bytes()).to_vec(),
+        EnclaveType::AMDSEV => Sha384::digest(keep_id.as_bytes()).to_vec(),
+    }
+}
+
+/// Mock quote attesting `measurement`, laid out like a real SGX quote or
+/// SEV-SNP report up to the end of the fields the contract reads
+pub fn attestation_report_with(enclave_type: EnclaveType, measurement: &[u8]) -> Vec<u8> {
+    let (offset, len) = match enclave_type {
+        EnclaveType::IntelSGX => (112, 432),
+        EnclaveType::AMDSEV => (0x90, 0xC0),
+    };
+    let mut quote = vec![0u8; len];
+    quote[offset..offset + measurement.len()].copy_from_slice(measurement);
+    quote
+}
+
+/// Mock quote from `keep_id`'s keep
+pub fn attestation_report_for(enclave_type: EnclaveType, keep_id: &str) -> Vec<u8> {
+    attestation_report_with(enclave_type.clone(), &measurement_for(enclave_type, keep_id))
+}
+
 pub fn setup_with_token_contract(context: &mut TestContext) {
     init_token_contract(
         context,
@@ -33,7 +173,6 @@ pub fn setup_with_token_contract(context: &mut TestContext) {
 pub fn setup_system(context: &mut TestContext) -> (Address, Address, Address) {
     let sgx_executor = Address::from([3u8; 32]);
     let sev_executor = Address::from([4u8; 32]);
-    let watchdog = Address::from([5u8; 32]);
 
     // Register executors
     context.set_caller(sgx_executor);
@@ -41,8 +180,8 @@ pub fn setup_system(context: &mut TestContext) -> (Address, Address, Address) {
         context,
         EnclaveType::IntelSGX,
         SGX_OPERATOR.to_string(),
-        vec![0u8; 32], // Mock attestation report
-        vec![0u8; 64], // Mock signature
+        attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+        drawbridge_token_for(SGX_OPERATOR), // Drawbridge token bound to the keep
     );
 
     context.set_caller(sev_executor);
@@ -50,20 +189,67 @@ pub fn setup_system(context: &mut TestContext) -> (Address, Address, Address) {
         context,
         EnclaveType::AMDSEV,
         SEV_OPERATOR.to_string(),
-        vec![0u8; 32],
-        vec![0u8; 64],
+        attestation_report_for(EnclaveType::AMDSEV, SEV_OPERATOR),
+        drawbridge_token_for(SEV_OPERATOR),
     );
 
-    // Register watchdog
-    context.set_caller(watchdog);
+    // Register watchdogs
+    let watchdog = register_watchdog_pool(context);
+    preallocate_execution_ids(context);
+
+    (sgx_executor, sev_executor, watchdog)
+}
+
+/// Registers `caller` as a watchdog whose keep attests as `keep_id`
+pub fn register_watchdog_keep(
+    context: &mut TestContext,
+    caller: Address,
+    enclave_type: EnclaveType,
+    keep_id: &str,
+) {
+    context.set_caller(caller);
     register_watchdog(
         context,
-        EnclaveType::IntelSGX,
-        vec![0u8; 32],
+        enclave_type.clone(),
+        keep_id.to_string(),
+        attestation_report_for(enclave_type, keep_id),
+        drawbridge_token_for(keep_id),
         vec![0u8; 64],
     );
+}
 
-    (sgx_executor, sev_executor, watchdog)
+/// Registers `caller` as a ready TEE whose keep attests as `keep_id`
+pub fn register_ready_tee_keep(
+    context: &mut TestContext,
+    caller: Address,
+    enclave_type: EnclaveType,
+    keep_id: &str,
+) -> Result<()> {
+    context.set_caller(caller);
+    register_ready_tee(
+        context,
+        enclave_type.clone(),
+        keep_id.to_string(),
+        attestation_report_for(enclave_type, keep_id),
+        drawbridge_token_for(keep_id),
+    )
+}
+
+/// Watchdogs registered next to the primary one so the pool reaches
+/// `MIN_WATCHDOGS`, the smallest pool that lets the system enter `Executing`
+pub fn supporting_watchdogs() -> Vec<Address> {
+    (1..MIN_WATCHDOGS as u8).map(|i| Address::from([59 + i; 32])).collect()
+}
+
+/// Registers the primary SGX watchdog and its supporting SEV watchdogs and
+/// returns the primary
+pub fn register_watchdog_pool(context: &mut TestContext) -> Address {
+    let watchdog = Address::from([5u8; 32]);
+    register_watchdog_keep(context, watchdog, EnclaveType::IntelSGX, "watchdog-keep");
+    for supporting in supporting_watchdogs() {
+        register_watchdog_keep(context, supporting, EnclaveType::AMDSEV, "watchdog-keep");
+    }
+    watchdog
 }
 
 pub fn setup_full_system(context: &mut TestContext) -> (Address, Address, Vec<Address>) {
@@ -77,8 +263,8 @@ pub fn setup_full_system(context: &mut TestContext) -> (Address, Address, Vec<Ad
         context,
         EnclaveType::IntelSGX,
         SGX_OPERATOR.to_string(),
-        vec![0u8; 32],
-        vec![0u8; 64],
+        attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+        drawbridge_token_for(SGX_OPERATOR),
     );
 
     context.set_caller(sev_executor);
@@ -86,22 +272,26 @@ pub fn setup_full_system(context: &mut TestContext) -> (Address, Address, Vec<Ad
         context,
         EnclaveType::AMDSEV,
         SEV_OPERATOR.to_string(),
-        vec![0u8; 32],
-        vec![0u8; 64],
+        attestation_report_for(EnclaveType::AMDSEV, SEV_OPERATOR),
+        drawbridge_token_for(SEV_OPERATOR),
     );
 
     // Register multiple watchdogs
     for i in 0..3 {
         let watchdog = Address::from([(i + 5) as u8; 32]);
         context.set_caller(watchdog);
+        let enclave_type = if i % 2 == 0 { EnclaveType::IntelSGX } else { EnclaveType::AMDSEV };
         register_watchdog(
             context,
-            if i % 2 == 0 { EnclaveType::IntelSGX } else { EnclaveType::AMDSEV },
-            vec![0u8; 32],
+            enclave_type.clone(),
+            "watchdog-keep".to_string(),
+            attestation_report_for(enclave_type, "watchdog-keep"),
+            drawbridge_token_for("watchdog-keep"),
             vec![0u8; 64],
         );
         watchdogs.push(watchdog);
     }
+    preallocate_execution_ids(context);
 
     (sgx_executor, sev_executor, watchdogs)
 }
@@ -18,8 +18,8 @@ mod executor_registration {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             keep_id.clone(),
-            vec![0u8; 32], // attestation report
-            vec![0u8; 64], // drawbridge token
+            attestation_report_for(EnclaveType::IntelSGX, &keep_id), // attestation report
+            drawbridge_token_for(&keep_id), // drawbridge token
         );
 
         // Original verifications
@@ -47,8 +47,8 @@ mod executor_registration {
             EnclaveType::AMDSEV,
             SEV_OPERATOR.to_string(),
             keep_id.clone(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::AMDSEV, &keep_id),
+            drawbridge_token_for(&keep_id),
         );
 
         // Original verifications
@@ -75,8 +75,8 @@ mod executor_registration {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "sgx-keep-123".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"),
         );
 
         // Register SEV executor with Enarx Keep
@@ -86,8 +86,8 @@ mod executor_registration {
             EnclaveType::AMDSEV,
             SEV_OPERATOR.to_string(),
             "sev-keep-456".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::AMDSEV, "sev-keep-456"),
+            drawbridge_token_for("sev-keep-456"),
         );
 
         // Original verifications
@@ -115,8 +115,8 @@ mod executor_registration {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "sgx-keep-123".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"),
         );
 
         // Attempt to register second SGX executor
@@ -126,8 +126,8 @@ mod executor_registration {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "sgx-keep-456".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-456"),
+            drawbridge_token_for("sgx-keep-456"),
         );
     }
 
@@ -145,8 +145,8 @@ mod executor_registration {
             EnclaveType::AMDSEV,
             SEV_OPERATOR.to_string(),
             "sev-keep-123".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::AMDSEV, "sev-keep-123"),
+            drawbridge_token_for("sev-keep-123"),
         );
 
         // Attempt to register second SEV executor
@@ -156,192 +156,1574 @@ mod executor_registration {
             EnclaveType::AMDSEV,
             SEV_OPERATOR.to_string(),
             "sev-keep-456".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::AMDSEV, "sev-keep-456"),
+            drawbridge_token_for("sev-keep-456"),
+        );
+    }
+}
+
+mod measurement_uniqueness {
+    use super::*;
+
+    fn register(
+        context: &mut TestContext,
+        caller: Address,
+        enclave_type: EnclaveType,
+        keep_id: &str,
+        attestation_report: Vec<u8>,
+    ) {
+        context.set_caller(caller);
+        register_executor(
+            context,
+            enclave_type,
+            keep_id.to_string(),
+            attestation_report,
+            drawbridge_token_for(keep_id),
+        );
+    }
+
+    #[test]
+    fn test_unique_measurements_accepted() {
+        let mut context = setup();
+        register_watchdog_pool(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+        let sev_executor = Address::from([4u8; 32]);
+
+        let sgx_report = attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123");
+        let sev_report = attestation_report_for(EnclaveType::AMDSEV, "sev-keep-456");
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, "sgx-keep-123", sgx_report);
+        register(&mut context, sev_executor, EnclaveType::AMDSEV, "sev-keep-456", sev_report);
+
+        assert_eq!(
+            context.get(KeepMeasurement(sev_executor)).unwrap(),
+            Some(measurement_for(EnclaveType::AMDSEV, "sev-keep-456"))
+        );
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate measurement")]
+    fn test_measurement_cloned_from_executor_rejected() {
+        let mut context = setup();
+        let report = attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123");
+        register(&mut context, Address::from([3u8; 32]), EnclaveType::IntelSGX, "sgx-keep-123", report.clone());
+
+        register(&mut context, Address::from([9u8; 32]), EnclaveType::IntelSGX, "sgx-keep-123", report);
+    }
+
+    #[test]
+    fn test_ready_tee_may_share_executor_measurement() {
+        let mut context = setup();
+        let report = attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123");
+        register(&mut context, Address::from([3u8; 32]), EnclaveType::IntelSGX, "sgx-keep-123", report.clone());
+
+        let tee = Address::from([20u8; 32]);
+        context.set_caller(tee);
+        let result = register_ready_tee(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-456".to_string(),
+            report,
+            drawbridge_token_for("sgx-keep-456"),
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            context.get(KeepMeasurement(tee)).unwrap(),
+            Some(measurement_for(EnclaveType::IntelSGX, "sgx-keep-123"))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attestation report carries no measurement")]
+    fn test_report_without_measurement_rejected() {
+        let mut context = setup();
+        register(&mut context, Address::from([3u8; 32]), EnclaveType::IntelSGX, "sgx-keep-123", vec![0u8; 32]);
+    }
+}
+
+mod registration_cosigning {
+    use super::*;
+    use crate::{PENDING_REGISTRATION_TTL, MIN_WATCHDOGS};
+
+    /// Registers the minimum watchdog pool and requires two co-signs per
+    /// executor
+    fn setup_cosigning(context: &mut TestContext) -> Vec<Address> {
+        let watchdogs = [vec![register_watchdog_pool(context)], supporting_watchdogs()].concat();
+
+        context.set_caller(governance_address());
+        update_system_config(context, ConfigUpdate::RegistrationCosigns(2));
+        watchdogs
+    }
+
+    fn register(context: &mut TestContext, caller: Address, enclave_type: EnclaveType, keep_id: &str) {
+        context.set_caller(caller);
+        register_executor(
+            context,
+            enclave_type.clone(),
+            keep_id.to_string(),
+            attestation_report_for(enclave_type, keep_id),
+            drawbridge_token_for(keep_id),
+        );
+    }
+
+    #[test]
+    fn test_registration_pending_until_cosigned() {
+        let mut context = setup();
+        let watchdogs = setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+
+        let pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(pool.sgx_executor, None);
+        assert!(get_pending_registration(&mut context, sgx_executor).is_some());
+
+        context.set_caller(watchdogs[0]);
+        attest_pending_executor(&mut context, sgx_executor);
+
+        let pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(pool.sgx_executor, None);
+        assert_eq!(
+            get_pending_registration(&mut context, sgx_executor).unwrap().cosigners,
+            vec![watchdogs[0]]
+        );
+
+        context.set_caller(watchdogs[1]);
+        attest_pending_executor(&mut context, sgx_executor);
+
+        let pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(pool.sgx_executor, Some(sgx_executor));
+        assert_eq!(
+            context.get(ExecutorState(sgx_executor)).unwrap(),
+            Some(ExecutorStatus::Active)
+        );
+        assert!(get_pending_registration(&mut context, sgx_executor).is_none());
+    }
+
+    #[test]
+    fn test_cosigned_executors_start_execution() {
+        let mut context = setup();
+        let watchdogs = setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+        let sev_executor = Address::from([4u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+        register(&mut context, sev_executor, EnclaveType::AMDSEV, SEV_OPERATOR);
+        assert_eq!(get_current_phase(&mut context), Phase::Creation);
+
+        for watchdog in &watchdogs[..2] {
+            context.set_caller(*watchdog);
+            attest_pending_executor(&mut context, sgx_executor);
+            attest_pending_executor(&mut context, sev_executor);
+        }
+
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+    }
+
+    #[test]
+    #[should_panic(expected = "already co-signed")]
+    fn test_duplicate_cosign_rejected() {
+        let mut context = setup();
+        let watchdogs = setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+
+        context.set_caller(watchdogs[0]);
+        attest_pending_executor(&mut context, sgx_executor);
+        attest_pending_executor(&mut context, sgx_executor);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a watchdog")]
+    fn test_non_watchdog_cosign_rejected() {
+        let mut context = setup();
+        setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+
+        context.set_caller(Address::from([42u8; 32]));
+        attest_pending_executor(&mut context, sgx_executor);
+    }
+
+    #[test]
+    #[should_panic(expected = "registration expired")]
+    fn test_expired_registration_cannot_be_cosigned() {
+        let mut context = setup();
+        let watchdogs = setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+        context.set_timestamp(context.timestamp() + PENDING_REGISTRATION_TTL + 1);
+
+        context.set_caller(watchdogs[0]);
+        attest_pending_executor(&mut context, sgx_executor);
+    }
+
+    #[test]
+    fn test_expired_registration_can_be_retried() {
+        let mut context = setup();
+        setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+        context.set_timestamp(context.timestamp() + PENDING_REGISTRATION_TTL + 1);
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+
+        let pending = get_pending_registration(&mut context, sgx_executor).unwrap();
+        assert_eq!(pending.requested_at, context.timestamp());
+    }
+
+    #[test]
+    #[should_panic(expected = "registration already pending")]
+    fn test_live_registration_cannot_be_replaced() {
+        let mut context = setup();
+        setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+    }
+
+    #[test]
+    fn test_cancelled_registration_cleared() {
+        let mut context = setup();
+        setup_cosigning(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+        context.set_caller(sgx_executor);
+        cancel_pending_registration(&mut context);
+
+        assert!(get_pending_registration(&mut context, sgx_executor).is_none());
+        register(&mut context, sgx_executor, EnclaveType::IntelSGX, SGX_OPERATOR);
+    }
+
+    #[test]
+    #[should_panic(expected = "co-sign requirement exceeds watchdog pool")]
+    fn test_cosigns_beyond_watchdog_pool_rejected() {
+        let mut context = setup();
+        setup_cosigning(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::RegistrationCosigns(MIN_WATCHDOGS + 1));
+    }
+}
+
+mod executor_unregistration {
+    use super::*;
+
+    #[test]
+    fn test_unregister_during_creation() {
+        let mut context = setup();
+        setup_with_token_contract(&mut context);
+        let sgx_executor = Address::from([3u8; 32]);
+
+        context.set_caller(sgx_executor);
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-123".to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"),
+        );
+        stake_tokens(&mut context, 1_000);
+        let balance = get_token_balance(&mut context, sgx_executor);
+
+        unregister_executor(&mut context);
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, None);
+        assert!(context.get(EnclaveType(sgx_executor)).unwrap().is_none());
+        assert!(context.get(KeepId(sgx_executor)).unwrap().is_none());
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 0);
+        assert_eq!(get_token_balance(&mut context, sgx_executor), balance + 1_000);
+
+        // The freed slot can be taken by the correct keep
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-456".to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-456"),
+            drawbridge_token_for("sgx-keep-456"),
         );
+        assert_eq!(get_current_phase(&mut context), Phase::Creation);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid phase")]
+    fn test_unregister_rejected_when_executing() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+
+        context.set_caller(sgx_executor);
+        unregister_executor(&mut context);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a registered executor")]
+    fn test_unregister_requires_registration() {
+        let mut context = setup();
+
+        context.set_caller(Address::from([3u8; 32]));
+        unregister_executor(&mut context);
     }
 }
 
 mod executor_operations {
     use super::*;
+    use crate::MIN_HEARTBEAT_SPACING;
+
+    #[test]
+    fn test_executor_heartbeat() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_heartbeat(&mut context);
+
+        // Original verifications
+        let timestamp = context.get(HeartbeatTimestamp(sgx_executor)).unwrap().unwrap();
+        assert!(timestamp > 0);
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.execution_count, 1);
+
+        // New Enarx-specific verifications
+        let keep_status = context.get(KeepStatus(sgx_executor)).unwrap().unwrap();
+        assert!(keep_status, "Keep should remain active after heartbeat");
+    }
+
+    #[test]
+    fn test_executor_state_updates() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        // Submit multiple heartbeats
+        for executor in [sgx_executor, sev_executor].iter() {
+            context.set_caller(*executor);
+            for _ in 0..3 {
+                submit_heartbeat(&mut context);
+                context.set_block_height(context.block_height() + MIN_HEARTBEAT_SPACING);
+            }
+        }
+
+        // Original verifications
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.execution_count, 6);
+
+        // Verify Enarx Keep states
+        for executor in [sgx_executor, sev_executor].iter() {
+            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
+            let last_attestation = context
+                .get(LastAttestationTime(*executor))
+                .unwrap()
+                .unwrap();
+            assert!(last_attestation > 0);
+        }
+    }
+
+    #[test]
+    fn test_keep_attestation_renewal() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        // Initial attestation time
+        let initial_attestation = context
+            .get(LastAttestationTime(sgx_executor))
+            .unwrap()
+            .unwrap();
+
+        // Simulate time passing
+        context.set_timestamp(initial_attestation + 1000);
+
+        // Submit new attestation
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR), // new attestation report
+            drawbridge_token_for(SGX_OPERATOR), // new drawbridge token
+        );
+
+        // Verify attestation was updated
+        let new_attestation = context
+            .get(LastAttestationTime(sgx_executor))
+            .unwrap()
+            .unwrap();
+        assert!(new_attestation > initial_attestation);
+    }
+
+    #[test]
+    fn test_executor_keep_operations() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        // Test Keep status checks
+        for executor in [sgx_executor, sev_executor].iter() {
+            context.set_caller(*executor);
+            
+            // Verify initial Keep state
+            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
+            
+            // Submit heartbeat with Keep status
+            submit_heartbeat_with_keep_status(&mut context, true);
+            
+            // Verify Keep remains active
+            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_keep_measurement_updates() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+
+        // Submit Keep measurement
+        let measurement = vec![3u8; 32]; // Mock measurement
+        update_keep_measurement(&mut context, measurement.clone());
+
+        // Verify measurement was stored
+        let stored_measurement = context
+            .get(KeepMeasurement(sgx_executor))
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored_measurement, measurement);
+    }
+
+    #[test]
+    #[should_panic(expected = "keep not active")]
+    fn test_operation_with_inactive_keep() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        // Deactivate Keep
+        context.store_by_key(KeepStatus(sgx_executor), false)
+            .expect("failed to update keep status");
+
+        // Attempt operation with inactive Keep
+        context.set_caller(sgx_executor);
+        submit_heartbeat(&mut context);
+    }
+
+    #[test]
+    fn test_keep_status_transitions() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+
+        // Test Keep pause
+        pause_keep(&mut context);
+        assert!(!context.get(KeepStatus(sgx_executor)).unwrap().unwrap());
+
+        // Test Keep resume
+        resume_keep(&mut context);
+        assert!(context.get(KeepStatus(sgx_executor)).unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_concurrent_keep_operations() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        // Simulate concurrent operations from both Keeps
+        for executor in [sgx_executor, sev_executor].iter() {
+            context.set_caller(*executor);
+            
+            // Submit heartbeat
+            submit_heartbeat(&mut context);
+            
+            // Update measurement
+            update_keep_measurement(&mut context, vec![4u8; 32]);
+            
+            // Renew attestation
+            let keep_id = context.get(KeepId(*executor)).unwrap().unwrap();
+            let enclave_type = get_enclave_type(&mut context, *executor).unwrap();
+            renew_attestation(
+                &mut context,
+                attestation_report_for(enclave_type, &keep_id),
+                drawbridge_token_for(&keep_id),
+            );
+        }
+
+        // Verify all operations were processed
+        for executor in [sgx_executor, sev_executor].iter() {
+            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
+            assert!(context.get(LastAttestationTime(*executor)).unwrap().unwrap() > 0);
+            assert!(context.get(KeepMeasurement(*executor)).unwrap().is_some());
+        }
+    }
+}
+
+mod heartbeat_spacing {
+    use super::*;
+    use crate::MIN_HEARTBEAT_SPACING;
+
+    #[test]
+    #[should_panic(expected = "heartbeat too frequent")]
+    fn test_heartbeat_before_spacing_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_heartbeat(&mut context);
+
+        context.set_block_height(context.block_height() + MIN_HEARTBEAT_SPACING - 1);
+        submit_heartbeat(&mut context);
+    }
+
+    #[test]
+    fn test_heartbeat_after_spacing_accepted() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        submit_heartbeat(&mut context);
+
+        context.set_block_height(context.block_height() + MIN_HEARTBEAT_SPACING);
+        submit_heartbeat(&mut context);
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.execution_count, 2);
+        assert_eq!(
+            context.get(LastHeartbeatBlock(sgx_executor)).unwrap().unwrap(),
+            context.block_height()
+        );
+    }
+
+    #[test]
+    fn test_spacing_is_configurable() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MinHeartbeatSpacing(1));
+
+        context.set_caller(sgx_executor);
+        submit_heartbeat(&mut context);
+        context.set_block_height(context.block_height() + 1);
+        submit_heartbeat(&mut context);
+    }
+}
+
+mod executor_status {
+    use super::*;
+    use crate::execution::*;
+
+    #[test]
+    fn test_registered_executor_is_active() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        assert_eq!(get_executor_status(&mut context, sgx_executor), Some(ExecutorStatus::Active));
+    }
+
+    #[test]
+    #[should_panic(expected = "executor not active")]
+    fn test_suspended_executor_cannot_submit() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        suspend_executor(&mut context, sgx_executor);
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "executor not active")]
+    fn test_draining_executor_cannot_submit() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        drain_executor(&mut context);
+        assert_eq!(get_executor_status(&mut context, sgx_executor), Some(ExecutorStatus::Draining));
+
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+    }
+
+    #[test]
+    fn test_governance_reinstates_suspended_executor() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        suspend_executor(&mut context, sgx_executor);
+        reinstate_executor(&mut context, sgx_executor);
+        assert_eq!(get_executor_status(&mut context, sgx_executor), Some(ExecutorStatus::Active));
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        assert!(verify_execution(&mut context, 1u128));
+    }
+
+    #[test]
+    fn test_governance_reinstates_draining_executor() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        drain_executor(&mut context);
+
+        context.set_caller(governance_address());
+        reinstate_executor(&mut context, sgx_executor);
+        assert_eq!(get_executor_status(&mut context, sgx_executor), Some(ExecutorStatus::Active));
+    }
+
+    #[test]
+    #[should_panic(expected = "executor not suspended or draining")]
+    fn test_active_executor_cannot_be_reinstated() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        reinstate_executor(&mut context, sgx_executor);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_reinstate_requires_governance() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        suspend_executor(&mut context, sgx_executor);
+
+        context.set_caller(sgx_executor);
+        reinstate_executor(&mut context, sgx_executor);
+    }
+
+    #[test]
+    fn test_challenge_failure_marks_executor_failed() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let challenges = challenge_executors_batch(
+            &mut context,
+            vec![(sgx_executor, ChallengeType::HealthDegradation, vec![1u8; 32])],
+        )
+        .unwrap();
+
+        context.set_caller(sgx_executor);
+        respond_with_attested_health(
+            &mut context,
+            challenges[0].id,
+            SGX_OPERATOR,
+            EnclaveType::IntelSGX,
+            MemoryStats { used: 99, total: 100 },
+            0,
+        );
+
+        assert_eq!(get_executor_status(&mut context, sgx_executor), Some(ExecutorStatus::Failed));
+    }
+}
+
+mod attestation_failures {
+    use super::*;
+    use crate::{core::drawbridge_token_hash, error::AttestationFailure};
+
+    #[test]
+    fn test_empty_report_reason() {
+        let mut context = setup();
+        let result = verify_attestation_report(
+            &mut context,
+            &[],
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Err(AttestationFailure::EmptyReport));
+    }
+
+    #[test]
+    fn test_unbound_token_reason() {
+        let mut context = setup();
+        let result = verify_attestation_report(
+            &mut context,
+            &[0u8; 32],
+            &drawbridge_token_for("other-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Err(AttestationFailure::TokenNotBoundToKeep));
+    }
+
+    #[test]
+    fn test_token_for_keep_id_with_shared_suffix_rejected() {
+        let mut context = setup();
+        let result = verify_attestation_report(
+            &mut context,
+            &[0u8; 32],
+            &drawbridge_token_for("evil-sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Err(AttestationFailure::TokenNotBoundToKeep));
+    }
+
+    #[test]
+    fn test_revoked_token_reason() {
+        let mut context = setup();
+        let token = drawbridge_token_for("sgx-keep");
+
+        context.set_caller(governance_address());
+        revoke_drawbridge_token(&mut context, drawbridge_token_hash(&token));
+
+        let result = verify_attestation_report(
+            &mut context,
+            &[0u8; 32],
+            &token,
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Err(AttestationFailure::RevokedToken));
+    }
+
+    fn bundle(collateral: Vec<u8>) -> Vec<u8> {
+        AttestationBundle {
+            quote: vec![1u8; 32],
+            collateral,
+            tcb_info: vec![3u8; 16],
+        }
+        .encode()
+    }
+
+    #[test]
+    fn test_complete_bundle_verifies() {
+        let mut context = setup();
+        let result = verify_attestation_report(
+            &mut context,
+            &bundle(vec![2u8; 64]),
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_bundle_missing_collateral_rejected() {
+        let mut context = setup();
+        let result = verify_attestation_report(
+            &mut context,
+            &bundle(Vec::new()),
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(
+            result,
+            Err(AttestationFailure::MissingBundleComponent("collateral".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_truncated_bundle_rejected() {
+        let mut context = setup();
+        let mut report = bundle(vec![2u8; 64]);
+        report.truncate(report.len() - 1);
+
+        let result = verify_attestation_report(
+            &mut context,
+            &report,
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Err(AttestationFailure::MalformedBundle));
+    }
+
+    #[test]
+    fn test_monolithic_report_rejected_when_bundle_required() {
+        let mut context = setup();
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::VerificationMode(VerificationMode::RequireBundle),
+        );
+
+        let result = verify_attestation_report(
+            &mut context,
+            &[1u8; 32],
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Err(AttestationFailure::MalformedBundle));
+
+        let result = verify_attestation_report(
+            &mut context,
+            &bundle(vec![2u8; 64]),
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_measurement_mismatch_reason() {
+        let mut context = setup();
+        let expected = measurement_for(EnclaveType::IntelSGX, "sgx-keep");
+
+        let result = verify_attestation_report(
+            &mut context,
+            &attestation_report_for(EnclaveType::IntelSGX, "other-keep"),
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            Some(&expected),
+        );
+        assert_eq!(result, Err(AttestationFailure::MeasurementMismatch));
+
+        let result = verify_attestation_report(
+            &mut context,
+            &attestation_report_for(EnclaveType::IntelSGX, "sgx-keep"),
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            Some(&expected),
+        );
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn test_expired_quote_reason() {
+        let mut context = setup();
+        context.set_timestamp(1_000);
+
+        let mut collateral = 999u64.to_le_bytes().to_vec();
+        collateral.extend([2u8; 56]);
+        let result = verify_attestation_report(
+            &mut context,
+            &bundle(collateral),
+            &drawbridge_token_for("sgx-keep"),
+            "sgx-keep",
+            EnclaveType::IntelSGX,
+            None,
+        );
+        assert_eq!(result, Err(AttestationFailure::ExpiredQuote));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid attestation: attested measurement does not match registered keep")]
+    fn test_renewal_from_different_keep_surfaces_reason() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::IntelSGX, "other-keep"),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid attestation: drawbridge token revoked")]
+    fn test_registration_surfaces_reason() {
+        let mut context = setup();
+        let token = drawbridge_token_for(SGX_OPERATOR);
+
+        context.set_caller(governance_address());
+        revoke_drawbridge_token(&mut context, drawbridge_token_hash(&token));
+
+        context.set_caller(Address::from([3u8; 32]));
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            SGX_OPERATOR.to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            token,
+        );
+    }
+}
+
+mod active_enclave_types {
+    use super::*;
+
+    #[test]
+    fn test_no_enclave_types_before_registration() {
+        let mut context = setup();
+        assert!(get_active_enclave_types(&mut context).is_empty());
+    }
+
+    #[test]
+    fn test_both_enclave_types_reported() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        assert_eq!(
+            get_active_enclave_types(&mut context),
+            vec![EnclaveType::IntelSGX, EnclaveType::AMDSEV]
+        );
+    }
+}
+
+mod caller_role {
+    use super::*;
+
+    #[test]
+    fn test_sgx_executor_role() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        assert_eq!(get_caller_role(&mut context), Role::Executor(EnclaveType::IntelSGX));
+    }
+
+    #[test]
+    fn test_watchdog_role() {
+        let mut context = setup();
+        let (_, _, watchdog) = setup_system(&mut context);
+
+        context.set_caller(watchdog);
+        assert_eq!(get_caller_role(&mut context), Role::Watchdog(EnclaveType::IntelSGX));
+    }
+
+    #[test]
+    fn test_unregistered_address_has_no_role() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        context.set_caller(Address::from([99u8; 32]));
+        assert_eq!(get_caller_role(&mut context), Role::None);
+    }
+}
+
+mod keep_migration_support {
+    use super::*;
+
+    #[test]
+    fn test_sev_keep_supports_migration() {
+        let mut context = setup();
+        let (_, sev_executor, _) = setup_system(&mut context);
+
+        assert!(is_migration_supported(&mut context, sev_executor));
+    }
+
+    #[test]
+    fn test_sgx_keep_requires_cold_replacement() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        assert!(!is_migration_supported(&mut context, sgx_executor));
+    }
+
+    #[test]
+    fn test_unregistered_address_not_migratable() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        assert!(!is_migration_supported(&mut context, Address::from([99u8; 32])));
+    }
+}
+
+mod keep_attestation_state {
+    use super::*;
+
+    #[test]
+    fn test_registered_executor_state() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        context.store_by_key(KeepMeasurement(sgx_executor), vec![9u8; 32]).unwrap();
+
+        let registered_at = context.get(LastAttestationTime(sgx_executor)).unwrap().unwrap();
+        assert_eq!(
+            get_keep_attestation_state(&mut context, sgx_executor),
+            Some((registered_at, vec![9u8; 32], true))
+        );
+    }
+
+    #[test]
+    fn test_unregistered_address_has_no_state() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        assert_eq!(get_keep_attestation_state(&mut context, Address::from([99u8; 32])), None);
+    }
+}
+
+mod payload_validation {
+    use super::*;
+
+    #[test]
+    fn test_payload_within_sgx_limit_but_over_sev_limit() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::MaxPayloadSize { enclave_type: EnclaveType::AMDSEV, size: 1_024 },
+        );
+
+        assert_eq!(
+            validate_payload(&mut context, payload_hash(), 2_048),
+            vec![(EnclaveType::IntelSGX, true), (EnclaveType::AMDSEV, false)]
+        );
+    }
+
+    #[test]
+    fn test_payload_within_both_limits() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        assert_eq!(
+            validate_payload(&mut context, payload_hash(), 2_048),
+            vec![(EnclaveType::IntelSGX, true), (EnclaveType::AMDSEV, true)]
+        );
+    }
+}
+
+mod failed_attempts {
+    use super::*;
+
+    #[test]
+    fn test_failed_attempts_count_and_reset() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdogs) = setup_full_system(&mut context);
+        assert_eq!(get_failed_attempts(&mut context), 0);
+
+        context.set_caller(watchdogs[0]);
+        let challenges = challenge_executors_batch(
+            &mut context,
+            vec![
+                (sgx_executor, ChallengeType::HealthDegradation, vec![1u8; 32]),
+                (sev_executor, ChallengeType::HealthDegradation, vec![1u8; 32]),
+            ],
+        )
+        .unwrap();
+
+        let keeps = [
+            (sgx_executor, SGX_OPERATOR, EnclaveType::IntelSGX),
+            (sev_executor, SEV_OPERATOR, EnclaveType::AMDSEV),
+        ];
+        for (challenge, (executor, keep_id, enclave_type)) in challenges.iter().zip(keeps) {
+            context.set_caller(executor);
+            respond_with_attested_health(
+                &mut context,
+                challenge.id,
+                keep_id,
+                enclave_type,
+                MemoryStats { used: 99, total: 100 },
+                1,
+            );
+        }
+
+        assert_eq!(get_failed_attempts(&mut context), 2);
+
+        context.set_caller(governance_address());
+        reset_failed_attempts(&mut context);
+        assert_eq!(get_failed_attempts(&mut context), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_reset_requires_governance() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        context.set_caller(Address::from([9u8; 32]));
+        reset_failed_attempts(&mut context);
+    }
+}
+
+mod renewal_continuity {
+    use super::*;
+
+    #[test]
+    fn test_renewal_with_matching_identity_accepted() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+
+        assert!(context.get(AttestationStatus(sgx_executor)).unwrap().unwrap());
+        assert_eq!(get_enclave_type(&mut context, sgx_executor), Some(EnclaveType::IntelSGX));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid attestation")]
+    fn test_quote_of_other_enclave_type_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::AMDSEV, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "renewal cannot change keep id")]
+    fn test_token_for_other_keep_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for("other-keep"),
+        );
+    }
+}
+
+mod forced_reattestation {
+    use super::*;
+    use crate::REATTESTATION_WINDOW;
+
+    #[test]
+    fn test_force_clears_attestation_status() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        let deadline = force_reattestation(&mut context);
+
+        assert_eq!(deadline, context.timestamp() + REATTESTATION_WINDOW);
+        assert!(!context.get(AttestationStatus(sgx_executor)).unwrap().unwrap());
+        assert!(!context.get(AttestationStatus(sev_executor)).unwrap().unwrap());
+
+        // Renewing restores the executor's status
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+        assert!(context.get(AttestationStatus(sgx_executor)).unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_non_renewing_executor_challengeable_after_deadline() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(governance_address());
+        let deadline = force_reattestation(&mut context);
+
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+
+        // Too early to challenge
+        context.set_caller(watchdogs[0]);
+        assert!(challenge_missed_reattestation(&mut context, sev_executor).is_err());
+
+        context.set_timestamp(deadline + 1);
+        let challenge = challenge_missed_reattestation(&mut context, sev_executor).unwrap();
+        assert_eq!(challenge.challenge_type, ChallengeType::Attestation);
+        assert_eq!(challenge.status, ChallengeStatus::Pending);
+
+        // The executor that renewed cannot be challenged
+        assert!(challenge_missed_reattestation(&mut context, sgx_executor).is_err());
+    }
+
+    #[test]
+    fn test_non_executor_not_challengeable() {
+        let mut context = setup();
+        let (_, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(governance_address());
+        let deadline = force_reattestation(&mut context);
+
+        // Addresses outside the executor pool never had an attestation to renew
+        context.set_timestamp(deadline + 1);
+        context.set_caller(watchdogs[0]);
+        for target in [watchdogs[1], Address::from([42u8; 32])] {
+            assert!(challenge_missed_reattestation(&mut context, target).is_err());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_force_requires_governance() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        context.set_caller(Address::from([9u8; 32]));
+        force_reattestation(&mut context);
+    }
+}
+
+mod attestation_report_limits {
+    use super::*;
+    use crate::MAX_ATTESTATION_REPORT_LEN;
+
+    #[test]
+    fn test_normal_size_report_accepted() {
+        let mut context = setup();
+        let sgx_executor = Address::from([3u8; 32]);
+
+        context.set_caller(sgx_executor);
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-123".to_string(),
+            vec![0u8; MAX_ATTESTATION_REPORT_LEN],
+            drawbridge_token_for("sgx-keep-123"),
+        );
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, Some(sgx_executor));
+    }
+
+    #[test]
+    #[should_panic(expected = "attestation report too large")]
+    fn test_oversized_report_rejected() {
+        let mut context = setup();
+
+        context.set_caller(Address::from([3u8; 32]));
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-123".to_string(),
+            vec![0u8; MAX_ATTESTATION_REPORT_LEN + 1],
+            drawbridge_token_for("sgx-keep-123"),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attestation report too large")]
+    fn test_limit_is_configurable() {
+        let mut context = setup();
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MaxAttestationReportLen(16));
+
+        context.set_caller(Address::from([3u8; 32]));
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-123".to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"),
+        );
+    }
+}
+
+mod enclave_type_lookup {
+    use super::*;
+
+    #[test]
+    fn test_registered_address_has_enclave_type() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        assert_eq!(get_enclave_type(&mut context, sgx_executor), Some(EnclaveType::IntelSGX));
+        assert_eq!(get_enclave_type(&mut context, sev_executor), Some(EnclaveType::AMDSEV));
+    }
+
+    #[test]
+    fn test_unknown_address_has_no_enclave_type() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        assert_eq!(get_enclave_type(&mut context, Address::from([42u8; 32])), None);
+    }
+}
+
+mod drawbridge_token_hashing {
+    use super::*;
+    use crate::core::drawbridge_token_hash;
+
+    #[test]
+    fn test_hashed_token_verification() {
+        let mut context = setup();
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::HashDrawbridgeTokens(true));
+
+        let sgx_executor = Address::from([3u8; 32]);
+        let token = drawbridge_token_for("sgx-keep-123");
+
+        context.set_caller(sgx_executor);
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-123".to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            token.clone(),
+        );
+
+        // Only the commitment is kept on-chain
+        assert!(context.get(DrawbridgeToken(sgx_executor)).unwrap().is_none());
+        assert_eq!(
+            context.get(DrawbridgeTokenHash(sgx_executor)).unwrap().unwrap(),
+            drawbridge_token_hash(&token)
+        );
+
+        assert!(verify_drawbridge_token(&mut context, sgx_executor, token));
+        assert!(!verify_drawbridge_token(
+            &mut context,
+            sgx_executor,
+            drawbridge_token_for("sgx-keep-999"),
+        ));
+    }
 
     #[test]
-    fn test_executor_heartbeat() {
+    fn test_migrate_full_tokens_to_hashes() {
         let mut context = setup();
-        let (sgx_executor, _, _) = setup_system(&mut context);
-
-        context.set_caller(sgx_executor);
-        submit_heartbeat(&mut context);
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
 
-        // Original verifications
-        let timestamp = context.get(HeartbeatTimestamp(sgx_executor)).unwrap().unwrap();
-        assert!(timestamp > 0);
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::HashDrawbridgeTokens(true));
+        assert_eq!(migrate_drawbridge_tokens(&mut context), 2);
 
-        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
-        assert_eq!(executor_pool.execution_count, 1);
+        for (executor, keep_id) in [(sgx_executor, SGX_OPERATOR), (sev_executor, SEV_OPERATOR)] {
+            assert!(context.get(DrawbridgeToken(executor)).unwrap().is_none());
+            assert!(verify_drawbridge_token(&mut context, executor, drawbridge_token_for(keep_id)));
+        }
 
-        // New Enarx-specific verifications
-        let keep_status = context.get(KeepStatus(sgx_executor)).unwrap().unwrap();
-        assert!(keep_status, "Keep should remain active after heartbeat");
+        // Running the migration again is a no-op
+        assert_eq!(migrate_drawbridge_tokens(&mut context), 0);
     }
 
     #[test]
-    fn test_executor_state_updates() {
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_migration_requires_governance() {
         let mut context = setup();
-        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        setup_system(&mut context);
 
-        // Submit multiple heartbeats
-        for executor in [sgx_executor, sev_executor].iter() {
-            context.set_caller(*executor);
-            for _ in 0..3 {
-                submit_heartbeat(&mut context);
-            }
-        }
+        context.set_caller(Address::from([9u8; 32]));
+        migrate_drawbridge_tokens(&mut context);
+    }
 
-        // Original verifications
-        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
-        assert_eq!(executor_pool.execution_count, 6);
+    #[test]
+    #[should_panic(expected = "drawbridge token hashing not enabled")]
+    fn test_migration_requires_hashing_enabled() {
+        let mut context = setup();
+        setup_system(&mut context);
 
-        // Verify Enarx Keep states
-        for executor in [sgx_executor, sev_executor].iter() {
-            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
-            let last_attestation = context
-                .get(LastAttestationTime(*executor))
-                .unwrap()
-                .unwrap();
-            assert!(last_attestation > 0);
-        }
+        context.set_caller(governance_address());
+        migrate_drawbridge_tokens(&mut context);
     }
 
     #[test]
-    fn test_keep_attestation_renewal() {
+    fn test_renewal_after_enabling_hashing_drops_full_token() {
         let mut context = setup();
         let (sgx_executor, _, _) = setup_system(&mut context);
 
-        // Initial attestation time
-        let initial_attestation = context
-            .get(LastAttestationTime(sgx_executor))
-            .unwrap()
-            .unwrap();
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::HashDrawbridgeTokens(true));
 
-        // Simulate time passing
-        context.set_timestamp(initial_attestation + 1000);
+        context.set_caller(sgx_executor);
+        renew_attestation(
+            &mut context,
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+
+        assert!(context.get(DrawbridgeToken(sgx_executor)).unwrap().is_none());
+        assert!(context.get(DrawbridgeTokenHash(sgx_executor)).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_renewal_after_disabling_hashing_drops_stale_hash() {
+        let mut context = setup();
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::HashDrawbridgeTokens(true));
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::HashDrawbridgeTokens(false));
 
-        // Submit new attestation
         context.set_caller(sgx_executor);
         renew_attestation(
             &mut context,
-            vec![1u8; 32], // new attestation report
-            vec![2u8; 64], // new drawbridge token
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
         );
 
-        // Verify attestation was updated
-        let new_attestation = context
-            .get(LastAttestationTime(sgx_executor))
-            .unwrap()
-            .unwrap();
-        assert!(new_attestation > initial_attestation);
+        assert!(context.get(DrawbridgeTokenHash(sgx_executor)).unwrap().is_none());
+        assert_eq!(
+            context.get(DrawbridgeToken(sgx_executor)).unwrap(),
+            Some(drawbridge_token_for(SGX_OPERATOR))
+        );
     }
+}
+
+mod tx_hash_chain {
+    use super::*;
+    use crate::{execution::*, core::{encode_tx_link, hash_incremental}};
 
     #[test]
-    fn test_executor_keep_operations() {
+    fn test_operation_advances_tx_hash() {
         let mut context = setup();
-        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        let (sgx_executor, _, _) = setup_system(&mut context);
 
-        // Test Keep status checks
-        for executor in [sgx_executor, sev_executor].iter() {
-            context.set_caller(*executor);
-            
-            // Verify initial Keep state
-            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
-            
-            // Submit heartbeat with Keep status
-            submit_heartbeat_with_keep_status(&mut context, true);
-            
-            // Verify Keep remains active
-            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
-        }
+        let before = get_incremental_tx_hash(&mut context);
+        let state_root = context.get(StateRoot()).unwrap().unwrap_or_default();
+
+        context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+        let after = get_incremental_tx_hash(&mut context);
+        let link = encode_tx_link(
+            &sgx_executor.to_string(),
+            "record_execution_result",
+            &[&1u128.to_le_bytes(), &[1u8; 32], &payload_hash()],
+            &state_root,
+        );
+        assert_ne!(after, before);
+        assert_eq!(after, hash_incremental(before, &link));
     }
 
     #[test]
-    fn test_keep_measurement_updates() {
+    fn test_tx_link_covers_arguments() {
         let mut context = setup();
         let (sgx_executor, _, _) = setup_system(&mut context);
 
+        let before = get_incremental_tx_hash(&mut context);
         context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+        let first = get_incremental_tx_hash(&mut context);
 
-        // Submit Keep measurement
-        let measurement = vec![3u8; 32]; // Mock measurement
-        update_keep_measurement(&mut context, measurement.clone());
+        // Same history, same caller, different result hash
+        let mut other = setup();
+        setup_system(&mut other);
+        assert_eq!(get_incremental_tx_hash(&mut other), before);
+        other.set_caller(sgx_executor);
+        submit_execution_result(&mut other, 1u128, vec![2u8; 32], payload_hash(), 1);
 
-        // Verify measurement was stored
-        let stored_measurement = context
-            .get(KeepMeasurement(sgx_executor))
-            .unwrap()
-            .unwrap();
-        assert_eq!(stored_measurement, measurement);
+        assert_ne!(get_incremental_tx_hash(&mut other), first);
     }
+}
+
+mod executor_liveness {
+    use super::*;
+    use crate::{
+        execution::*,
+        TIMEOUT_INTERVAL,
+        MIN_HEARTBEAT_SPACING,
+        HEARTBEAT_RECOVERY_STREAK,
+    };
 
     #[test]
-    #[should_panic(expected = "keep not active")]
-    fn test_operation_with_inactive_keep() {
+    fn test_stale_heartbeat_clears_attestation() {
         let mut context = setup();
-        let (sgx_executor, _, _) = setup_system(&mut context);
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
 
-        // Deactivate Keep
-        context.store_by_key(KeepStatus(sgx_executor), false)
-            .expect("failed to update keep status");
+        let last_heartbeat = context.get(HeartbeatTimestamp(sgx_executor)).unwrap().unwrap();
 
-        // Attempt operation with inactive Keep
-        context.set_caller(sgx_executor);
+        // Only the SEV executor keeps heartbeating
+        context.set_timestamp(last_heartbeat + TIMEOUT_INTERVAL + 1);
+        context.set_caller(sev_executor);
         submit_heartbeat(&mut context);
+
+        let expired = check_liveness(&mut context);
+        assert!(expired.contains(&sgx_executor));
+        assert!(!expired.contains(&sev_executor));
+
+        assert!(!context.get(AttestationStatus(sgx_executor)).unwrap().unwrap());
+        assert!(context.get(AttestationStatus(sev_executor)).unwrap().unwrap());
     }
 
     #[test]
-    fn test_keep_status_transitions() {
+    #[should_panic(expected = "attestation not valid")]
+    fn test_submission_blocked_after_liveness_expiry() {
         let mut context = setup();
         let (sgx_executor, _, _) = setup_system(&mut context);
 
+        let last_heartbeat = context.get(HeartbeatTimestamp(sgx_executor)).unwrap().unwrap();
+        context.set_timestamp(last_heartbeat + TIMEOUT_INTERVAL + 1);
+        check_liveness(&mut context);
+
         context.set_caller(sgx_executor);
+        submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+    }
 
-        // Test Keep pause
-        pause_keep(&mut context);
-        assert!(!context.get(KeepStatus(sgx_executor)).unwrap().unwrap());
+    #[test]
+    fn test_missed_heartbeats_accumulate() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
 
-        // Test Keep resume
-        resume_keep(&mut context);
-        assert!(context.get(KeepStatus(sgx_executor)).unwrap().unwrap());
+        let start = context.get(HeartbeatTimestamp(sgx_executor)).unwrap().unwrap();
+        for round in 1..=3 {
+            context.set_timestamp(start + round * (TIMEOUT_INTERVAL + 1));
+            check_liveness(&mut context);
+        }
+
+        assert_eq!(get_heartbeat_misses(&mut context, sgx_executor), 3);
+        assert_eq!(get_heartbeat_misses(&mut context, sev_executor), 3);
+        assert!(!has_reliable_liveness(&mut context, sgx_executor));
     }
 
     #[test]
-    fn test_concurrent_keep_operations() {
+    fn test_repeated_checks_count_one_miss_per_interval() {
         let mut context = setup();
-        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        let (sgx_executor, _, _) = setup_system(&mut context);
 
-        // Simulate concurrent operations from both Keeps
-        for executor in [sgx_executor, sev_executor].iter() {
-            context.set_caller(*executor);
-            
-            // Submit heartbeat
-            submit_heartbeat(&mut context);
-            
-            // Update measurement
-            update_keep_measurement(&mut context, vec![4u8; 32]);
-            
-            // Renew attestation
-            renew_attestation(
-                &mut context,
-                vec![5u8; 32],
-                vec![6u8; 64],
-            );
+        let start = context.get(HeartbeatTimestamp(sgx_executor)).unwrap().unwrap();
+        context.set_timestamp(start + TIMEOUT_INTERVAL + 1);
+        for _ in 0..5 {
+            check_liveness(&mut context);
         }
+        assert_eq!(get_heartbeat_misses(&mut context, sgx_executor), 1);
 
-        // Verify all operations were processed
-        for executor in [sgx_executor, sev_executor].iter() {
-            assert!(context.get(KeepStatus(*executor)).unwrap().unwrap());
-            assert!(context.get(LastAttestationTime(*executor)).unwrap().unwrap() > 0);
-            assert!(context.get(KeepMeasurement(*executor)).unwrap().is_some());
+        // A single late check still counts every interval that went unanswered
+        context.set_timestamp(start + 3 * TIMEOUT_INTERVAL + 1);
+        check_liveness(&mut context);
+        assert_eq!(get_heartbeat_misses(&mut context, sgx_executor), 3);
+    }
+
+    #[test]
+    fn test_sustained_heartbeats_reset_misses() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        let start = context.get(HeartbeatTimestamp(sgx_executor)).unwrap().unwrap();
+        let mut timestamp = start + TIMEOUT_INTERVAL + 1;
+        let mut block_height = context.block_height();
+        context.set_timestamp(timestamp);
+        check_liveness(&mut context);
+        assert_eq!(get_heartbeat_misses(&mut context, sgx_executor), 1);
+
+        context.set_caller(sgx_executor);
+        for beat in 0..=HEARTBEAT_RECOVERY_STREAK {
+            // The first heartbeat after the miss is late and starts a fresh streak
+            if beat > 0 {
+                timestamp += TIMEOUT_INTERVAL;
+            }
+            block_height += MIN_HEARTBEAT_SPACING;
+            context.set_timestamp(timestamp);
+            context.set_block_height(block_height);
+            submit_heartbeat(&mut context);
+
+            if beat < HEARTBEAT_RECOVERY_STREAK {
+                assert_eq!(get_heartbeat_misses(&mut context, sgx_executor), 1);
+            }
         }
+
+        assert_eq!(get_heartbeat_misses(&mut context, sgx_executor), 0);
     }
 }
 
@@ -375,18 +1757,9 @@ fn resume_keep(context: &mut Context) {
         .expect("failed to resume keep");
 }
 
-fn renew_attestation(context: &mut Context, attestation_report: Vec<u8>, drawbridge_token: Vec<u8>) {
-    let caller = context.actor();
-    assert!(
-        context.get(KeepStatus(caller)).unwrap().unwrap(),
-        "keep not active"
-    );
-    context.store_by_key(LastAttestationTime(caller), context.timestamp())
-        .expect("failed to update attestation time");
-}
-
 mod executor_verification {
     use super::*;
+    use crate::ATTESTATION_VALIDITY_PERIOD;
 
     #[test]
     fn test_executor_attestation() {
@@ -400,8 +1773,8 @@ mod executor_verification {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "sgx-keep-123".to_string(),
-            vec![1u8; 32], // Unique attestation data
-            vec![2u8; 64], // Unique Drawbridge token
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"), // Unique Drawbridge token
         );
 
         // Original verification
@@ -446,8 +1819,8 @@ mod executor_verification {
 
         // Submit new attestation
         context.set_caller(sgx_executor);
-        let new_attestation = vec![3u8; 32];
-        let new_token = vec![4u8; 64];
+        let new_attestation = attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR);
+        let new_token = drawbridge_token_for(SGX_OPERATOR);
         
         renew_attestation(
             &mut context,
@@ -488,7 +1861,7 @@ mod executor_verification {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "invalid-keep".to_string(),
-            vec![0u8; 32], // Invalid attestation
+            attestation_report_for(EnclaveType::IntelSGX, "invalid-keep"), // Invalid attestation
             vec![0u8; 64], // Invalid token
         );
     }
@@ -500,14 +1873,14 @@ mod executor_verification {
 
         // Register with valid Drawbridge token
         context.set_caller(sgx_executor);
-        let valid_token = vec![6u8; 64];
+        let valid_token = drawbridge_token_for("sgx-keep-123");
         
         register_executor(
             &mut context,
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "sgx-keep-123".to_string(),
-            vec![1u8; 32],
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
             valid_token.clone(),
         );
 
@@ -516,6 +1889,43 @@ mod executor_verification {
         assert_eq!(stored_token, valid_token);
     }
 
+    #[test]
+    fn test_drawbridge_token_bound_to_keep_id() {
+        let mut context = setup();
+        let sgx_executor = Address::from([3u8; 32]);
+
+        context.set_caller(sgx_executor);
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-123".to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"),
+        );
+
+        assert_eq!(
+            context.get(KeepId(sgx_executor)).unwrap().unwrap(),
+            "sgx-keep-123".to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid attestation")]
+    fn test_drawbridge_token_for_other_keep_rejected() {
+        let mut context = setup();
+        let sgx_executor = Address::from([3u8; 32]);
+
+        // Token issued for the SEV keep replayed against the SGX keep id
+        context.set_caller(sgx_executor);
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            "sgx-keep-123".to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sev-keep-456"),
+        );
+    }
+
     #[test]
     fn test_keep_status_verification() {
         let mut context = setup();
@@ -548,23 +1958,46 @@ mod executor_verification {
 
         // Verify attestation is expired
         context.set_caller(sgx_executor);
-        assert!(!is_attestation_valid(&mut context, sgx_executor));
+        assert!(!is_attestation_fresh(&mut context, sgx_executor));
     }
-}
 
-// Constants for verification
-const ATTESTATION_VALIDITY_PERIOD: u64 = 86400; // 24 hours in seconds
+    #[test]
+    fn test_sgx_expires_before_sev_with_shorter_period() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::AttestationValidity {
+                enclave_type: EnclaveType::IntelSGX,
+                period: 3600,
+            },
+        );
+        update_system_config(
+            &mut context,
+            ConfigUpdate::AttestationValidity {
+                enclave_type: EnclaveType::AMDSEV,
+                period: 7200,
+            },
+        );
+
+        let sgx_attested = context.get(LastAttestationTime(sgx_executor)).unwrap().unwrap();
+        let sev_attested = context.get(LastAttestationTime(sev_executor)).unwrap().unwrap();
+        assert_eq!(sgx_attested, sev_attested);
+
+        context.set_timestamp(sgx_attested + 3601);
+        assert!(!is_attestation_fresh(&mut context, sgx_executor));
+        assert!(is_attestation_fresh(&mut context, sev_executor));
 
-// Helper function for attestation validation
-fn is_attestation_valid(context: &mut Context, executor: Address) -> bool {
-    let last_attestation = context.get(LastAttestationTime(executor)).unwrap().unwrap();
-    let current_time = context.timestamp();
-    
-    current_time - last_attestation <= ATTESTATION_VALIDITY_PERIOD
+        context.set_timestamp(sgx_attested + 7201);
+        assert!(!is_attestation_fresh(&mut context, sev_executor));
+    }
 }
 
 mod executor_phase_transitions {
     use super::*;
+    use crate::ATTESTATION_VALIDITY_PERIOD;
 
     #[test]
     fn test_phase_transitions() {
@@ -581,8 +2014,8 @@ mod executor_phase_transitions {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "sgx-keep-123".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"),
         );
         assert_eq!(get_current_phase(&mut context), Phase::Creation);
 
@@ -594,8 +2027,8 @@ mod executor_phase_transitions {
             EnclaveType::AMDSEV,
             SEV_OPERATOR.to_string(),
             "sev-keep-456".to_string(),
-            vec![0u8; 32],
-            vec![0u8; 64],
+            attestation_report_for(EnclaveType::AMDSEV, "sev-keep-456"),
+            drawbridge_token_for("sev-keep-456"),
         );
         assert_eq!(get_current_phase(&mut context), Phase::Executing);
 
@@ -617,8 +2050,8 @@ mod executor_phase_transitions {
             EnclaveType::IntelSGX,
             SGX_OPERATOR.to_string(),
             "sgx-keep-123".to_string(),
-            vec![1u8; 32],
-            vec![1u8; 64],
+            attestation_report_for(EnclaveType::IntelSGX, "sgx-keep-123"),
+            drawbridge_token_for("sgx-keep-123"),
         );
 
         context.set_caller(sev_executor);
@@ -627,8 +2060,8 @@ mod executor_phase_transitions {
             EnclaveType::AMDSEV,
             SEV_OPERATOR.to_string(),
             "sev-keep-456".to_string(),
-            vec![2u8; 32],
-            vec![2u8; 64],
+            attestation_report_for(EnclaveType::AMDSEV, "sev-keep-456"),
+            drawbridge_token_for("sev-keep-456"),
         );
 
         // Verify all states after transition
@@ -722,10 +2155,12 @@ mod executor_phase_transitions {
         // Renew attestations
         for executor in [sgx_executor, sev_executor].iter() {
             context.set_caller(*executor);
+            let keep_id = context.get(KeepId(*executor)).unwrap().unwrap();
+            let enclave_type = get_enclave_type(&mut context, *executor).unwrap();
             renew_attestation(
                 &mut context,
-                vec![3u8; 32],
-                vec![3u8; 64],
+                attestation_report_for(enclave_type, &keep_id),
+                drawbridge_token_for(&keep_id),
             );
         }
 
@@ -734,7 +2169,7 @@ mod executor_phase_transitions {
 
         // Verify renewed attestations
         for executor in [sgx_executor, sev_executor].iter() {
-            assert!(is_attestation_valid(&mut context, *executor));
+            assert!(is_attestation_fresh(&mut context, *executor));
         }
     }
 
@@ -771,6 +2206,84 @@ mod executor_phase_transitions {
     }
 }
 
+mod transition_blockers {
+    use super::*;
+    use crate::MIN_WATCHDOGS;
+
+    #[test]
+    fn test_half_configured_system_blockers() {
+        let mut context = setup();
+
+        context.set_caller(Address::from([3u8; 32]));
+        register_executor(
+            &mut context,
+            EnclaveType::IntelSGX,
+            SGX_OPERATOR.to_string(),
+            attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+            drawbridge_token_for(SGX_OPERATOR),
+        );
+
+        register_watchdog_keep(
+            &mut context,
+            Address::from([5u8; 32]),
+            EnclaveType::IntelSGX,
+            "watchdog-keep",
+        );
+
+        assert_eq!(
+            why_not_executing(&mut context),
+            vec![
+                TransitionBlocker::MissingExecutor(EnclaveType::AMDSEV),
+                TransitionBlocker::InsufficientWatchdogs { registered: 1, required: MIN_WATCHDOGS },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_executors_wait_for_watchdog_quorum() {
+        let mut context = setup();
+        register_watchdog_keep(
+            &mut context,
+            Address::from([5u8; 32]),
+            EnclaveType::IntelSGX,
+            "watchdog-keep",
+        );
+
+        for (byte, enclave_type, keep_id) in [
+            (3u8, EnclaveType::IntelSGX, SGX_OPERATOR),
+            (4u8, EnclaveType::AMDSEV, SEV_OPERATOR),
+        ] {
+            context.set_caller(Address::from([byte; 32]));
+            register_executor(
+                &mut context,
+                enclave_type.clone(),
+                keep_id.to_string(),
+                attestation_report_for(enclave_type, keep_id),
+                drawbridge_token_for(keep_id),
+            );
+        }
+        assert_eq!(get_current_phase(&mut context), Phase::Creation);
+        assert_eq!(
+            why_not_executing(&mut context),
+            vec![TransitionBlocker::InsufficientWatchdogs { registered: 1, required: MIN_WATCHDOGS }]
+        );
+
+        for watchdog in supporting_watchdogs() {
+            register_watchdog_keep(&mut context, watchdog, EnclaveType::AMDSEV, "watchdog-keep");
+        }
+        assert_eq!(get_current_phase(&mut context), Phase::Executing);
+        assert!(why_not_executing(&mut context).is_empty());
+    }
+
+    #[test]
+    fn test_no_blockers_once_executing() {
+        let mut context = setup();
+        setup_full_system(&mut context);
+
+        assert!(why_not_executing(&mut context).is_empty());
+    }
+}
+
 // Helper function for phase transition verification
 fn verify_phase_transition(context: &mut Context) {
     let current_phase = get_current_phase(context);
@@ -0,0 +1,264 @@
+use super::common::*;
+use crate::{types::*, state::*};
+
+mod system_config {
+    use super::*;
+
+    #[test]
+    fn test_init_stores_default_config() {
+        let mut context = setup();
+
+        let config = context.get(SystemConfig()).unwrap().expect("config should be stored");
+        assert_eq!(config, SystemConfig::default());
+    }
+
+    #[test]
+    fn test_init_with_config_overrides() {
+        let mut context = setup_test();
+        let overrides = SystemConfig {
+            min_stake: 5_000,
+            ..SystemConfig::default()
+        };
+
+        init(
+            &mut context,
+            SGX_OPERATOR.to_string(),
+            SEV_OPERATOR.to_string(),
+            Address::from([1u8; 32]),
+            governance_address(),
+            Some(overrides.clone()),
+            operator_signature(),
+            operator_signature(),
+        );
+
+        assert_eq!(get_system_config(&mut context), overrides);
+    }
+
+    #[test]
+    fn test_governance_updates_single_field() {
+        let mut context = setup();
+        let before = get_system_config(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MinStake(2_500));
+
+        let after = get_system_config(&mut context);
+        assert_eq!(after.min_stake, 2_500);
+        assert_eq!(
+            after,
+            SystemConfig {
+                min_stake: 2_500,
+                ..before
+            }
+        );
+    }
+
+    #[test]
+    fn test_governance_updates_verification_mode() {
+        let mut context = setup();
+        let before = get_system_config(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::VerificationMode(VerificationMode::RequireBundle),
+        );
+
+        assert_eq!(
+            get_system_config(&mut context),
+            SystemConfig {
+                verification_mode: VerificationMode::RequireBundle,
+                ..before
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_config_update_requires_governance() {
+        let mut context = setup();
+
+        context.set_caller(Address::from([9u8; 32]));
+        update_system_config(&mut context, ConfigUpdate::MinStake(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid quorum")]
+    fn test_invalid_quorum_rejected() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::Quorum { numerator: 2, denominator: 0 },
+        );
+    }
+}
+
+mod state_migration {
+    use super::*;
+    use crate::MIGRATION_ROLLBACK_WINDOW;
+
+    fn setup_orphaned_ready_tee(context: &mut TestContext) -> Address {
+        let tee = Address::from([20u8; 32]);
+        register_ready_tee_keep(context, tee, EnclaveType::IntelSGX, "sgx-keep-ready").unwrap();
+
+        // Legacy deregistration cleared the keep but left the pool entry
+        context.remove(KeepId(tee)).unwrap();
+        tee
+    }
+
+    #[test]
+    fn test_migration_drops_orphaned_ready_tees() {
+        let mut context = setup();
+        setup_system(&mut context);
+        let tee = setup_orphaned_ready_tee(&mut context);
+
+        context.set_caller(governance_address());
+        assert_eq!(migrate_state(&mut context), 1);
+        assert_eq!(get_state_version(&mut context), 1);
+
+        let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert!(!watchdog_pool.ready_tees.iter().any(|(addr, _)| *addr == tee));
+    }
+
+    #[test]
+    fn test_rollback_restores_pools() {
+        let mut context = setup();
+        setup_system(&mut context);
+        setup_orphaned_ready_tee(&mut context);
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
+
+        context.set_caller(governance_address());
+        migrate_state(&mut context);
+        rollback_migration(&mut context);
+
+        assert_eq!(get_state_version(&mut context), 0);
+        assert_eq!(
+            format!("{:?}", context.get(ExecutorPool()).unwrap().unwrap()),
+            format!("{:?}", executor_pool)
+        );
+        assert_eq!(
+            format!("{:?}", context.get(WatchdogPool()).unwrap().unwrap()),
+            format!("{:?}", watchdog_pool)
+        );
+        assert!(context.get(MigrationBackup()).unwrap().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "rollback window elapsed")]
+    fn test_rollback_after_window_rejected() {
+        let mut context = setup();
+        setup_system(&mut context);
+
+        context.set_caller(governance_address());
+        migrate_state(&mut context);
+
+        context.set_timestamp(context.timestamp() + MIGRATION_ROLLBACK_WINDOW + 1);
+        rollback_migration(&mut context);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_migration_requires_governance() {
+        let mut context = setup();
+
+        context.set_caller(Address::from([9u8; 32]));
+        migrate_state(&mut context);
+    }
+}
+
+mod domain_governance {
+    use super::*;
+
+    fn security_governance() -> Address {
+        Address::from([30u8; 32])
+    }
+
+    fn economic_governance() -> Address {
+        Address::from([31u8; 32])
+    }
+
+    fn setup_domains() -> TestContext {
+        let mut context = setup();
+        context.set_caller(governance_address());
+        set_domain_governance(&mut context, GovernanceDomain::Security, security_governance());
+        set_domain_governance(&mut context, GovernanceDomain::Economic, economic_governance());
+        context
+    }
+
+    fn economic_proposal(context: &mut TestContext) -> u128 {
+        let (sgx_executor, _, _) = setup_system(context);
+
+        context.set_caller(sgx_executor);
+        create_governance_proposal(context, GovernanceDomain::Economic, vec![1u8], vec![2u8; 8])
+    }
+
+    #[test]
+    fn test_domain_governance_executes_own_decision() {
+        let mut context = setup_domains();
+        let proposal_id = economic_proposal(&mut context);
+
+        context.set_caller(economic_governance());
+        execute_governance_decision(&mut context, proposal_id, vec![1u8; 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized executor")]
+    fn test_security_governance_cannot_execute_economic_decision() {
+        let mut context = setup_domains();
+        let proposal_id = economic_proposal(&mut context);
+
+        context.set_caller(security_governance());
+        execute_governance_decision(&mut context, proposal_id, vec![1u8; 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized executor")]
+    fn test_decision_executed_once() {
+        let mut context = setup_domains();
+        let proposal_id = economic_proposal(&mut context);
+
+        context.set_caller(economic_governance());
+        execute_governance_decision(&mut context, proposal_id, vec![1u8; 8]);
+        execute_governance_decision(&mut context, proposal_id, vec![1u8; 8]);
+    }
+
+    #[test]
+    fn test_unrecorded_proposal_executed_by_primary_governance() {
+        let mut context = setup_domains();
+
+        context.set_caller(governance_address());
+        execute_governance_decision(&mut context, 42, vec![1u8; 8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized executor")]
+    fn test_domain_governance_cannot_execute_unrecorded_proposal() {
+        let mut context = setup_domains();
+
+        context.set_caller(economic_governance());
+        execute_governance_decision(&mut context, 42, vec![1u8; 8]);
+    }
+
+    #[test]
+    fn test_unassigned_domain_falls_back_to_primary_governance() {
+        let mut context = setup();
+
+        assert_eq!(
+            get_domain_governance(&mut context, GovernanceDomain::Security),
+            governance_address()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_domain_assignment_requires_governance() {
+        let mut context = setup();
+
+        context.set_caller(security_governance());
+        set_domain_governance(&mut context, GovernanceDomain::Security, security_governance());
+    }
+}
@@ -1,5 +1,11 @@
 use super::common::*;
-use crate::types::Phase;
+use crate::{
+    types::{Phase, StateSummary, SystemHealth, EnclaveType, SystemConfig},
+    core::init_message,
+    state::*,
+    execution::*,
+    CRASH_RECOVERY_COOLDOWN,
+};
 
 #[test]
 fn test_initialization() {
@@ -34,6 +40,31 @@ fn test_initialization() {
     assert!(watchdog_pool.watchdogs.is_empty());
 }
 
+#[test]
+fn test_get_operator_reads_registered_operators() {
+    let mut context = setup();
+
+    for operator_id in [SGX_OPERATOR, SEV_OPERATOR] {
+        let operator = get_operator(&mut context, operator_id.to_string())
+            .expect("operator should be registered");
+        assert!(operator.initialized);
+        assert_eq!(operator.tee_signature_address, operator_id);
+        assert_eq!(operator.challenges_responded, 0);
+    }
+
+    assert!(get_operator(&mut context, "unknown".to_string()).is_none());
+}
+
+#[test]
+fn test_list_operators_returns_both_ids() {
+    let mut context = setup();
+
+    assert_eq!(
+        list_operators(&mut context),
+        vec![SGX_OPERATOR.to_string(), SEV_OPERATOR.to_string()]
+    );
+}
+
 #[test]
 #[should_panic(expected = "system already initialized")]
 fn test_double_initialization() {
@@ -44,6 +75,9 @@ fn test_double_initialization() {
         SEV_OPERATOR.to_string(),
         Address::from([1u8; 32]),
         Address::from([2u8; 32]),
+        None,
+        operator_signature(),
+        operator_signature(),
     );
 }
 
@@ -65,3 +99,216 @@ fn test_system_stats_initial() {
     assert_eq!(challenges, 0);
     assert!(last_update > 0);
 }
+
+#[test]
+fn test_co_signed_initialization() {
+    let mut context = setup_test();
+    init(
+        &mut context,
+        SGX_OPERATOR.to_string(),
+        SEV_OPERATOR.to_string(),
+        Address::from([1u8; 32]),
+        Address::from([2u8; 32]),
+        None,
+        operator_signature(),
+        operator_signature(),
+    );
+
+    assert_eq!(get_current_phase(&mut context), Phase::Creation);
+}
+
+#[test]
+#[should_panic(expected = "missing operator co-signature")]
+fn test_initialization_missing_sev_signature() {
+    let mut context = setup_test();
+    init(
+        &mut context,
+        SGX_OPERATOR.to_string(),
+        SEV_OPERATOR.to_string(),
+        Address::from([1u8; 32]),
+        Address::from([2u8; 32]),
+        None,
+        operator_signature(),
+        Vec::new(),
+    );
+}
+
+#[test]
+fn test_init_message_covers_config_overrides() {
+    let overrides = Some(SystemConfig {
+        min_stake: 5_000,
+        ..SystemConfig::default()
+    });
+    let token_contract = Address::from([1u8; 32]);
+
+    assert_ne!(
+        init_message(SGX_OPERATOR, SEV_OPERATOR, token_contract, governance_address(), &None),
+        init_message(SGX_OPERATOR, SEV_OPERATOR, token_contract, governance_address(), &overrides),
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid quorum")]
+fn test_initialization_rejects_zero_quorum_denominator() {
+    let mut context = setup_test();
+    init(
+        &mut context,
+        SGX_OPERATOR.to_string(),
+        SEV_OPERATOR.to_string(),
+        Address::from([1u8; 32]),
+        Address::from([2u8; 32]),
+        Some(SystemConfig {
+            quorum_denominator: 0,
+            ..SystemConfig::default()
+        }),
+        operator_signature(),
+        operator_signature(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "timeout interval must be positive")]
+fn test_initialization_rejects_zero_timeout_interval() {
+    let mut context = setup_test();
+    init(
+        &mut context,
+        SGX_OPERATOR.to_string(),
+        SEV_OPERATOR.to_string(),
+        Address::from([1u8; 32]),
+        Address::from([2u8; 32]),
+        Some(SystemConfig {
+            timeout_interval: 0,
+            ..SystemConfig::default()
+        }),
+        operator_signature(),
+        operator_signature(),
+    );
+}
+
+#[test]
+fn test_default_config_is_valid() {
+    SystemConfig::default().validate();
+}
+
+#[test]
+fn test_init_message_fields_are_delimited() {
+    let token_contract = Address::from([1u8; 32]);
+
+    assert_ne!(
+        init_message("ab", "c", token_contract, governance_address(), &None),
+        init_message("a", "bc", token_contract, governance_address(), &None),
+    );
+}
+
+#[test]
+#[should_panic(expected = "operators must be distinct")]
+fn test_initialization_with_identical_operators() {
+    let mut context = setup_test();
+    init(
+        &mut context,
+        SGX_OPERATOR.to_string(),
+        SGX_OPERATOR.to_string(),
+        Address::from([1u8; 32]),
+        Address::from([2u8; 32]),
+        None,
+        operator_signature(),
+        operator_signature(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "crash cool-down active")]
+fn test_recovery_during_cooldown_rejected() {
+    let mut context = setup();
+    enter_crashed_phase(&mut context);
+
+    context.set_timestamp(context.timestamp() + CRASH_RECOVERY_COOLDOWN - 1);
+    context.set_caller(governance_address());
+    recover_from_crash(&mut context);
+}
+
+#[test]
+fn test_recovery_after_cooldown() {
+    let mut context = setup();
+    enter_crashed_phase(&mut context);
+
+    context.set_timestamp(context.timestamp() + CRASH_RECOVERY_COOLDOWN);
+    context.set_caller(governance_address());
+    recover_from_crash(&mut context);
+
+    assert_eq!(get_current_phase(&mut context), Phase::Creation);
+    assert!(context.get(CrashedAt()).unwrap().is_none());
+}
+
+#[test]
+fn test_state_summary_reflects_configured_system() {
+    let mut context = setup();
+    let (sgx_executor, sev_executor, watchdog) = setup_system(&mut context);
+
+    context.set_caller(sgx_executor);
+    submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+    context.set_caller(sev_executor);
+    submit_execution_result(&mut context, 1u128, vec![1u8; 32], payload_hash(), 1);
+
+    let summary = export_state_summary(&mut context);
+    assert_eq!(
+        summary,
+        StateSummary {
+            phase: context.get(CurrentPhase()).unwrap().unwrap(),
+            sgx_executor: Some(sgx_executor),
+            sev_executor: Some(sev_executor),
+            watchdogs: [vec![watchdog], supporting_watchdogs()].concat(),
+            challenge_count: 0,
+            verified_execution_count: 1,
+            state_root: context.get(StateRoot()).unwrap().unwrap_or_default(),
+        }
+    );
+}
+
+#[test]
+fn test_health_of_ready_system() {
+    let mut context = setup();
+    setup_full_system(&mut context);
+
+    assert_eq!(
+        system_health(&mut context),
+        SystemHealth {
+            phase: Phase::Executing,
+            executors_ready: true,
+            watchdogs_sufficient: true,
+            paused: false,
+        }
+    );
+}
+
+#[test]
+fn test_health_of_system_missing_executor() {
+    let mut context = setup();
+
+    context.set_caller(Address::from([3u8; 32]));
+    register_executor(
+        &mut context,
+        EnclaveType::IntelSGX,
+        SGX_OPERATOR.to_string(),
+        attestation_report_for(EnclaveType::IntelSGX, SGX_OPERATOR),
+        drawbridge_token_for(SGX_OPERATOR),
+    );
+
+    for i in 0..3 {
+        context.set_caller(Address::from([(i + 5) as u8; 32]));
+        let enclave_type = if i % 2 == 0 { EnclaveType::IntelSGX } else { EnclaveType::AMDSEV };
+        register_watchdog(
+            &mut context,
+            enclave_type.clone(),
+            "watchdog-keep".to_string(),
+            attestation_report_for(enclave_type, "watchdog-keep"),
+            drawbridge_token_for("watchdog-keep"),
+            vec![0u8; 64],
+        );
+    }
+
+    let health = system_health(&mut context);
+    assert_eq!(health.phase, Phase::Creation);
+    assert!(!health.executors_ready);
+    assert!(health.watchdogs_sufficient);
+}
@@ -0,0 +1,544 @@
+use super::common::*;
+use crate::{types::*, state::*};
+
+mod reward_reserve {
+    use super::*;
+
+    #[test]
+    fn test_approved_reserve_withdrawal() {
+        let mut context = setup();
+        let (_, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        distribute_rewards(&mut context);
+        let reserve = get_reward_reserve(&mut context);
+        assert!(reserve > 0);
+
+        let recipient = Address::from([20u8; 32]);
+        let before = get_token_balance(&mut context, recipient);
+
+        context.set_caller(governance_address());
+        withdraw_reserve(&mut context, recipient, reserve / 2);
+
+        assert_eq!(get_reward_reserve(&mut context), reserve - reserve / 2);
+        assert_eq!(get_token_balance(&mut context, recipient), before + reserve / 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "amount exceeds reserve")]
+    fn test_withdrawal_exceeding_reserve() {
+        let mut context = setup();
+        let (_, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        distribute_rewards(&mut context);
+        let reserve = get_reward_reserve(&mut context);
+
+        context.set_caller(governance_address());
+        withdraw_reserve(&mut context, Address::from([20u8; 32]), reserve + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "reserve withdrawal transfer failed")]
+    fn test_failed_reserve_transfer_aborts() {
+        let mut context = setup();
+        setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        // Book more reserve than the contract holds so the transfer fails
+        context.store_by_key(RewardReserve(), 10_000_000u64).unwrap();
+
+        context.set_caller(governance_address());
+        withdraw_reserve(&mut context, Address::from([20u8; 32]), 10_000_000);
+    }
+
+    #[test]
+    fn test_stakes_excluded_from_reserve() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(sgx_executor);
+        stake_tokens(&mut context, 1_000);
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 1_000);
+
+        distribute_rewards(&mut context);
+
+        let total = get_total_staked(&mut context);
+        assert!(get_reward_reserve(&mut context) + 1_000 <= total);
+    }
+}
+
+mod reward_claims {
+    use super::*;
+
+    #[test]
+    fn test_claim_zeroes_ledger() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        distribute_rewards(&mut context);
+        let pending = get_pending_reward(&mut context, sgx_executor);
+        assert!(pending > 0);
+        let before = get_token_balance(&mut context, sgx_executor);
+
+        context.set_caller(sgx_executor);
+        assert_eq!(claim_rewards(&mut context), pending);
+
+        assert_eq!(get_pending_reward(&mut context, sgx_executor), 0);
+        assert_eq!(get_token_balance(&mut context, sgx_executor), before + pending);
+    }
+
+    #[test]
+    #[should_panic(expected = "no rewards to claim")]
+    fn test_retried_claim_does_not_pay_twice() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        distribute_rewards(&mut context);
+
+        context.set_caller(sgx_executor);
+        claim_rewards(&mut context);
+        claim_rewards(&mut context);
+    }
+
+    #[test]
+    fn test_failed_transfer_restores_claimable_amount() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        // Credit more than the contract holds so the transfer fails
+        let amount = 10_000_000;
+        context
+            .store((
+                (PendingReward(sgx_executor), amount),
+                (TotalPendingRewards(), amount),
+            ))
+            .unwrap();
+        let before = get_token_balance(&mut context, sgx_executor);
+
+        context.set_caller(sgx_executor);
+        assert_eq!(claim_rewards(&mut context), 0);
+
+        assert_eq!(get_pending_reward(&mut context, sgx_executor), amount);
+        assert_eq!(context.get(TotalPendingRewards()).unwrap().unwrap(), amount);
+        assert_eq!(get_token_balance(&mut context, sgx_executor), before);
+    }
+}
+
+mod weighted_rewards {
+    use super::*;
+
+    #[test]
+    fn test_executor_reward_weighted_by_executions() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context
+            .store((
+                (KeepExecutionCount(sgx_executor), 3),
+                (KeepExecutionCount(sev_executor), 1),
+            ))
+            .unwrap();
+
+        distribute_rewards(&mut context);
+
+        let sgx_reward = get_pending_reward(&mut context, sgx_executor);
+        let sev_reward = get_pending_reward(&mut context, sev_executor);
+        assert!(sev_reward > 0);
+        assert_eq!(sgx_reward, 3 * sev_reward);
+    }
+
+    #[test]
+    fn test_equal_split_without_executions() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        distribute_rewards(&mut context);
+
+        let sgx_reward = get_pending_reward(&mut context, sgx_executor);
+        assert!(sgx_reward > 0);
+        assert_eq!(sgx_reward, get_pending_reward(&mut context, sev_executor));
+    }
+}
+
+mod minimum_distribution {
+    use super::*;
+    use crate::MIN_DISTRIBUTABLE_REWARD;
+
+    #[test]
+    fn test_dust_balance_not_distributed() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdog) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        // Leave only a dust amount above the reserve
+        let balance = get_total_staked(&mut context);
+        context
+            .store_by_key(RewardReserve(), balance - (MIN_DISTRIBUTABLE_REWARD - 1))
+            .unwrap();
+
+        distribute_rewards(&mut context);
+
+        assert_eq!(get_pending_reward(&mut context, sgx_executor), 0);
+        assert_eq!(get_pending_reward(&mut context, sev_executor), 0);
+        assert_eq!(get_pending_reward(&mut context, watchdog), 0);
+        assert!(context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "RewardDistributionSkipped"));
+    }
+
+    #[test]
+    fn test_sufficient_balance_distributed() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdog) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        distribute_rewards(&mut context);
+
+        assert!(get_pending_reward(&mut context, sgx_executor) > 0);
+        assert!(get_pending_reward(&mut context, sev_executor) > 0);
+        assert!(get_pending_reward(&mut context, watchdog) > 0);
+        assert!(!context
+            .events()
+            .iter()
+            .any(|(name, _)| name == "RewardDistributionSkipped"));
+    }
+}
+
+mod stake_handoff {
+    use super::*;
+
+    fn replace_staked_executor(context: &mut TestContext, policy: StakeHandoffPolicy) -> (Address, Address) {
+        let (sgx_executor, _, _) = setup_system(context);
+        setup_with_token_contract(context);
+
+        context.set_caller(sgx_executor);
+        stake_tokens(context, 1_000);
+
+        let tee = Address::from([20u8; 32]);
+        register_ready_tee_keep(context, tee, EnclaveType::IntelSGX, "sgx-keep-ready").unwrap();
+
+        context.set_caller(governance_address());
+        update_system_config(context, ConfigUpdate::StakeHandoffPolicy(policy));
+
+        fail_executor(context, sgx_executor);
+        replace_executor(context, sgx_executor).unwrap();
+        (sgx_executor, tee)
+    }
+
+    #[test]
+    fn test_slash_and_redistribute() {
+        let mut context = setup();
+        let reserve_before = get_reward_reserve(&mut context);
+
+        let (failed, replacement) =
+            replace_staked_executor(&mut context, StakeHandoffPolicy::SlashAndRedistribute);
+
+        assert_eq!(get_staked_amount(&mut context, failed), 0);
+        assert_eq!(get_staked_amount(&mut context, replacement), 0);
+        assert_eq!(get_total_staked(&mut context), 0);
+        assert_eq!(get_reward_reserve(&mut context), reserve_before + 1_000);
+    }
+
+    #[test]
+    fn test_transfer_to_replacement() {
+        let mut context = setup();
+        let reserve_before = get_reward_reserve(&mut context);
+
+        let (failed, replacement) =
+            replace_staked_executor(&mut context, StakeHandoffPolicy::TransferToReplacement);
+
+        assert_eq!(get_staked_amount(&mut context, failed), 0);
+        assert_eq!(get_staked_amount(&mut context, replacement), 1_000);
+        assert_eq!(get_total_staked(&mut context), 1_000);
+        assert_eq!(get_reward_reserve(&mut context), reserve_before);
+    }
+}
+
+mod token_migration {
+    use super::*;
+
+    fn migration_plan(expected_obligations: u64) -> TokenMigrationPlan {
+        TokenMigrationPlan {
+            expected_obligations,
+            old_balance_recipient: Address::from([21u8; 32]),
+        }
+    }
+
+    #[test]
+    fn test_token_contract_address_updated() {
+        let mut context = setup();
+        setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        let new_contract = Address::from([22u8; 32]);
+        context.set_caller(governance_address());
+        migrate_token_contract(&mut context, new_contract, migration_plan(0));
+
+        assert_eq!(context.get(TokenContract()).unwrap().unwrap(), new_contract);
+    }
+
+    #[test]
+    #[should_panic(expected = "migration plan does not match ledger")]
+    fn test_unreconciled_migration_refused() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(sgx_executor);
+        stake_tokens(&mut context, 1_000);
+
+        context.set_caller(governance_address());
+        migrate_token_contract(&mut context, Address::from([22u8; 32]), migration_plan(0));
+    }
+
+    #[test]
+    fn test_stakes_refunded_on_migration() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(sgx_executor);
+        stake_tokens(&mut context, 1_000);
+
+        context.set_caller(governance_address());
+        migrate_token_contract(&mut context, Address::from([22u8; 32]), migration_plan(1_000));
+
+        assert_eq!(get_staked_amount(&mut context, sgx_executor), 0);
+        assert_eq!(context.get(TotalStaked()).unwrap().unwrap_or(0), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "stake ledger not covered by participants")]
+    fn test_migration_rejects_stake_outside_pools() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(sgx_executor);
+        stake_tokens(&mut context, 1_000);
+
+        // A former participant's stake still on the ledger
+        let departed = Address::from([30u8; 32]);
+        context
+            .store((
+                (StakedAmount(departed), 500u64),
+                (TotalStaked(), 1_500u64),
+            ))
+            .unwrap();
+
+        context.set_caller(governance_address());
+        migrate_token_contract(&mut context, Address::from([22u8; 32]), migration_plan(1_500));
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized governance call")]
+    fn test_migration_requires_governance() {
+        let mut context = setup();
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(Address::from([9u8; 32]));
+        migrate_token_contract(&mut context, Address::from([22u8; 32]), migration_plan(0));
+    }
+}
+
+mod uptime_rewards {
+    use super::*;
+    use crate::{TIMEOUT_INTERVAL, UPTIME_BASIS_POINTS, MIN_HEARTBEAT_SPACING};
+
+    #[test]
+    fn test_full_uptime_earns_more_than_half_uptime() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(
+            &mut context,
+            ConfigUpdate::UptimeMultipliers(vec![(0, 0), (5_000, 5_000), (10_000, 10_000)]),
+        );
+
+        // Ten heartbeats are expected over the period
+        let period_start = context.get(RewardPeriodStart()).unwrap().unwrap();
+        context.set_timestamp(period_start + 10 * TIMEOUT_INTERVAL);
+        context
+            .store((
+                (PeriodHeartbeats(sgx_executor), 10),
+                (PeriodHeartbeats(sev_executor), 5),
+            ))
+            .unwrap();
+        assert_eq!(get_uptime_ratio(&mut context, sgx_executor), UPTIME_BASIS_POINTS);
+        assert_eq!(get_uptime_ratio(&mut context, sev_executor), UPTIME_BASIS_POINTS / 2);
+
+        // Both executors have the same base share
+        distribute_rewards(&mut context);
+
+        let sgx_reward = get_pending_reward(&mut context, sgx_executor);
+        let sev_reward = get_pending_reward(&mut context, sev_executor);
+        assert!(sev_reward > 0);
+        assert_eq!(sev_reward, sgx_reward / 2);
+        assert_eq!(get_uptime_ratio(&mut context, sgx_executor), 0);
+    }
+
+    #[test]
+    fn test_heartbeats_within_one_interval_count_once() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        let period_start = context.get(RewardPeriodStart()).unwrap().unwrap();
+        context.set_timestamp(period_start + 3 * TIMEOUT_INTERVAL);
+        context.set_caller(sgx_executor);
+        for _ in 0..3 {
+            submit_heartbeat(&mut context);
+            context.set_block_height(context.block_height() + MIN_HEARTBEAT_SPACING);
+        }
+        assert_eq!(context.get(PeriodHeartbeats(sgx_executor)).unwrap(), Some(1));
+
+        context.set_timestamp(period_start + 4 * TIMEOUT_INTERVAL);
+        submit_heartbeat(&mut context);
+        assert_eq!(context.get(PeriodHeartbeats(sgx_executor)).unwrap(), Some(2));
+        assert_eq!(get_uptime_ratio(&mut context, sgx_executor), UPTIME_BASIS_POINTS / 2);
+    }
+
+    #[test]
+    fn test_permissionless_distribution_keeps_uptime_window() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        let period_start = context.get(RewardPeriodStart()).unwrap().unwrap();
+        context.set_timestamp(period_start + 10 * TIMEOUT_INTERVAL);
+        context.store_by_key(PeriodHeartbeats(sgx_executor), 10).unwrap();
+
+        context.set_caller(sgx_executor);
+        distribute_rewards(&mut context);
+
+        assert_eq!(context.get(RewardPeriodStart()).unwrap(), Some(period_start));
+        assert_eq!(get_uptime_ratio(&mut context, sgx_executor), UPTIME_BASIS_POINTS);
+    }
+}
+
+mod balance_breakdown {
+    use super::*;
+
+    #[test]
+    fn test_categories_sum_to_balance() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(sgx_executor);
+        stake_tokens(&mut context, 1_000);
+        context.set_caller(sev_executor);
+        stake_tokens(&mut context, 500);
+
+        distribute_rewards(&mut context);
+
+        let breakdown = get_balance_breakdown(&mut context);
+        assert_eq!(breakdown.staked, 1_500);
+        assert!(breakdown.reserve > 0);
+        assert!(breakdown.pending_rewards > 0);
+        assert_eq!(
+            breakdown.staked + breakdown.reserve + breakdown.pending_rewards,
+            get_total_staked(&mut context)
+        );
+    }
+}
+
+mod stake_table {
+    use super::*;
+
+    #[test]
+    fn test_all_participant_stakes_listed() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, watchdog) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        for (participant, amount) in [(sgx_executor, 1_000), (sev_executor, 2_000), (watchdog, 500)] {
+            context.set_caller(participant);
+            stake_tokens(&mut context, amount);
+        }
+
+        let stakes = get_all_stakes(&mut context, 0, 3);
+        assert_eq!(
+            stakes,
+            vec![(sgx_executor, 1_000), (sev_executor, 2_000), (watchdog, 500)]
+        );
+    }
+
+    #[test]
+    fn test_stake_table_paginated() {
+        let mut context = setup();
+        let (_, sev_executor, watchdog) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(watchdog);
+        stake_tokens(&mut context, 500);
+
+        assert_eq!(
+            get_all_stakes(&mut context, 1, 2),
+            vec![(sev_executor, 0), (watchdog, 500)]
+        );
+        assert_eq!(get_all_stakes(&mut context, 1, 1), vec![(sev_executor, 0)]);
+    }
+}
+
+mod automatic_distribution {
+    use super::*;
+    use crate::execution::submit_execution_result;
+
+    fn submit_verified(context: &mut TestContext, sgx_executor: Address, sev_executor: Address, execution_id: u128) {
+        context.set_caller(sgx_executor);
+        submit_execution_result(context, execution_id, vec![1u8; 32], payload_hash(), 1);
+        context.set_caller(sev_executor);
+        submit_execution_result(context, execution_id, vec![1u8; 32], payload_hash(), 1);
+    }
+
+    #[test]
+    fn test_distribution_after_configured_executions() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::RewardCadence(RewardCadence::VerifiedExecutions(3)));
+
+        submit_verified(&mut context, sgx_executor, sev_executor, 1);
+        submit_verified(&mut context, sgx_executor, sev_executor, 2);
+        assert_eq!(get_pending_reward(&mut context, sgx_executor), 0);
+        assert_eq!(get_reward_reserve(&mut context), 0);
+
+        submit_verified(&mut context, sgx_executor, sev_executor, 3);
+        assert!(get_pending_reward(&mut context, sgx_executor) > 0);
+        assert!(get_reward_reserve(&mut context) > 0);
+    }
+
+    #[test]
+    fn test_manual_cadence_never_distributes() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        setup_with_token_contract(&mut context);
+
+        for execution_id in 1..=5 {
+            submit_verified(&mut context, sgx_executor, sev_executor, execution_id);
+        }
+
+        assert_eq!(get_pending_reward(&mut context, sgx_executor), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid reward cadence")]
+    fn test_zero_cadence_rejected() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::RewardCadence(RewardCadence::VerifiedExecutions(0)));
+    }
+}
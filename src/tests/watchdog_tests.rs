@@ -14,28 +14,25 @@ mod watchdog_registration {
         let watchdog3 = Address::from([7u8; 32]);
 
         // Register multiple watchdogs of different types
-        context.set_caller(watchdog1);
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            watchdog1,
             EnclaveType::IntelSGX,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
 
-        context.set_caller(watchdog2);
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            watchdog2,
             EnclaveType::AMDSEV,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
 
-        context.set_caller(watchdog3);
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            watchdog3,
             EnclaveType::IntelSGX,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
 
         // Verify watchdog registrations
@@ -62,12 +59,11 @@ mod watchdog_registration {
 
         // Register in Creation phase
         let watchdog1 = Address::from([5u8; 32]);
-        context.set_caller(watchdog1);
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            watchdog1,
             EnclaveType::IntelSGX,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
 
         // Setup executors to transition to Executing phase
@@ -75,37 +71,70 @@ mod watchdog_registration {
 
         // Register in Executing phase
         let watchdog2 = Address::from([6u8; 32]);
-        context.set_caller(watchdog2);
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            watchdog2,
             EnclaveType::AMDSEV,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
 
         let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
         assert_eq!(watchdog_pool.watchdogs.len(), 2);
     }
 
+    #[test]
+    fn test_watchdog_keep_id_and_measurement_stored() {
+        let mut context = setup();
+        let watchdog = Address::from([5u8; 32]);
+
+        register_watchdog_keep(
+            &mut context,
+            watchdog,
+            EnclaveType::AMDSEV,
+            "sev-watchdog-keep",
+        );
+
+        assert_eq!(context.get(KeepId(watchdog)).unwrap().unwrap(), "sev-watchdog-keep");
+        assert_eq!(
+            context.get(KeepMeasurement(watchdog)).unwrap().unwrap(),
+            measurement_for(EnclaveType::AMDSEV, "sev-watchdog-keep")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid attestation")]
+    fn test_watchdog_token_for_other_keep_rejected() {
+        let mut context = setup();
+
+        context.set_caller(Address::from([5u8; 32]));
+        register_watchdog(
+            &mut context,
+            EnclaveType::AMDSEV,
+            "sev-watchdog-keep".to_string(),
+            attestation_report_for(EnclaveType::AMDSEV, "sev-watchdog-keep"),
+            drawbridge_token_for("other-keep"),
+            vec![0u8; 64],
+        );
+    }
+
     #[test]
     #[should_panic(expected = "watchdog already registered")]
     fn test_duplicate_watchdog_registration() {
         let mut context = setup();
         let watchdog = Address::from([5u8; 32]);
 
-        context.set_caller(watchdog);
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            watchdog,
             EnclaveType::IntelSGX,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
 
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            watchdog,
             EnclaveType::IntelSGX,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
     }
 }
@@ -132,13 +161,15 @@ mod watchdog_operations {
 
         context.set_caller(watchdog);
         
-        let attestation_report = vec![1u8; 32];
+        let attestation_report = attestation_report_for(EnclaveType::IntelSGX, "watchdog-keep");
         let tee_signature = vec![2u8; 64];
 
         register_watchdog(
             &mut context,
             EnclaveType::IntelSGX,
+            "watchdog-keep".to_string(),
             attestation_report.clone(),
+            drawbridge_token_for("watchdog-keep"),
             tee_signature.clone(),
         );
 
@@ -203,12 +234,11 @@ mod watchdog_executor_replacement {
 
         // Register additional SEV watchdog
         let sev_watchdog = Address::from([6u8; 32]);
-        context.set_caller(sev_watchdog);
-        register_watchdog(
+        register_watchdog_keep(
             &mut context,
+            sev_watchdog,
             EnclaveType::AMDSEV,
-            vec![0u8; 32],
-            vec![0u8; 64],
+            "watchdog-keep",
         );
 
         // Create challenge
@@ -238,10 +268,13 @@ mod watchdog_pool_management {
         for i in 0..10 {
             let watchdog = Address::from([i as u8 + 10; 32]);
             context.set_caller(watchdog);
+            let enclave_type = if i % 2 == 0 { EnclaveType::IntelSGX } else { EnclaveType::AMDSEV };
             register_watchdog(
                 &mut context,
-                if i % 2 == 0 { EnclaveType::IntelSGX } else { EnclaveType::AMDSEV },
-                vec![0u8; 32],
+                enclave_type.clone(),
+                "watchdog-keep".to_string(),
+                attestation_report_for(enclave_type, "watchdog-keep"),
+                drawbridge_token_for("watchdog-keep"),
                 vec![0u8; 64],
             );
             watchdogs.push(watchdog);
@@ -276,10 +309,13 @@ mod watchdog_pool_management {
 
         for (i, &watchdog) in watchdogs.iter().enumerate() {
             context.set_caller(watchdog);
+            let enclave_type = if i % 2 == 0 { EnclaveType::IntelSGX } else { EnclaveType::AMDSEV };
             register_watchdog(
                 &mut context,
-                if i % 2 == 0 { EnclaveType::IntelSGX } else { EnclaveType::AMDSEV },
-                vec![0u8; 32],
+                enclave_type.clone(),
+                "watchdog-keep".to_string(),
+                attestation_report_for(enclave_type, "watchdog-keep"),
+                drawbridge_token_for("watchdog-keep"),
                 vec![0u8; 64],
             );
 
@@ -296,3 +332,415 @@ mod watchdog_pool_management {
         }
     }
 }
+
+mod watchdog_deregistration {
+    use super::*;
+    use crate::{error::Error, MIN_WATCHDOGS};
+
+    #[test]
+    fn test_reregistration_after_deregistration() {
+        let mut context = setup();
+        let tee = Address::from([8u8; 32]);
+
+        register_ready_tee_keep(&mut context, tee, EnclaveType::IntelSGX, "sgx-keep-old").unwrap();
+
+        deregister_watchdog(&mut context).unwrap();
+
+        // Residual per-address state is gone
+        assert!(context.get(KeepId(tee)).unwrap().is_none());
+        assert!(context.get(DrawbridgeToken(tee)).unwrap().is_none());
+        assert!(context.get(EnclaveType(tee)).unwrap().is_none());
+        assert!(context.get(AttestationStatus(tee)).unwrap().is_none());
+
+        // Re-join with fresh data
+        register_ready_tee_keep(&mut context, tee, EnclaveType::AMDSEV, "sev-keep-new").unwrap();
+
+        assert_eq!(context.get(KeepId(tee)).unwrap().unwrap(), "sev-keep-new");
+        assert_eq!(
+            context.get(DrawbridgeToken(tee)).unwrap().unwrap(),
+            drawbridge_token_for("sev-keep-new")
+        );
+        assert_eq!(context.get(EnclaveType(tee)).unwrap().unwrap(), EnclaveType::AMDSEV);
+
+        let pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert_eq!(pool.ready_tees.iter().filter(|(addr, _)| *addr == tee).count(), 1);
+    }
+
+    #[test]
+    fn test_watchdog_above_minimum_can_leave() {
+        let mut context = setup();
+        setup_full_system(&mut context);
+        let watchdog = Address::from([12u8; 32]);
+        register_watchdog_keep(&mut context, watchdog, EnclaveType::AMDSEV, "watchdog-keep");
+
+        context.set_caller(watchdog);
+        deregister_watchdog(&mut context).unwrap();
+
+        let pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert_eq!(pool.watchdogs.len(), MIN_WATCHDOGS);
+    }
+
+    #[test]
+    fn test_departing_watchdog_stake_refunded() {
+        let mut context = setup();
+        setup_full_system(&mut context);
+        setup_with_token_contract(&mut context);
+        let watchdog = Address::from([12u8; 32]);
+        register_watchdog_keep(&mut context, watchdog, EnclaveType::AMDSEV, "watchdog-keep");
+
+        context.set_caller(watchdog);
+        stake_tokens(&mut context, 1_000);
+        let before = get_token_balance(&mut context, watchdog);
+
+        deregister_watchdog(&mut context).unwrap();
+
+        assert_eq!(get_staked_amount(&mut context, watchdog), 0);
+        assert_eq!(context.get(TotalStaked()).unwrap().unwrap_or(0), 0);
+        assert_eq!(get_token_balance(&mut context, watchdog), before + 1_000);
+    }
+
+    #[test]
+    fn test_watchdog_at_minimum_cannot_leave() {
+        let mut context = setup();
+        let (_, _, watchdogs) = setup_full_system(&mut context);
+
+        context.set_caller(watchdogs[0]);
+        let result = deregister_watchdog(&mut context);
+
+        assert!(matches!(
+            result,
+            Err(Error::StateError(msg)) if msg == "watchdog pool at minimum size"
+        ));
+    }
+
+    #[test]
+    fn test_watchdog_cannot_leave_during_challenge() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdogs) = setup_full_system(&mut context);
+        let watchdog = Address::from([12u8; 32]);
+        register_watchdog_keep(&mut context, watchdog, EnclaveType::AMDSEV, "watchdog-keep");
+
+        context.set_caller(watchdogs[0]);
+        challenge_executors_batch(
+            &mut context,
+            vec![(sgx_executor, ChallengeType::Execution, vec![1u8; 32])],
+        )
+        .unwrap();
+
+        context.set_caller(watchdog);
+        let result = deregister_watchdog(&mut context);
+
+        assert!(matches!(
+            result,
+            Err(Error::ChallengeError(msg)) if msg == "challenge in progress"
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "not a registered watchdog")]
+    fn test_deregister_unknown_watchdog() {
+        let mut context = setup();
+
+        context.set_caller(Address::from([9u8; 32]));
+        deregister_watchdog(&mut context).unwrap();
+    }
+}
+
+mod watchdog_ready_pool {
+    use super::*;
+
+    #[test]
+    fn test_ready_tee_getters() {
+        let mut context = setup();
+        let sgx_tee = Address::from([10u8; 32]);
+        let sev_tee = Address::from([11u8; 32]);
+
+        register_ready_tee_keep(
+            &mut context,
+            sgx_tee,
+            EnclaveType::IntelSGX,
+            "sgx-keep-ready",
+        )
+        .unwrap();
+
+        register_ready_tee_keep(
+            &mut context,
+            sev_tee,
+            EnclaveType::AMDSEV,
+            "sev-keep-ready",
+        )
+        .unwrap();
+
+        let ready = get_ready_tees(&mut context).unwrap();
+        assert_eq!(ready.len(), 2);
+        assert!(ready.contains(&(sgx_tee, EnclaveType::IntelSGX)));
+        assert!(ready.contains(&(sev_tee, EnclaveType::AMDSEV)));
+
+        assert_eq!(get_ready_tee_count_by_type(&mut context, EnclaveType::IntelSGX).unwrap(), 1);
+        assert_eq!(get_ready_tee_count_by_type(&mut context, EnclaveType::AMDSEV).unwrap(), 1);
+    }
+}
+
+mod watchdog_pool_cap {
+    use super::*;
+    use crate::{error::Error, MIN_WATCHDOGS};
+
+    #[test]
+    fn test_registration_rejected_when_pool_full() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MaxWatchdogs(MIN_WATCHDOGS));
+
+        for i in 0..MIN_WATCHDOGS as u8 {
+            register_ready_tee_keep(
+                &mut context,
+                Address::from([i + 20; 32]),
+                EnclaveType::IntelSGX,
+                &format!("sgx-keep-{}", i),
+            )
+            .unwrap();
+        }
+
+        let result = register_ready_tee_keep(
+            &mut context,
+            Address::from([MIN_WATCHDOGS as u8 + 20; 32]),
+            EnclaveType::IntelSGX,
+            &format!("sgx-keep-{}", MIN_WATCHDOGS),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::StateError(msg)) if msg == "watchdog pool full"
+        ));
+        assert_eq!(get_ready_tees(&mut context).unwrap().len(), MIN_WATCHDOGS);
+    }
+
+    #[test]
+    #[should_panic(expected = "watchdog pool full")]
+    fn test_watchdog_registration_rejected_when_pool_full() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MaxWatchdogs(MIN_WATCHDOGS));
+        setup_full_system(&mut context);
+
+        register_watchdog_keep(
+            &mut context,
+            Address::from([30u8; 32]),
+            EnclaveType::AMDSEV,
+            "watchdog-keep-extra",
+        );
+    }
+
+    #[test]
+    fn test_raising_cap_admits_more() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MaxWatchdogs(MIN_WATCHDOGS));
+        for i in 0..MIN_WATCHDOGS as u8 {
+            register_ready_tee_keep(
+                &mut context,
+                Address::from([i + 20; 32]),
+                EnclaveType::IntelSGX,
+                &format!("sgx-keep-{}", i),
+            )
+            .unwrap();
+        }
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MaxWatchdogs(MIN_WATCHDOGS + 1));
+        register_ready_tee_keep(
+            &mut context,
+            Address::from([MIN_WATCHDOGS as u8 + 20; 32]),
+            EnclaveType::IntelSGX,
+            &format!("sgx-keep-{}", MIN_WATCHDOGS),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "watchdog cap below minimum")]
+    fn test_cap_below_minimum_rejected() {
+        let mut context = setup();
+
+        context.set_caller(governance_address());
+        update_system_config(&mut context, ConfigUpdate::MaxWatchdogs(MIN_WATCHDOGS - 1));
+    }
+}
+
+mod replacement_freshness {
+    use super::*;
+    use crate::{error::Error, ATTESTATION_VALIDITY_PERIOD};
+
+    #[test]
+    fn test_fresh_replacement_promoted() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        let tee = Address::from([20u8; 32]);
+        register_ready_tee_keep(&mut context, tee, EnclaveType::IntelSGX, "sgx-keep-ready")
+            .unwrap();
+
+        fail_executor(&mut context, sgx_executor);
+        replace_executor(&mut context, sgx_executor).unwrap();
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, Some(tee));
+    }
+
+    #[test]
+    fn test_stale_replacement_rejected() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        let tee = Address::from([20u8; 32]);
+        register_ready_tee_keep(&mut context, tee, EnclaveType::IntelSGX, "sgx-keep-ready")
+            .unwrap();
+
+        context.set_timestamp(context.timestamp() + ATTESTATION_VALIDITY_PERIOD + 1);
+
+        fail_executor(&mut context, sgx_executor);
+        let result = replace_executor(&mut context, sgx_executor);
+        assert!(matches!(result, Err(Error::InvalidAttestation)));
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, None);
+        let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert!(watchdog_pool.ready_tees.iter().any(|(addr, _)| *addr == tee));
+    }
+
+    #[test]
+    fn test_replacement_requires_governance_or_watchdog() {
+        let mut context = setup();
+        let (sgx_executor, sev_executor, _) = setup_system(&mut context);
+        register_ready_tee_keep(
+            &mut context,
+            Address::from([20u8; 32]),
+            EnclaveType::IntelSGX,
+            "sgx-keep-ready",
+        )
+        .unwrap();
+
+        fail_executor(&mut context, sgx_executor);
+        context.set_caller(sev_executor);
+        let result = replace_executor(&mut context, sgx_executor);
+        assert!(matches!(result, Err(Error::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_healthy_executor_not_replaced() {
+        let mut context = setup();
+        let (sgx_executor, _, watchdog) = setup_system(&mut context);
+        register_ready_tee_keep(
+            &mut context,
+            Address::from([20u8; 32]),
+            EnclaveType::IntelSGX,
+            "sgx-keep-ready",
+        )
+        .unwrap();
+
+        context.set_caller(watchdog);
+        assert!(replace_executor(&mut context, sgx_executor).is_err());
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, Some(sgx_executor));
+    }
+
+    #[test]
+    fn test_only_slot_holder_replaced() {
+        let mut context = setup();
+        setup_system(&mut context);
+        let tee = Address::from([20u8; 32]);
+        register_ready_tee_keep(&mut context, tee, EnclaveType::IntelSGX, "sgx-keep-ready")
+            .unwrap();
+
+        context.set_caller(governance_address());
+        let result = replace_executor(&mut context, tee);
+        assert!(matches!(result, Err(Error::ExecutorNotFound)));
+    }
+
+    #[test]
+    fn test_refilled_slot_not_replaced_again() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+        let tee = Address::from([20u8; 32]);
+        register_ready_tee_keep(&mut context, tee, EnclaveType::IntelSGX, "sgx-keep-ready")
+            .unwrap();
+
+        fail_executor(&mut context, sgx_executor);
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, None);
+
+        replace_executor(&mut context, sgx_executor).unwrap();
+        let result = replace_executor(&mut context, sgx_executor);
+        assert!(matches!(result, Err(Error::ExecutorNotFound)));
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, Some(tee));
+    }
+}
+
+mod replacement_ordering {
+    use super::*;
+
+    #[test]
+    fn test_replacement_prefers_fewest_misses_then_lowest_address() {
+        let mut context = setup();
+        let (sgx_executor, _, _) = setup_system(&mut context);
+
+        // Registered out of address order so pool position can't decide the outcome
+        let high = Address::from([30u8; 32]);
+        let low = Address::from([21u8; 32]);
+        let middle = Address::from([25u8; 32]);
+        register_ready_tee_keep(&mut context, high, EnclaveType::IntelSGX, "sgx-keep-30").unwrap();
+        register_ready_tee_keep(&mut context, low, EnclaveType::IntelSGX, "sgx-keep-21").unwrap();
+        register_ready_tee_keep(&mut context, middle, EnclaveType::IntelSGX, "sgx-keep-25")
+            .unwrap();
+        context.store_by_key(HeartbeatMisses(low), 1).unwrap();
+
+        fail_executor(&mut context, sgx_executor);
+        replace_executor(&mut context, sgx_executor).unwrap();
+
+        let executor_pool = context.get(ExecutorPool()).unwrap().unwrap();
+        assert_eq!(executor_pool.sgx_executor, Some(middle));
+        let watchdog_pool = context.get(WatchdogPool()).unwrap().unwrap();
+        assert!(watchdog_pool.ready_tees.iter().any(|(addr, _)| *addr == high));
+        assert!(watchdog_pool.ready_tees.iter().any(|(addr, _)| *addr == low));
+    }
+}
+
+mod replacement_history {
+    use super::*;
+
+    #[test]
+    fn test_replacement_history_read_in_pages() {
+        let mut context = setup();
+        let (mut current, _, _) = setup_system(&mut context);
+
+        let mut expected = Vec::new();
+        for byte in 40..45u8 {
+            let tee = Address::from([byte; 32]);
+            let keep_id = format!("sgx-keep-{}", byte);
+            register_ready_tee_keep(&mut context, tee, EnclaveType::IntelSGX, &keep_id).unwrap();
+
+            fail_executor(&mut context, current);
+            replace_executor(&mut context, current).unwrap();
+            expected.push((current, tee));
+            current = tee;
+        }
+
+        let mut history = Vec::new();
+        for offset in [0, 2, 4] {
+            let (page, total) = get_replacement_history_page(&mut context, offset, 2).unwrap();
+            assert_eq!(total, 5);
+            assert_eq!(page.len(), if offset == 4 { 1 } else { 2 });
+            history.extend(page);
+        }
+
+        let replaced: Vec<(Address, Address)> = history
+            .iter()
+            .map(|record| (record.failed_executor, record.replacement))
+            .collect();
+        assert_eq!(replaced, expected);
+        assert!(history.iter().all(|record| record.enclave_type == EnclaveType::IntelSGX));
+    }
+}
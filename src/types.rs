@@ -1,4 +1,24 @@
 use wasmlanche::Address;
+use crate::{
+    MAX_ACTIVE_CHALLENGES,
+    MIN_STAKE,
+    MIN_HEARTBEAT_SPACING,
+    MAX_WATCHDOGS,
+    MIN_WATCHDOGS,
+    MAX_ATTESTATION_REPORT_LEN,
+    ATTESTATION_VALIDITY_PERIOD,
+    MISMATCH_PAUSE_THRESHOLD,
+    MISMATCH_PAUSE_WINDOW,
+    CRASH_RECOVERY_COOLDOWN,
+    TIMEOUT_INTERVAL,
+    CHALLENGE_RESPONSE_WINDOW,
+    MAX_PAYLOAD_SIZE,
+    MISMATCH_SUSPENSION_THRESHOLD,
+    UPTIME_BASIS_POINTS,
+    MAX_CLOCK_SKEW,
+    MISMATCH_REMOVAL_THRESHOLD,
+    challenge::MIN_VERIFICATION_PROOFS,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum EnclaveType {
@@ -6,6 +26,17 @@ pub enum EnclaveType {
     AMDSEV,
 }
 
+impl EnclaveType {
+    /// Whether keeps on this backend can be live-migrated. SGX sealed state is
+    /// bound to the CPU it was sealed on, so SGX keeps must be cold-replaced.
+    pub fn supports_live_migration(&self) -> bool {
+        match self {
+            EnclaveType::IntelSGX => false,
+            EnclaveType::AMDSEV => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Phase {
     None,
@@ -16,12 +47,23 @@ pub enum Phase {
     Crashed,
 }
 
+/// On-chain lifecycle of a registered executor; only `Active` executors may submit results
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutorStatus {
+    Active,
+    Draining,
+    Failed,
+    Suspended,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ChallengeType {
     Attestation,
     Execution,
     StateVerification,
     HeartbeatMissed,
+    HealthDegradation,
+    WatchdogMisbehavior,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -33,15 +75,17 @@ pub enum ChallengeStatus {
     Expired,
 }
 
+/// Registry entry for a TEE operator. Keep ids and drawbridge tokens are
+/// tracked per executor address under `KeepId` and `DrawbridgeToken`.
 #[derive(Debug, Clone)]
 pub struct Operator {
-    initialized: bool,
-    keep_id: String,           // New: Enarx Keep identifier
-    attestation_report: Vec<u8>,
-    drawbridge_token: Vec<u8>, // New: Enarx attestation token
-    last_heartbeat: u64,
-    challenges_initiated: u64,
-    challenges_responded: u64,
+    pub initialized: bool,
+    pub tee_signature_address: String,
+    pub tee_encryption_key: Vec<u8>,
+    pub attestation_report: Vec<u8>,
+    pub last_heartbeat: u64,
+    pub challenges_initiated: u64,
+    pub challenges_responded: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -72,12 +116,87 @@ pub struct Challenge {
     pub verification_proofs: Vec<Vec<u8>>,
 }
 
+/// Pool state captured before `migrate_state` so a botched migration can be
+/// rolled back
+#[derive(Debug, Clone)]
+pub struct MigrationBackup {
+    pub version: u32,
+    pub executor_pool: ExecutorPool,
+    pub watchdog_pool: WatchdogPool,
+    pub taken_at: u64,
+}
+
+/// Point-in-time overview of the system for auditors and off-chain backups
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSummary {
+    pub phase: Phase,
+    pub sgx_executor: Option<Address>,
+    pub sev_executor: Option<Address>,
+    pub watchdogs: Vec<Address>,
+    pub challenge_count: u128,
+    pub verified_execution_count: usize,
+    pub state_root: Vec<u8>,
+}
+
+/// A precondition for entering `Phase::Executing` that isn't met yet
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionBlocker {
+    /// Executor registration only happens in `Creation`
+    NotInCreation(Phase),
+    /// The slot is empty or its registration still awaits co-signs
+    MissingExecutor(EnclaveType),
+    InsufficientWatchdogs { registered: usize, required: usize },
+}
+
+/// Readiness signal for load balancers and orchestrators
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemHealth {
+    pub phase: Phase,
+    /// Both executor slots hold an active, attested keep
+    pub executors_ready: bool,
+    /// The watchdog pool can reach a challenge quorum
+    pub watchdogs_sufficient: bool,
+    pub paused: bool,
+}
+
+/// Emitted when a challenge reaches a final status; the latest one per
+/// challenged address is also kept in state
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengeResolved {
+    pub challenge_id: u128,
+    pub challenged: Address,
+    pub challenge_type: ChallengeType,
+    pub outcome: ChallengeStatus,
+}
+
 #[derive(Debug, Clone)]
 pub struct ChallengeProof {
     pub challenge_id: u128,
     pub proof_ Vec<u8>,
     pub timestamp: u64,
     pub witness_signatures: Vec<(Address, Vec<u8>)>,
+    /// Responder's Drawbridge token, presented to answer attestation challenges
+    pub drawbridge_token: Vec<u8>,
+}
+
+/// Governance-approved parameters for moving to a new token contract
+#[derive(Debug, Clone)]
+pub struct TokenMigrationPlan {
+    /// Stakes, reserve and unclaimed rewards the plan was drawn up against;
+    /// must equal the ledger at execution time
+    pub expected_obligations: u64,
+    /// Receives the contract's old token balance left after stakes are
+    /// refunded to their stakers
+    pub old_balance_recipient: Address,
+}
+
+/// The contract's token balance split by ledger category
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceBreakdown {
+    pub staked: u64,
+    pub reserve: u64,
+    /// Credited to participants but not yet claimed
+    pub pending_rewards: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -114,11 +233,36 @@ pub struct Contract {
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExecutionResult {
     pub result_hash: Vec<u8>,      // Checksum of execution result
+    pub payload_hash: Vec<u8>,     // Hash of the input the executor ran
     pub execution_id: u128,        // Unique ID for this execution
     pub executor: Address,         // Address of executor
     pub enclave_type: EnclaveType,
     pub timestamp: u64,
     pub block_height: u64,
+    /// Time the executor attests it produced the result, if it reported one
+    pub executed_at: Option<u64>,
+}
+
+/// Everything a watchdog needs to vote on a mismatched execution. Each
+/// result carries its enclave type and block height.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MismatchEvidence {
+    pub execution_id: u128,
+    pub sgx_result: ExecutionResult,
+    pub sev_result: ExecutionResult,
+    /// Payload hash committed at allocation, or the SGX executor's reported
+    /// one if none was committed
+    pub payload_hash: Vec<u8>,
+}
+
+/// Known-input/known-output probe run against the executor pair
+#[derive(Debug, Clone)]
+pub struct DeterminismProbe {
+    pub payload_hash: Vec<u8>,
+    pub expected_hash: Vec<u8>,
+    pub sgx_matched: Option<bool>,
+    pub sev_matched: Option<bool>,
+    pub created_at: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -142,3 +286,378 @@ pub struct AttestationReport {
     pub enclave_type: EnclaveType,
     pub measurement: Vec<u8>,
 }
+
+/// Attestation evidence made of several quotes. Encoded reports start with
+/// `ATTESTATION_BUNDLE_MAGIC` followed by each component as a little-endian
+/// `u32` length and its bytes; any other report is a monolithic quote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestationBundle {
+    pub quote: Vec<u8>,
+    pub collateral: Vec<u8>,
+    pub tcb_info: Vec<u8>,
+}
+
+pub const ATTESTATION_BUNDLE_MAGIC: &[u8] = b"ATTB";
+
+impl AttestationBundle {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut encoded = ATTESTATION_BUNDLE_MAGIC.to_vec();
+        for component in [&self.quote, &self.collateral, &self.tcb_info] {
+            encoded.extend((component.len() as u32).to_le_bytes());
+            encoded.extend(component);
+        }
+        encoded
+    }
+
+    /// Splits an encoded bundle into its components, or `None` if the
+    /// report isn't a well-formed bundle
+    pub fn decode(report: &[u8]) -> Option<Self> {
+        let mut rest = report.strip_prefix(ATTESTATION_BUNDLE_MAGIC)?;
+        let mut components = Vec::with_capacity(3);
+        for _ in 0..3 {
+            let (len, tail) = rest.split_first_chunk::<4>()?;
+            let len = u32::from_le_bytes(*len) as usize;
+            if tail.len() < len {
+                return None;
+            }
+            components.push(tail[..len].to_vec());
+            rest = &tail[len..];
+        }
+        if !rest.is_empty() {
+            return None;
+        }
+
+        let tcb_info = components.pop()?;
+        let collateral = components.pop()?;
+        let quote = components.pop()?;
+        Some(Self { quote, collateral, tcb_info })
+    }
+
+    pub fn is_bundle(report: &[u8]) -> bool {
+        report.starts_with(ATTESTATION_BUNDLE_MAGIC)
+    }
+}
+
+/// Contract-wide tunables stored under a single key
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemConfig {
+    pub max_active_challenges: usize,
+    pub min_stake: u64,
+    /// Challenge quorum is `watchdogs * numerator / denominator + 1`
+    pub quorum_numerator: usize,
+    pub quorum_denominator: usize,
+    pub reward_policy: RewardPolicy,
+    /// Run-length encode stored verification proofs and mismatch hashes
+    pub compress_proofs: bool,
+    /// Minimum number of blocks between two heartbeats from one participant
+    pub min_heartbeat_spacing: u64,
+    /// Upper bound on watchdogs plus ready TEEs held in the watchdog pool
+    pub max_watchdogs: usize,
+    /// Keep only a SHA-256 commitment of Drawbridge tokens on-chain
+    pub hash_drawbridge_tokens: bool,
+    /// Largest attestation report accepted, in bytes
+    pub max_attestation_report_len: usize,
+    /// Require agreeing execution results from at least two enclave types
+    pub require_enclave_diversity: bool,
+    /// Seconds an SGX attestation stays fresh
+    pub sgx_attestation_validity: u64,
+    /// Seconds an SEV attestation stays fresh
+    pub sev_attestation_validity: u64,
+    /// Verification mismatches within `mismatch_pause_window` that pause the system
+    pub mismatch_pause_threshold: usize,
+    /// Rolling window, in seconds, over which mismatches are counted
+    pub mismatch_pause_window: u64,
+    pub stake_handoff_policy: StakeHandoffPolicy,
+    pub vote_tie_policy: VoteTiePolicy,
+    /// Seconds after a crash before governance may start recovery
+    pub crash_recovery_cooldown: u64,
+    /// Distinct watchdog witnesses a challenge proof must carry
+    pub min_proof_witnesses: usize,
+    /// Seconds a matching execution stays open to watchdog disputes before it
+    /// can be finalized; 0 finalizes matches immediately
+    pub execution_dispute_window: u64,
+    /// Seconds without a heartbeat before a participant is considered offline
+    pub timeout_interval: u64,
+    /// Seconds a challenged party has to respond to a new challenge
+    pub challenge_response_window: u64,
+    /// Challenge both executors on a verification mismatch; when off, the
+    /// mismatch is only recorded and flagged for off-chain arbitration
+    pub auto_challenge_on_mismatch: bool,
+    /// Largest payload an SGX keep accepts, in bytes
+    pub sgx_max_payload_size: usize,
+    /// Largest payload an SEV keep accepts, in bytes
+    pub sev_max_payload_size: usize,
+    /// Consecutive lost mismatches after which an executor is suspended
+    pub mismatch_suspension_threshold: u64,
+    /// Reward multiplier steps as `(min_uptime, multiplier)` pairs, both in
+    /// basis points and sorted by uptime; empty pays every share in full
+    pub uptime_multipliers: Vec<(u64, u64)>,
+    /// Largest gap, in seconds, between the executor-attested times of two
+    /// matching results before the match is flagged as clock-desynchronized
+    pub max_clock_skew: u64,
+    /// Mismatch strike at which an executor pair is challenged; earlier
+    /// strikes only warn and request re-execution
+    pub mismatch_challenge_threshold: u64,
+    /// Mismatch strike from which the executor a tiebreak finds at fault is
+    /// removed
+    pub mismatch_removal_threshold: u64,
+    /// Watchdog co-signs an executor registration needs before activating;
+    /// 0 activates executors as soon as they register
+    pub registration_cosigns: usize,
+    pub reward_cadence: RewardCadence,
+    pub verification_mode: VerificationMode,
+}
+
+impl SystemConfig {
+    /// Attestation validity period for the given enclave type
+    pub fn attestation_validity(&self, enclave_type: &EnclaveType) -> u64 {
+        match enclave_type {
+            EnclaveType::IntelSGX => self.sgx_attestation_validity,
+            EnclaveType::AMDSEV => self.sev_attestation_validity,
+        }
+    }
+
+    /// Multiplier, in basis points, earned at the given uptime ratio. Uptime
+    /// below the lowest step earns nothing.
+    pub fn uptime_multiplier(&self, uptime: u64) -> u64 {
+        if self.uptime_multipliers.is_empty() {
+            return UPTIME_BASIS_POINTS;
+        }
+        self.uptime_multipliers
+            .iter()
+            .rev()
+            .find(|(min_uptime, _)| uptime >= *min_uptime)
+            .map_or(0, |(_, multiplier)| *multiplier)
+    }
+
+    /// Payload size limit for keeps of the given enclave type
+    pub fn max_payload_size(&self, enclave_type: &EnclaveType) -> usize {
+        match enclave_type {
+            EnclaveType::IntelSGX => self.sgx_max_payload_size,
+            EnclaveType::AMDSEV => self.sev_max_payload_size,
+        }
+    }
+
+    /// Panics unless every field is usable, e.g. a zero quorum denominator
+    /// or timeout interval would later divide by zero. Checked whenever a
+    /// config is stored, at init and on governance updates.
+    pub fn validate(&self) {
+        assert!(self.max_active_challenges > 0, "challenge limit must be positive");
+        assert!(
+            self.quorum_denominator > 0 && self.quorum_numerator <= self.quorum_denominator,
+            "invalid quorum"
+        );
+        assert!(
+            self.reward_policy.total_shares > 0
+                && self.reward_policy.executor_share + self.reward_policy.watchdog_share
+                    <= self.reward_policy.total_shares,
+            "invalid reward policy"
+        );
+        assert!(self.max_watchdogs >= MIN_WATCHDOGS, "watchdog cap below minimum");
+        assert!(self.max_attestation_report_len > 0, "attestation report limit must be positive");
+        assert!(
+            self.sgx_attestation_validity > 0 && self.sev_attestation_validity > 0,
+            "attestation validity must be positive"
+        );
+        assert!(
+            self.mismatch_pause_threshold > 0 && self.mismatch_pause_window > 0,
+            "invalid mismatch pause policy"
+        );
+        assert!(self.min_proof_witnesses > 0, "witness requirement must be positive");
+        assert!(self.timeout_interval > 0, "timeout interval must be positive");
+        assert!(self.challenge_response_window > 0, "response window must be positive");
+        assert!(
+            self.sgx_max_payload_size > 0 && self.sev_max_payload_size > 0,
+            "payload size limit must be positive"
+        );
+        assert!(self.mismatch_suspension_threshold > 0, "suspension threshold must be positive");
+        assert!(
+            self.uptime_multipliers.windows(2).all(|pair| pair[0].0 < pair[1].0)
+                && self.uptime_multipliers.iter().all(|(min_uptime, multiplier)| {
+                    *min_uptime <= UPTIME_BASIS_POINTS && *multiplier <= UPTIME_BASIS_POINTS
+                }),
+            "invalid uptime multipliers"
+        );
+        assert!(
+            self.mismatch_challenge_threshold > 0
+                && self.mismatch_challenge_threshold <= self.mismatch_removal_threshold,
+            "invalid mismatch escalation"
+        );
+        assert!(
+            !matches!(
+                self.reward_cadence,
+                RewardCadence::VerifiedExecutions(0) | RewardCadence::Blocks(0)
+            ),
+            "invalid reward cadence"
+        );
+    }
+}
+
+/// One executor replacement, as appended to the replacement log
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplacementRecord {
+    pub failed_executor: Address,
+    pub replacement: Address,
+    pub enclave_type: EnclaveType,
+    pub timestamp: u64,
+}
+
+/// An executor registration waiting for watchdog co-signs before it takes
+/// its slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingExecutor {
+    pub enclave_type: EnclaveType,
+    pub keep_id: String,
+    pub measurement: Vec<u8>,
+    pub drawbridge_token: Vec<u8>,
+    pub requested_at: u64,
+    /// Watchdogs that have co-signed, in signing order
+    pub cosigners: Vec<Address>,
+}
+
+/// Parameter area a governance decision touches; each may be governed by its
+/// own contract
+#[derive(Debug, Clone, PartialEq)]
+pub enum GovernanceDomain {
+    /// Stakes, rewards and token parameters
+    Economic,
+    /// Attestation, challenge and membership parameters
+    Security,
+}
+
+/// Role an address currently holds in the executor and watchdog pools
+#[derive(Debug, Clone, PartialEq)]
+pub enum Role {
+    Executor(EnclaveType),
+    Watchdog(EnclaveType),
+    None,
+}
+
+/// How a challenge vote split evenly between approval and rejection resolves
+#[derive(Debug, Clone, PartialEq)]
+pub enum VoteTiePolicy {
+    /// Treat a tie as a failed challenge response
+    FailClosed,
+    /// Treat a tie as a verified challenge response
+    FailOpen,
+}
+
+/// When rewards are distributed without an explicit `distribute_rewards` call
+#[derive(Debug, Clone, PartialEq)]
+pub enum RewardCadence {
+    /// Only on an explicit call
+    Manual,
+    /// After every N verified executions
+    VerifiedExecutions(u64),
+    /// On the first verified execution at least M blocks after the last
+    /// automatic distribution
+    Blocks(u64),
+}
+
+/// How attestation reports are checked before a keep is trusted
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationMode {
+    /// Accept a monolithic quote or a multi-quote bundle
+    AcceptMonolithic,
+    /// Require a bundle carrying the quote, collateral and TCB info
+    RequireBundle,
+}
+
+/// What happens to a replaced executor's stake
+#[derive(Debug, Clone, PartialEq)]
+pub enum StakeHandoffPolicy {
+    /// Forfeit the stake to the reward reserve for redistribution
+    SlashAndRedistribute,
+    /// Move the stake to the replacement to back its bond
+    TransferToReplacement,
+}
+
+/// Shares of distributable rewards; the remainder goes to the reserve
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardPolicy {
+    pub executor_share: u64,
+    pub watchdog_share: u64,
+    pub total_shares: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfigUpdate {
+    MaxActiveChallenges(usize),
+    MinStake(u64),
+    Quorum { numerator: usize, denominator: usize },
+    RewardPolicy(RewardPolicy),
+    CompressProofs(bool),
+    MinHeartbeatSpacing(u64),
+    MaxWatchdogs(usize),
+    HashDrawbridgeTokens(bool),
+    MaxAttestationReportLen(usize),
+    RequireEnclaveDiversity(bool),
+    AttestationValidity { enclave_type: EnclaveType, period: u64 },
+    MismatchPause { threshold: usize, window: u64 },
+    StakeHandoffPolicy(StakeHandoffPolicy),
+    VoteTiePolicy(VoteTiePolicy),
+    CrashRecoveryCooldown(u64),
+    MinProofWitnesses(usize),
+    ExecutionDisputeWindow(u64),
+    TimeoutInterval(u64),
+    ChallengeResponseWindow(u64),
+    AutoChallengeOnMismatch(bool),
+    MaxPayloadSize { enclave_type: EnclaveType, size: usize },
+    MismatchSuspensionThreshold(u64),
+    UptimeMultipliers(Vec<(u64, u64)>),
+    MaxClockSkew(u64),
+    MismatchEscalation { challenge_after: u64, remove_after: u64 },
+    RegistrationCosigns(usize),
+    RewardCadence(RewardCadence),
+    VerificationMode(VerificationMode),
+}
+
+impl Default for SystemConfig {
+    fn default() -> Self {
+        Self {
+            max_active_challenges: MAX_ACTIVE_CHALLENGES,
+            min_stake: MIN_STAKE,
+            quorum_numerator: 2,
+            quorum_denominator: 3,
+            reward_policy: RewardPolicy::default(),
+            compress_proofs: false,
+            min_heartbeat_spacing: MIN_HEARTBEAT_SPACING,
+            max_watchdogs: MAX_WATCHDOGS,
+            hash_drawbridge_tokens: false,
+            max_attestation_report_len: MAX_ATTESTATION_REPORT_LEN,
+            require_enclave_diversity: true,
+            sgx_attestation_validity: ATTESTATION_VALIDITY_PERIOD,
+            sev_attestation_validity: ATTESTATION_VALIDITY_PERIOD,
+            mismatch_pause_threshold: MISMATCH_PAUSE_THRESHOLD,
+            mismatch_pause_window: MISMATCH_PAUSE_WINDOW,
+            stake_handoff_policy: StakeHandoffPolicy::SlashAndRedistribute,
+            vote_tie_policy: VoteTiePolicy::FailClosed,
+            crash_recovery_cooldown: CRASH_RECOVERY_COOLDOWN,
+            min_proof_witnesses: MIN_VERIFICATION_PROOFS,
+            execution_dispute_window: 0,
+            timeout_interval: TIMEOUT_INTERVAL,
+            challenge_response_window: CHALLENGE_RESPONSE_WINDOW,
+            auto_challenge_on_mismatch: true,
+            sgx_max_payload_size: MAX_PAYLOAD_SIZE,
+            sev_max_payload_size: MAX_PAYLOAD_SIZE,
+            mismatch_suspension_threshold: MISMATCH_SUSPENSION_THRESHOLD,
+            uptime_multipliers: Vec::new(),
+            max_clock_skew: MAX_CLOCK_SKEW,
+            mismatch_challenge_threshold: 1,
+            mismatch_removal_threshold: MISMATCH_REMOVAL_THRESHOLD,
+            registration_cosigns: 0,
+            reward_cadence: RewardCadence::Manual,
+            verification_mode: VerificationMode::AcceptMonolithic,
+        }
+    }
+}
+
+impl Default for RewardPolicy {
+    fn default() -> Self {
+        Self {
+            executor_share: 1,
+            watchdog_share: 1,
+            total_shares: 3,
+        }
+    }
+}